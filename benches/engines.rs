@@ -0,0 +1,41 @@
+use conway_game_of_life::bench::{game, ENGINES};
+use conway_game_of_life::bitboard::BitMatrix;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const SIZES: [usize; 3] = [100, 1000, 4000];
+
+fn engines(c: &mut Criterion) {
+    for size in SIZES {
+        let mut group = c.benchmark_group(format!("tick/{size}x{size}"));
+        group.throughput(Throughput::Elements((size * size) as u64));
+        if size >= 1000 {
+            group.sample_size(10);
+        }
+
+        for (name, engine_args) in ENGINES {
+            let mut game = game(size, 0, engine_args);
+            group.bench_function(BenchmarkId::from_parameter(name), |b| {
+                b.iter(|| game.tick())
+            });
+        }
+
+        // packed boards ticked without unpacking them every generation
+        let matrix = Matrix::random(size, size, 0.5, &mut StdRng::seed_from_u64(0));
+        let (mut bits, mut next) = (BitMatrix::from_matrix(&matrix), BitMatrix::new(size, size));
+        group.bench_function(BenchmarkId::from_parameter("bit-matrix"), |b| {
+            b.iter(|| {
+                bits.step(&Rule::CONWAY, &mut next);
+                std::mem::swap(&mut bits, &mut next);
+            })
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, engines);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // the C header of the ffi module, for programs linking the library
+    #[cfg(feature = "cdylib")]
+    {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").expect("manifest dir exception");
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        let mut config = cbindgen::Config::default();
+        config.usize_is_size_t = true;
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{dir}/src/ffi.rs"))
+            .with_language(cbindgen::Language::C)
+            .with_include_guard("CONWAY_GAME_OF_LIFE_H")
+            .generate()
+            .expect("header generation exception")
+            .write_to_file(format!("{dir}/include/conway_game_of_life.h"));
+    }
+}
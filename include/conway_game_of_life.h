@@ -0,0 +1,62 @@
+#ifndef CONWAY_GAME_OF_LIFE_H
+#define CONWAY_GAME_OF_LIFE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A game behind a C pointer.
+ */
+typedef struct GolGame GolGame;
+
+/**
+ * A random board of `rows` by `cols` cells under Conway's rule, each alive
+ * with probability `fill`, 0 for an empty board. The game must be released
+ * with `gol_free`.
+ */
+struct GolGame *gol_new(size_t rows, size_t cols, uint64_t seed, double fill);
+
+/**
+ * Runs `ticks` generations.
+ *
+ * # Safety
+ *
+ * `game` must come from `gol_new` and not be freed yet.
+ */
+void gol_tick(struct GolGame *game, size_t ticks);
+
+/**
+ * Brings the cell at `(row, col)` to life, or kills it, if it lies on the
+ * board.
+ *
+ * # Safety
+ *
+ * `game` must come from `gol_new` and not be freed yet.
+ */
+void gol_set_cell(struct GolGame *game, size_t row, size_t col, bool alive);
+
+/**
+ * Pointer to the first cell, 0 for dead and 1 for live cells, row after
+ * row, valid until the next call on the game. The size of the board is
+ * written to `rows` and `cols` unless they are null.
+ *
+ * # Safety
+ *
+ * `game` must come from `gol_new` and not be freed yet, `rows` and `cols`
+ * must be null or valid to write to.
+ */
+const uint8_t *gol_cells(const struct GolGame *game, size_t *rows, size_t *cols);
+
+/**
+ * Releases a game, ignoring null.
+ *
+ * # Safety
+ *
+ * `game` must be null or come from `gol_new` and not be freed yet.
+ */
+void gol_free(struct GolGame *game);
+
+#endif /* CONWAY_GAME_OF_LIFE_H */
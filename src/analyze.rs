@@ -0,0 +1,699 @@
+//! Census of the objects on a board, in the manner of soup statistics:
+//! the board is split into separate objects, each object is run on its own
+//! to tell still lifes, oscillators and spaceships apart, and well-known
+//! objects are named in any phase and orientation. Objects are also given
+//! their apgcode, the name Catagolue, the census of soups run by apgsearch,
+//! counts them under.
+
+use clap::Args;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+
+use crate::edge::Edge;
+use crate::game_of_life::{GameOfLife, GameOfLifeArgs};
+use crate::matrix::Matrix;
+use crate::padded::PaddedMatrix;
+use crate::patterns;
+use crate::rule::Rule;
+
+/// Live cells at most this many cells apart belong to the same object, they
+/// are close enough to affect each other's next generation.
+const OBJECT_DISTANCE: usize = 2;
+
+/// Objects that do not repeat within this many generations are active.
+const MAX_PERIOD: usize = 64;
+
+/// How far the center of an object may move in one generation and still be
+/// taken for the same object, a phase change can shift it further than the
+/// one cell per generation the object itself moves at most.
+const MAX_STEP: f64 = 2.0;
+
+/// Built-in patterns recognized by name, in Conway's Game of Life.
+const KNOWN_OBJECTS: &[&str] = &[
+    "block",
+    "beehive",
+    "loaf",
+    "boat",
+    "ship",
+    "tub",
+    "pond",
+    "blinker",
+    "toad",
+    "beacon",
+    "pulsar",
+    "pentadecathlon",
+    "glider",
+    "lwss",
+    "mwss",
+    "hwss",
+];
+
+/// Digits of the extended Wechsler format, each five cells of a column.
+const WECHSLER_DIGITS: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+
+/// Digits counting blank columns after a `y`, from 4 blank columns on.
+const BLANK_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Objects larger than this in both directions in every phase have no
+/// Wechsler code, like in apgsearch.
+const MAX_CODE_SIZE: usize = 40;
+
+/// apgcode of the glider.
+pub const GLIDER_APGCODE: &str = "xq4_153";
+
+#[derive(Args, Debug)]
+pub struct AnalyzeArgs {
+    #[command(flatten)]
+    game: GameOfLifeArgs,
+    /// Number of generations to run before taking the census
+    #[arg(long, default_value_t = 0)]
+    ticks: usize,
+    /// List every object with its position, not just the census
+    #[arg(long)]
+    list: bool,
+    /// Follow the objects for this many more generations and report the ones
+    /// that moved, best a multiple of their periods such as 120
+    #[arg(long)]
+    track: Option<usize>,
+    /// Count the objects by apgcode and print the census in the layout of a Catagolue haul
+    #[arg(long)]
+    catagolue: bool,
+}
+
+/// How an object behaves when left on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Kind {
+    StillLife,
+    Oscillator {
+        period: usize,
+    },
+    /// Repeats its shape `period` generations later, moved by `shift` rows and columns.
+    Spaceship {
+        period: usize,
+        shift: (isize, isize),
+    },
+    /// Dies out or does not repeat within a few dozen generations.
+    Active,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::StillLife => write!(f, "still life"),
+            Kind::Oscillator { period } => write!(f, "p{period} oscillator"),
+            Kind::Spaceship { period, shift } => {
+                write!(f, "{} spaceship", Velocity::new(*shift, *period))
+            }
+            Kind::Active => write!(f, "active object"),
+        }
+    }
+}
+
+/// How far an object moved in a number of generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Velocity {
+    pub rows: isize,
+    pub cols: isize,
+    pub ticks: usize,
+}
+
+impl Velocity {
+    pub fn new((rows, cols): (isize, isize), ticks: usize) -> Self {
+        Velocity { rows, cols, ticks }
+    }
+
+    /// The speed as a fraction of the speed of light, one cell per
+    /// generation, in lowest terms.
+    pub fn speed(&self) -> (usize, usize) {
+        let cells = self.rows.unsigned_abs().max(self.cols.unsigned_abs());
+        let divisor = gcd(cells, self.ticks).max(1);
+        (cells / divisor, self.ticks / divisor)
+    }
+
+    /// Whether it moves along a row or column, a diagonal or neither.
+    pub fn slope(&self) -> &'static str {
+        match (self.rows, self.cols) {
+            (0, _) | (_, 0) => "orthogonal",
+            (rows, cols) if rows.abs() == cols.abs() => "diagonal",
+            _ => "oblique",
+        }
+    }
+
+    /// The compass direction it moves in, north being up.
+    pub fn direction(&self) -> &'static str {
+        match (self.rows.signum(), self.cols.signum()) {
+            (-1, -1) => "north-west",
+            (-1, 0) => "north",
+            (-1, _) => "north-east",
+            (0, -1) => "west",
+            (0, 0) => "nowhere",
+            (0, _) => "east",
+            (_, -1) => "south-west",
+            (_, 0) => "south",
+            _ => "south-east",
+        }
+    }
+}
+
+impl Display for Velocity {
+    /// The speed and slope, e.g. `c/4 diagonal` or `2c/5 orthogonal`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.speed() {
+            (1, 1) => write!(f, "c")?,
+            (1, ticks) => write!(f, "c/{ticks}")?,
+            (cells, 1) => write!(f, "{cells}c")?,
+            (cells, ticks) => write!(f, "{cells}c/{ticks}")?,
+        }
+        write!(f, " {}", self.slope())
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// A group of live cells standing apart from the rest of the board.
+#[derive(Clone)]
+pub struct Object {
+    /// Name of the well-known object it is, if any.
+    pub name: Option<&'static str>,
+    pub kind: Kind,
+    /// Its apgcode, e.g. `xs4_33` for a block, see `apgcode`.
+    pub apgcode: String,
+    /// Top-left corner of its bounding box on the board.
+    pub row: usize,
+    pub col: usize,
+    /// Its live cells, cropped to its bounding box.
+    pub cells: Matrix,
+}
+
+impl Object {
+    /// The name it is counted under in a census, e.g. `glider` or `p3 oscillator`.
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(name) => name.to_string(),
+            None => self.kind.to_string(),
+        }
+    }
+
+    /// The center of its live cells on the board.
+    pub fn centroid(&self) -> (f64, f64) {
+        centroid(self.row, self.col, &self.cells)
+    }
+}
+
+/// An object followed over a number of generations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track {
+    /// What the object was counted as in the last generation.
+    pub label: String,
+    /// Centers of the object in the first and last generation, relative to
+    /// the top-left cell of the initial board.
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+    pub velocity: Velocity,
+}
+
+/// Number of objects of every label.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Census {
+    counts: BTreeMap<String, usize>,
+}
+
+impl Census {
+    pub fn new(objects: &[Object]) -> Self {
+        let mut counts = BTreeMap::new();
+        for object in objects {
+            *counts.entry(object.label()).or_default() += 1;
+        }
+        Census { counts }
+    }
+
+    /// Number of objects of every apgcode, as Catagolue counts them.
+    pub fn apgcodes(objects: &[Object]) -> Self {
+        let mut census = Census::default();
+        for object in objects {
+            census.add(&object.apgcode, 1);
+        }
+        census
+    }
+
+    pub fn count(&self, label: &str) -> usize {
+        self.counts.get(label).copied().unwrap_or(0)
+    }
+
+    /// Counts `count` more objects labelled `label`.
+    pub fn add(&mut self, label: &str, count: usize) {
+        if count > 0 {
+            *self.counts.entry(label.to_string()).or_default() += count;
+        }
+    }
+
+    /// Adds the counts of `other`, e.g. to sum up the census of many soups.
+    pub fn merge(&mut self, other: &Census) {
+        for (label, &count) in &other.counts {
+            self.add(label, count);
+        }
+    }
+
+    /// Number of objects counted.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Labels and counts, the most common first.
+    pub fn entries(&self) -> Vec<(&str, usize)> {
+        let mut entries: Vec<(&str, usize)> = self
+            .counts
+            .iter()
+            .map(|(label, &count)| (label.as_str(), count))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        entries
+    }
+}
+
+impl Display for Census {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, count) in self.entries() {
+            writeln!(f, "{count:>6} {label}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A census in the layout of the hauls apgsearch submits to Catagolue: a
+/// header of `@` lines with the rule, the symmetry and number of soups if
+/// taken over soups, and the number of objects, then the census table of
+/// apgcodes and counts, the most common first. The version, root and
+/// sample soups of a real haul are left out, Catagolue only takes hauls
+/// from apgsearch itself, but census tables can be compared line by line.
+pub struct Haul<'a> {
+    pub rule: &'a Rule,
+    /// Symmetry of the soups in Catagolue's notation, e.g. `C1`.
+    pub symmetry: Option<&'a str>,
+    pub soups: Option<u64>,
+    pub census: &'a Census,
+}
+
+impl Display for Haul<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "@RULE {}", catagolue_rule(self.rule))?;
+        if let Some(symmetry) = self.symmetry {
+            writeln!(f, "@SYMMETRY {symmetry}")?;
+        }
+        if let Some(soups) = self.soups {
+            writeln!(f, "@NUM_SOUPS {soups}")?;
+        }
+        writeln!(f, "@NUM_OBJECTS {}", self.census.total())?;
+        writeln!(f)?;
+        writeln!(f, "@CENSUS TABLE")?;
+        for (apgcode, count) in self.census.entries() {
+            writeln!(f, "{apgcode} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The rule as Catagolue writes it, e.g. `b3s23`.
+pub fn catagolue_rule(rule: &Rule) -> String {
+    rule.to_string().to_lowercase().replace('/', "")
+}
+
+/// The apgcode of an object of kind `kind` under `rule`: `xs` and the
+/// population for still lifes, `xp` or `xq` and the period for oscillators
+/// and spaceships, then an underscore and the cells in the extended
+/// Wechsler format, in the phase and orientation giving the shortest code,
+/// the first in alphabetical order among equally short ones. Objects too
+/// large for a code are `ov_` and their kind, active objects are
+/// `PATHOLOGICAL`.
+pub fn apgcode(cells: &Matrix, kind: Kind, rule: &Rule) -> String {
+    let (prefix, phases) = match kind {
+        Kind::StillLife => (format!("xs{}", cells.count_live()), 1),
+        Kind::Oscillator { period } => (format!("xp{period}"), period),
+        Kind::Spaceship { period, .. } => (format!("xq{period}"), period),
+        Kind::Active => return "PATHOLOGICAL".to_string(),
+    };
+
+    let rule = rule.with_edge(Edge::Dead);
+    let mut best: Option<String> = None;
+    let mut phase = cells.clone();
+    for _ in 0..phases {
+        if phase.row_count() <= MAX_CODE_SIZE && phase.col_count() <= MAX_CODE_SIZE {
+            for orientation in orientations(&phase) {
+                let code = wechsler(&orientation);
+                let better = best
+                    .as_ref()
+                    .is_none_or(|best| (code.len(), &code) < (best.len(), best));
+                if better {
+                    best = Some(code);
+                }
+            }
+        }
+        phase = step(&phase, &rule).0;
+    }
+
+    match best {
+        Some(code) => format!("{prefix}_{code}"),
+        None => format!("ov_{}", &prefix[1..]),
+    }
+}
+
+/// The cells in the extended Wechsler format: strips of five rows separated
+/// by `z`, each a digit per column with the top cell in the lowest bit, runs
+/// of blank columns shortened to `0`, `w`, `x`, or `y` and their number
+/// less four, and the blank columns at the end of a strip left out.
+fn wechsler(cells: &Matrix) -> String {
+    let (rows, cols) = (cells.row_count(), cells.col_count());
+    let mut code = String::new();
+    for strip in 0..rows.div_ceil(5) {
+        if strip > 0 {
+            code.push('z');
+        }
+        let mut blanks = 0;
+        for col in 0..cols {
+            let digit = (0..5)
+                .map(|bit| (strip * 5 + bit, bit))
+                .filter(|&(row, _)| row < rows && cells.get(row, col) != 0)
+                .fold(0, |digit, (_, bit)| digit | 1 << bit);
+            if digit == 0 {
+                blanks += 1;
+                continue;
+            }
+            match blanks {
+                0 => {}
+                1 => code.push('0'),
+                2 => code.push('w'),
+                3 => code.push('x'),
+                // no wider than `MAX_CODE_SIZE`, so at most 38 blanks
+                _ => {
+                    code.push('y');
+                    code.push(char::from(BLANK_DIGITS[blanks - 4]));
+                }
+            }
+            blanks = 0;
+            code.push(char::from(WECHSLER_DIGITS[digit]));
+        }
+    }
+    code
+}
+
+/// The objects on the board, top to bottom and left to right, classified
+/// by running each of them on its own under `rule` with dead edges. Objects
+/// are only named under Conway's rule.
+pub fn objects(matrix: &Matrix, rule: &Rule) -> Vec<Object> {
+    let rule = rule.with_edge(Edge::Dead);
+    components(matrix)
+        .into_iter()
+        .map(|(row, col, cells)| {
+            let kind = classify(&cells, &rule);
+            let name = if rule.is_conway() {
+                known_objects().get(&cells).copied()
+            } else {
+                None
+            };
+            Object {
+                name,
+                kind,
+                apgcode: apgcode(&cells, kind, &rule),
+                row,
+                col,
+                cells,
+            }
+        })
+        .collect()
+}
+
+/// Ticks the game for `ticks` generations, following every object by the
+/// center of its live cells, and returns the objects that moved. Objects
+/// that die or collide are lost. Velocities are only exact when `ticks` is
+/// a multiple of the period of an object.
+pub fn moving_objects(game: &mut GameOfLife, ticks: usize) -> Vec<Track> {
+    let centroids = |game: &GameOfLife| -> Vec<(f64, f64)> {
+        let origin = game.origin();
+        components(game.matrix())
+            .iter()
+            .map(|(row, col, cells)| {
+                let (row, col) = centroid(*row, *col, cells);
+                (row - origin.0 as f64, col - origin.1 as f64)
+            })
+            .collect()
+    };
+
+    // the first and latest center of every object still followed
+    let mut tracks: Vec<((f64, f64), (f64, f64))> = centroids(game)
+        .into_iter()
+        .map(|center| (center, center))
+        .collect();
+    for _ in 0..ticks {
+        game.tick();
+        let mut next = centroids(game);
+        tracks.retain_mut(|(_, latest)| {
+            let nearest = next
+                .iter()
+                .enumerate()
+                .map(|(idx, center)| (idx, distance(*latest, *center)))
+                .filter(|&(_, distance)| distance <= MAX_STEP)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            match nearest {
+                Some((idx, _)) => {
+                    *latest = next.swap_remove(idx);
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
+    let origin = game.origin();
+    let objects = objects(game.matrix(), game.rule());
+    tracks
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let shift = (
+                (end.0 - start.0).round() as isize,
+                (end.1 - start.1).round() as isize,
+            );
+            let object = objects.iter().find(|object| {
+                let (row, col) = object.centroid();
+                (row - origin.0 as f64, col - origin.1 as f64) == end
+            })?;
+            (shift != (0, 0)).then(|| Track {
+                label: object.label(),
+                start,
+                end,
+                velocity: Velocity::new(shift, ticks),
+            })
+        })
+        .collect()
+}
+
+/// Runs the game for `--ticks` generations and prints the census of the
+/// final board, with `--list` preceded by every object. With `--track` the
+/// objects are then followed and the moving ones reported.
+pub fn run(args: &AnalyzeArgs) {
+    let mut game = GameOfLife::from_args(&args.game);
+    game.set_history(0);
+    for _ in 0..args.ticks {
+        game.tick();
+    }
+
+    let objects = objects(game.matrix(), game.rule());
+    if args.list {
+        for object in &objects {
+            let label = match args.catagolue {
+                true => object.apgcode.clone(),
+                false => object.label(),
+            };
+            println!("{label} at {},{}", object.row, object.col);
+        }
+    }
+    if args.catagolue {
+        let haul = Haul {
+            rule: game.rule(),
+            symmetry: None,
+            soups: None,
+            census: &Census::apgcodes(&objects),
+        };
+        print!("{haul}");
+    } else {
+        print!("{}", Census::new(&objects));
+        println!(
+            "{} objects, {} live cells at tick {}",
+            objects.len(),
+            game.matrix().count_live(),
+            game.ticks()
+        );
+    }
+
+    if let Some(ticks) = args.track {
+        let tracks = moving_objects(&mut game, ticks);
+        for track in &tracks {
+            println!(
+                "{} from {:.1},{:.1} to {:.1},{:.1}: {} {}",
+                track.label,
+                track.start.0,
+                track.start.1,
+                track.end.0,
+                track.end.1,
+                track.velocity,
+                track.velocity.direction()
+            );
+        }
+        println!("{} moving objects over {ticks} ticks", tracks.len());
+    }
+}
+
+/// Splits the live cells into groups of cells connected through cells at
+/// most `OBJECT_DISTANCE` apart, each as its top-left corner and its cells.
+fn components(matrix: &Matrix) -> Vec<(usize, usize, Matrix)> {
+    let (rows, cols) = (matrix.row_count(), matrix.col_count());
+    let mut seen = Matrix::<bool>::new(rows, cols);
+    let mut found = Vec::new();
+
+    for start in 0..rows * cols {
+        let (row, col) = matrix.inverse_idx(start);
+        if matrix.get(row, col) == 0 || seen.get(row, col) {
+            continue;
+        }
+
+        seen.set(row, col, true);
+        let mut cells = vec![(row, col)];
+        let mut next = 0;
+        while let Some(&(row, col)) = cells.get(next) {
+            next += 1;
+            let near_rows =
+                row.saturating_sub(OBJECT_DISTANCE)..(row + OBJECT_DISTANCE + 1).min(rows);
+            for near_row in near_rows {
+                let near_cols =
+                    col.saturating_sub(OBJECT_DISTANCE)..(col + OBJECT_DISTANCE + 1).min(cols);
+                for near_col in near_cols {
+                    if matrix.get(near_row, near_col) != 0 && !seen.get(near_row, near_col) {
+                        seen.set(near_row, near_col, true);
+                        cells.push((near_row, near_col));
+                    }
+                }
+            }
+        }
+
+        let top = cells.iter().map(|&(row, _)| row).min().unwrap_or(row);
+        let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(col);
+        let bottom = cells.iter().map(|&(row, _)| row).max().unwrap_or(row);
+        let right = cells.iter().map(|&(_, col)| col).max().unwrap_or(col);
+        let mut object = Matrix::new(bottom - top + 1, right - left + 1);
+        for (row, col) in cells {
+            object.set(row - top, col - left, 1);
+        }
+        found.push((top, left, object));
+    }
+
+    found
+}
+
+/// The center of the live cells of an object whose top-left corner is at `(row, col)`.
+fn centroid(row: usize, col: usize, cells: &Matrix) -> (f64, f64) {
+    let live = cells
+        .as_slice()
+        .iter()
+        .enumerate()
+        .filter(|(_, &cell)| cell != 0)
+        .map(|(idx, _)| cells.inverse_idx(idx));
+    let (mut rows, mut cols, mut count) = (0.0, 0.0, 0.0);
+    for (cell_row, cell_col) in live {
+        rows += cell_row as f64;
+        cols += cell_col as f64;
+        count += 1.0;
+    }
+    (row as f64 + rows / count, col as f64 + cols / count)
+}
+
+/// Chebyshev distance, the number of generations light takes between two points.
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+/// Runs an object on its own until it takes on its initial shape again.
+fn classify(cells: &Matrix, rule: &Rule) -> Kind {
+    let mut phase = cells.clone();
+    let mut shift = (0, 0);
+
+    for period in 1..=MAX_PERIOD {
+        let (next, moved) = step(&phase, rule);
+        if next.row_count() == 0 {
+            return Kind::Active;
+        }
+        shift = (shift.0 + moved.0, shift.1 + moved.1);
+        phase = next;
+
+        if phase == *cells {
+            return match shift {
+                (0, 0) if period == 1 => Kind::StillLife,
+                (0, 0) => Kind::Oscillator { period },
+                _ => Kind::Spaceship { period, shift },
+            };
+        }
+    }
+
+    Kind::Active
+}
+
+/// The next generation of an object cropped to its live cells, and how far
+/// its top-left corner moved.
+pub fn step(cells: &Matrix, rule: &Rule) -> (Matrix, (isize, isize)) {
+    let expanded = cells.expand(1, 1, 1, 1);
+    let mut padded = PaddedMatrix::new(expanded.row_count(), expanded.col_count());
+    padded.load(&expanded, Edge::Dead);
+
+    let mut next = Matrix::new(expanded.row_count(), expanded.col_count());
+    for row in 0..next.row_count() {
+        for col in 0..next.col_count() {
+            next.set(row, col, padded.next_state(row, col, rule));
+        }
+    }
+
+    let moved = next.bounding_box().map_or((0, 0), |bounds| {
+        (bounds.row as isize - 1, bounds.col as isize - 1)
+    });
+    (next.crop_to_bounding_box(), moved)
+}
+
+/// Every phase of every known object in all eight orientations, by name.
+fn known_objects() -> &'static HashMap<Matrix, &'static str> {
+    static KNOWN: OnceLock<HashMap<Matrix, &'static str>> = OnceLock::new();
+    KNOWN.get_or_init(|| {
+        let mut known = HashMap::new();
+        for &name in KNOWN_OBJECTS {
+            let pattern = patterns::find(name).expect("known objects are built-in patterns");
+            let first = pattern.matrix();
+            let mut phase = first.clone();
+            for _ in 0..MAX_PERIOD {
+                for orientation in orientations(&phase) {
+                    known.insert(orientation, name);
+                }
+                phase = step(&phase, &Rule::CONWAY).0;
+                if phase == first {
+                    break;
+                }
+            }
+        }
+        known
+    })
+}
+
+/// The object in all eight orientations, turned and mirrored.
+fn orientations(cells: &Matrix) -> Vec<Matrix> {
+    let mut orientations = Vec::with_capacity(8);
+    for orientation in [cells.clone(), cells.flip_h()] {
+        let mut turned = orientation;
+        for _ in 0..4 {
+            turned = turned.rotate90();
+            orientations.push(turned.clone());
+        }
+    }
+    orientations
+}
@@ -0,0 +1,186 @@
+use clap::{Args, ValueEnum};
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::events::EventWriter;
+use crate::format::STDIO;
+use crate::game_of_life::{GameOfLife, GameOfLifeArgs};
+use crate::rle;
+use crate::shutdown;
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    #[command(flatten)]
+    game: GameOfLifeArgs,
+    /// Number of generations to run for, fewer if the simulation stops earlier
+    #[arg(long, default_value_t = 1000)]
+    ticks: usize,
+    /// Initial board as json, RLE, plaintext, macrocell, MCell or Life 1.06 file, - for stdin, overrides initial_file
+    #[arg(long = "in")]
+    input: Option<PathBuf>,
+    /// File the final board is written to in the format its extension suggests, RLE on stdout if not set or -
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Also write the final board as an SVG image to this file, in the style of --cell-size, the colors and the stroke options
+    #[arg(long)]
+    svg_out: Option<PathBuf>,
+    /// What is printed to stdout: the final board, unless written with --out, or a JSON line per generation
+    #[arg(long, value_enum, default_value_t = Output::Board)]
+    output: Output,
+    /// Add the cells that changed since the previous generation as RLE to the JSON lines of --output jsonl
+    #[arg(long, default_value_t = false)]
+    output_changes: bool,
+    /// Do not print the summary statistics
+    #[arg(long, default_value_t = false)]
+    quiet: bool,
+}
+
+/// What a batch run prints to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// The final board as RLE
+    Board,
+    /// One JSON object per generation with its tick, population, births,
+    /// deaths and bounding box
+    Jsonl,
+}
+
+/// How a batch run ended, told apart by its exit status so shell scripts can
+/// branch on it. Failures exit with 1 or, on panics, 101.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Neither settled nor repeated by the time it ran all its generations
+    /// or its time, or its stop condition held.
+    MaxTicks,
+    /// Settled into a still life other than the empty board.
+    Stabilized,
+    /// No live cells are left.
+    DiedOut,
+    /// Started repeating with a period above 1.
+    Cycle,
+    /// Stopped by Ctrl-C or SIGTERM before it got to its end.
+    Interrupted,
+}
+
+impl Outcome {
+    /// The outcome of `game` after its run, from the cycle it is tracked
+    /// to have entered whether or not `--stop-on-cycle` ended the run there.
+    pub fn of(game: &GameOfLife) -> Self {
+        if shutdown::requested() {
+            return Outcome::Interrupted;
+        }
+        if game.stats().latest().population == 0 {
+            return Outcome::DiedOut;
+        }
+        match game.cycle() {
+            Some(cycle) if cycle.period == 1 => Outcome::Stabilized,
+            Some(_) => Outcome::Cycle,
+            None => Outcome::MaxTicks,
+        }
+    }
+
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Outcome::MaxTicks => 0,
+            Outcome::Stabilized => 2,
+            Outcome::DiedOut => 3,
+            Outcome::Cycle => 4,
+            // like shells report commands killed by SIGINT
+            Outcome::Interrupted => 130,
+        }
+    }
+}
+
+/// Runs the simulation without rendering and writes out the final board and
+/// a summary. The summary goes to stderr when the board is written to stdout.
+/// Exits with the status of the outcome of the run.
+pub fn run(run_args: &RunArgs) -> ExitCode {
+    let mut game = match &run_args.input {
+        Some(path) => GameOfLife::from_args(&run_args.game.with_initial_file(path)),
+        None => GameOfLife::from_args(&run_args.game),
+    };
+    // nothing steps backwards in a batch run
+    game.set_history(0);
+    if run_args.output == Output::Jsonl {
+        let mut events = EventWriter::new(io::stdout(), run_args.output_changes, game.matrix());
+        events
+            .write(game.stats().latest(), game.matrix())
+            .expect("io exception");
+        // the run ends once the reader of the events goes away
+        game.on_tick(
+            move |generation, matrix| match events.write(generation, matrix) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(_) => ControlFlow::Break(()),
+            },
+        );
+    }
+    // Ctrl-C ends the run like running out of ticks, with the board written
+    shutdown::catch_signals().expect("signal exception");
+    let (first_tick, started) = (game.ticks(), Instant::now());
+    let max_ticks = first_tick + run_args.ticks;
+    game.run_until(|game| game.is_finished() || game.ticks() >= max_ticks || shutdown::requested());
+    let elapsed = started.elapsed();
+
+    let to_stdout = match &run_args.out {
+        Some(path) => {
+            game.matrix()
+                .save_with_rule(path, game.rule())
+                .expect("io exception");
+            path == Path::new(STDIO)
+        }
+        None if run_args.output == Output::Board => {
+            print!("{}", rle::encode_with_rule(game.matrix(), game.rule()));
+            true
+        }
+        None => false,
+    };
+    if let Some(path) = &run_args.svg_out {
+        game.render_svg(path).expect("io exception");
+    }
+
+    let outcome = ExitCode::from(Outcome::of(&game).exit_code());
+    if run_args.quiet {
+        return outcome;
+    }
+
+    let summary = summary(&game, game.ticks() - first_tick, elapsed);
+    if to_stdout || run_args.output == Output::Jsonl {
+        eprint!("{summary}");
+    } else {
+        print!("{summary}");
+    }
+    io::stdout().flush().expect("io exception");
+    outcome
+}
+
+/// The figures of a finished run of `ticks` generations that took
+/// `elapsed`, and why it finished.
+pub fn summary(game: &GameOfLife, ticks: usize, elapsed: Duration) -> String {
+    let mut summary = format!(
+        "ticks: {}\nsize: {} x {}\n{}\n",
+        game.ticks(),
+        game.matrix().row_count(),
+        game.matrix().col_count(),
+        game.stats()
+    );
+    if game.metrics().ticks() > 0 {
+        summary.push_str(&format!("pace: {}\n", game.metrics()));
+    }
+    summary.push_str(&format!("elapsed: {:.2} s", elapsed.as_secs_f64()));
+    if !elapsed.is_zero() {
+        let ticks_per_sec = ticks as f64 / elapsed.as_secs_f64();
+        summary.push_str(&format!(", {ticks_per_sec:.1} ticks/s on average"));
+    }
+    summary.push('\n');
+
+    if let Some(reason) = game.stop_reason() {
+        summary.push_str(&format!("stopped: {reason}\n"));
+    } else if shutdown::requested() {
+        summary.push_str("stopped: interrupted\n");
+    }
+
+    summary
+}
@@ -0,0 +1,171 @@
+use clap::{Args, Parser};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::game_of_life::{self, GameOfLife, GameOfLifeArgs};
+
+/// Engines compared by benchmarks, with the options selecting them.
+pub const ENGINES: [(&str, &[&str]); 10] = [
+    ("serial", &["--engine", "serial"]),
+    ("rayon", &["--engine", "rayon"]),
+    ("rayon-chunked", &["--engine", "rayon-chunked"]),
+    ("threads", &["--engine", "threads"]),
+    ("simd", &["--engine", "simd"]),
+    ("incremental", &["--engine", "incremental"]),
+    ("rayon-tiles", &["--engine", "rayon-tiles"]),
+    ("tiled", &["--engine", "tiled"]),
+    ("lut", &["--engine", "lut"]),
+    ("bit-parallel", &["--engine", "bit-parallel"]),
+];
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Widths and heights of the random boards, separated by commas
+    #[arg(long, value_delimiter = ',', default_values_t = [256, 1024])]
+    sizes: Vec<usize>,
+    /// Number of generations each engine runs on each board
+    #[arg(long, default_value_t = 50)]
+    ticks: usize,
+    /// Seed of the random boards, the same for every engine
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Stop each engine on each board after this many seconds, fractions allowed, measuring the generations it got through
+    #[arg(long, value_parser = game_of_life::parse_seconds)]
+    max_seconds: Option<Duration>,
+    /// Print the measurements as JSON instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+/// How fast an engine ticked a board.
+#[derive(Debug, Clone, Serialize)]
+pub struct Measurement {
+    pub engine: &'static str,
+    pub size: usize,
+    /// Generations run, fewer than asked for if the time limit ran out.
+    pub ticks: usize,
+    pub seconds: f64,
+}
+
+impl Measurement {
+    pub fn generations_per_sec(&self) -> f64 {
+        self.ticks as f64 / self.seconds
+    }
+
+    pub fn cells_per_sec(&self) -> f64 {
+        (self.size * self.size) as f64 * self.generations_per_sec()
+    }
+}
+
+/// A random game of `size` by `size` cells ticked by the engine `engine_args` select.
+pub fn game(size: usize, seed: u64, engine_args: &[&str]) -> GameOfLife {
+    let size = size.to_string();
+    let seed = seed.to_string();
+    // keeping past generations would measure cloning rather than ticking
+    let args = [
+        "conway-game-of-life",
+        "--rows",
+        &size,
+        "--cols",
+        &size,
+        "--seed",
+        &seed,
+        "--history",
+        "0",
+    ]
+    .into_iter()
+    .chain(engine_args.iter().copied());
+    GameOfLife::from_args(&GameOfLifeArgs::parse_from(args))
+}
+
+/// Ticks a random board of `size` by `size` cells `ticks` times with the
+/// engine `engine_args` select, or as many times as fit in `max_seconds`.
+pub fn measure(
+    engine: &'static str,
+    engine_args: &[&str],
+    size: usize,
+    ticks: usize,
+    max_seconds: Option<Duration>,
+    seed: u64,
+) -> Measurement {
+    let mut game = game(size, seed, engine_args);
+    let first = game.ticks();
+    game.set_max_ticks(Some(first.saturating_add(ticks)));
+    game.set_max_seconds(max_seconds);
+
+    let start = Instant::now();
+    while !game.is_finished() {
+        game.tick();
+    }
+
+    Measurement {
+        engine,
+        size,
+        ticks: game.ticks() - first,
+        seconds: start.elapsed().as_secs_f64(),
+    }
+}
+
+pub fn run(args: &BenchArgs) {
+    let measurements: Vec<Measurement> = args
+        .sizes
+        .iter()
+        .flat_map(|&size| {
+            ENGINES.iter().map(move |&(name, engine_args)| {
+                measure(
+                    name,
+                    engine_args,
+                    size,
+                    args.ticks,
+                    args.max_seconds,
+                    args.seed,
+                )
+            })
+        })
+        .collect();
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&measurements).expect("json encode exception")
+        );
+    } else {
+        print!("{}", report(&measurements));
+    }
+}
+
+/// A table of the generations per second of every engine on every board
+/// size, each with its speedup over the serial engine.
+pub fn report(measurements: &[Measurement]) -> String {
+    let mut sizes: Vec<usize> = measurements.iter().map(|m| m.size).collect();
+    sizes.dedup();
+    let find = |engine: &str, size: usize| {
+        measurements
+            .iter()
+            .find(|m| m.engine == engine && m.size == size)
+    };
+
+    let mut report = format!("{:<14}", "engine");
+    for size in &sizes {
+        report += &format!(" {:>22}", format!("{size}x{size} gen/s"));
+    }
+    report.push('\n');
+
+    for (engine, _) in ENGINES {
+        report += &format!("{engine:<14}");
+        for &size in &sizes {
+            let cell = match (find(engine, size), find("serial", size)) {
+                (Some(m), Some(serial)) => format!(
+                    "{:.1} ({:.2}x)",
+                    m.generations_per_sec(),
+                    m.generations_per_sec() / serial.generations_per_sec()
+                ),
+                (Some(m), None) => format!("{:.1}", m.generations_per_sec()),
+                (None, _) => "-".to_string(),
+            };
+            report += &format!(" {cell:>22}");
+        }
+        report.push('\n');
+    }
+    report
+}
@@ -0,0 +1,335 @@
+//! Boards packed one bit per cell, 64 cells to a `u64`, ticked 64 cells at a
+//! time: the eight neighbors of every cell of a word are the word and the
+//! words above and below it shifted by one column, summed bit by bit with
+//! full adders into the four bits of each cell's neighbor count.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edge::Edge;
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// Number of cells of a word.
+const WORD_BITS: usize = 64;
+
+/// A board of one bit per cell, row after row, each row a whole number of
+/// words with bit `i` of word `k` the cell in column `64 * k + i`. Bits
+/// beyond the last column are always clear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    /// Words per row.
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let stride = cols.div_ceil(WORD_BITS);
+        BitMatrix {
+            rows,
+            cols,
+            stride,
+            words: vec![0; rows * stride],
+        }
+    }
+
+    pub fn from_matrix(matrix: &Matrix) -> Self {
+        let mut bits = BitMatrix::new(matrix.row_count(), matrix.col_count());
+        bits.load(matrix);
+        bits
+    }
+
+    pub fn to_matrix(&self) -> Matrix {
+        let mut matrix = Matrix::new(self.rows, self.cols);
+        self.store(&mut matrix);
+        matrix
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.cols
+    }
+
+    /// The words of row `row`.
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.stride..(row + 1) * self.stride]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        debug_assert!(row < self.rows && col < self.cols);
+        (self.words[row * self.stride + col / WORD_BITS] >> (col % WORD_BITS) & 1) as u8
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, val: u8) {
+        debug_assert!(row < self.rows && col < self.cols);
+        let word = &mut self.words[row * self.stride + col / WORD_BITS];
+        let bit = 1 << (col % WORD_BITS);
+        match val & 1 {
+            0 => *word &= !bit,
+            _ => *word |= bit,
+        }
+    }
+
+    /// Packs the cells of `matrix`, which must have the same size.
+    pub fn load(&mut self, matrix: &Matrix) {
+        assert_eq!(
+            (matrix.row_count(), matrix.col_count()),
+            (self.rows, self.cols)
+        );
+        let stride = self.stride;
+        for (words, cells) in self.words.chunks_exact_mut(stride).zip(matrix.rows()) {
+            for (word, cells) in words.iter_mut().zip(cells.chunks(WORD_BITS)) {
+                *word = pack(cells);
+            }
+        }
+    }
+
+    /// Unpacks the cells into `matrix`, which must have the same size.
+    pub fn store(&self, matrix: &mut Matrix) {
+        assert_eq!(
+            (matrix.row_count(), matrix.col_count()),
+            (self.rows, self.cols)
+        );
+        let cols = self.cols.max(1);
+        let rows = matrix.as_mut_slice().chunks_exact_mut(cols);
+        for (cells, words) in rows.zip(self.words.chunks_exact(self.stride)) {
+            for (cells, word) in cells.chunks_mut(WORD_BITS).zip(words) {
+                for (bit, cell) in cells.iter_mut().enumerate() {
+                    *cell = (word >> bit & 1) as u8;
+                }
+            }
+        }
+    }
+
+    /// Mask of the columns of the last word of a row.
+    fn last_mask(&self) -> u64 {
+        match self.cols % WORD_BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    /// The row beyond the top edge, if `row` is -1, or the bottom edge, if
+    /// it is `rows`, in edge mode `edge`, or else the row itself.
+    fn row_or_ghost<'a>(&'a self, row: isize, edge: Edge, ghosts: &'a [Vec<u64>; 2]) -> &'a [u64] {
+        let last = self.rows as isize - 1;
+        match (row, edge) {
+            (0.., _) if row <= last => self.row(row as usize),
+            (_, Edge::Dead) => &ghosts[0],
+            (_, Edge::Alive) => &ghosts[1],
+            (_, Edge::Wrap) => self.row(row.rem_euclid(self.rows as isize) as usize),
+            (_, Edge::Mirror) => self.row(row.clamp(0, last) as usize),
+        }
+    }
+
+    /// Computes the next generation under `rule` into `next`, which must
+    /// have the same size.
+    pub fn step(&self, rule: &Rule, next: &mut BitMatrix) {
+        assert_eq!((next.rows, next.cols), (self.rows, self.cols));
+        if self.rows == 0 || self.cols == 0 {
+            return;
+        }
+
+        let last_mask = self.last_mask();
+        let mut alive_row = vec![!0; self.stride];
+        *alive_row.last_mut().unwrap() = last_mask;
+        let ghosts = [vec![0; self.stride], alive_row];
+        let counts = Counts::new(rule);
+
+        for row in 0..self.rows {
+            let above = self.row_or_ghost(row as isize - 1, rule.edge, &ghosts);
+            let center = self.row(row);
+            let below = self.row_or_ghost(row as isize + 1, rule.edge, &ghosts);
+            let (above, below) = (
+                Shifted::new(above, self, rule.edge),
+                Shifted::new(below, self, rule.edge),
+            );
+            let center_shifted = Shifted::new(center, self, rule.edge);
+
+            let words = &mut next.words[row * self.stride..(row + 1) * self.stride];
+            for (k, word) in words.iter_mut().enumerate() {
+                let neighbors = [
+                    above.left(k),
+                    above.words[k],
+                    above.right(k),
+                    center_shifted.left(k),
+                    center_shifted.right(k),
+                    below.left(k),
+                    below.words[k],
+                    below.right(k),
+                ];
+                *word = counts.next(center[k], neighbors);
+            }
+            *words.last_mut().unwrap() &= last_mask;
+        }
+    }
+}
+
+/// Packs up to 64 cells into a word, eight at a time by multiplying their
+/// bytes, each 0 or 1, into the top byte of the product.
+fn pack(cells: &[u8]) -> u64 {
+    const ONES: u64 = u64::from_le_bytes([1; 8]);
+    const GATHER: u64 = 0x0102_0408_1020_4080;
+
+    let mut word = 0;
+    let mut chunks = cells.chunks_exact(8);
+    for (index, chunk) in chunks.by_ref().enumerate() {
+        let bytes = u64::from_le_bytes(chunk.try_into().unwrap()) & ONES;
+        word |= (bytes.wrapping_mul(GATHER) >> 56) << (index * 8);
+    }
+    let start = cells.len() / 8 * 8;
+    for (bit, &cell) in chunks.remainder().iter().enumerate() {
+        word |= u64::from(cell & 1) << (start + bit);
+    }
+    word
+}
+
+/// A row with the cells beyond its left and right edges, to shift it by a
+/// column either way.
+struct Shifted<'a> {
+    words: &'a [u64],
+    /// The cells beyond the first and the last column.
+    left_ghost: u64,
+    right_ghost: u64,
+    /// Bit of the last column in the last word.
+    last_bit: usize,
+}
+
+impl<'a> Shifted<'a> {
+    fn new(words: &'a [u64], board: &BitMatrix, edge: Edge) -> Self {
+        let last_bit = (board.cols - 1) % WORD_BITS;
+        let first = words[0] & 1;
+        let last = words[words.len() - 1] >> last_bit & 1;
+        let (left_ghost, right_ghost) = match edge {
+            Edge::Dead => (0, 0),
+            Edge::Alive => (1, 1),
+            Edge::Wrap => (last, first),
+            Edge::Mirror => (first, last),
+        };
+        Shifted {
+            words,
+            left_ghost,
+            right_ghost,
+            last_bit,
+        }
+    }
+
+    /// Word `k` of the west neighbors of the cells.
+    #[inline]
+    fn left(&self, k: usize) -> u64 {
+        let carry = match k {
+            0 => self.left_ghost,
+            _ => self.words[k - 1] >> (WORD_BITS - 1),
+        };
+        self.words[k] << 1 | carry
+    }
+
+    /// Word `k` of the east neighbors of the cells.
+    #[inline]
+    fn right(&self, k: usize) -> u64 {
+        // bits beyond the last column are clear, so the ghost is simply
+        // added at the last column
+        match self.words.get(k + 1) {
+            Some(next) => self.words[k] >> 1 | next << (WORD_BITS - 1),
+            None => self.words[k] >> 1 | self.right_ghost << self.last_bit,
+        }
+    }
+}
+
+/// The neighbor counts of a rule at which cells are born or survive.
+struct Counts {
+    birth: u16,
+    survival: u16,
+}
+
+impl Counts {
+    fn new(rule: &Rule) -> Self {
+        Counts {
+            birth: rule.birth_mask(),
+            survival: rule.survival_mask(),
+        }
+    }
+
+    /// Next states of the 64 cells of `alive` with the eight words of
+    /// their neighbors.
+    #[inline]
+    fn next(&self, alive: u64, [n0, n1, n2, n3, n4, n5, n6, n7]: [u64; 8]) -> u64 {
+        // bit by bit, the ones, twos, fours and eights of the counts
+        let (a1, a2) = full_add(n0, n1, n2);
+        let (b1, b2) = full_add(n3, n4, n5);
+        let (c1, c2) = (n6 ^ n7, n6 & n7);
+        let (ones, d2) = full_add(a1, b1, c1);
+        let (e2, e4) = full_add(a2, b2, c2);
+        let (twos, f4) = (e2 ^ d2, e2 & d2);
+        let (fours, eights) = (e4 ^ f4, e4 & f4);
+
+        let mut next = 0;
+        for count in 0..=8 {
+            let born = self.birth >> count & 1 != 0;
+            let survives = self.survival >> count & 1 != 0;
+            if !born && !survives {
+                continue;
+            }
+            let bit = |word: u64, weight: usize| match count & weight {
+                0 => !word,
+                _ => word,
+            };
+            let matches = bit(ones, 1) & bit(twos, 2) & bit(fours, 4) & bit(eights, 8);
+            next |= matches
+                & match (born, survives) {
+                    (true, true) => !0,
+                    (true, false) => !alive,
+                    _ => alive,
+                };
+        }
+        next
+    }
+}
+
+/// The sum and carry bits of adding three words bit by bit.
+#[inline]
+fn full_add(a: u64, b: u64, c: u64) -> (u64, u64) {
+    let partial = a ^ b;
+    (partial ^ c, a & b | partial & c)
+}
+
+/// 64 cells per word on copies of the board packed as [`BitMatrix`]es.
+pub struct BitParallel {
+    current: BitMatrix,
+    next: BitMatrix,
+}
+
+impl BitParallel {
+    pub fn new() -> Self {
+        BitParallel {
+            current: BitMatrix::new(0, 0),
+            next: BitMatrix::new(0, 0),
+        }
+    }
+}
+
+impl Default for BitParallel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickEngine for BitParallel {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        let (rows, cols) = (src.row_count(), src.col_count());
+        if (self.current.rows, self.current.cols) != (rows, cols) {
+            self.current = BitMatrix::new(rows, cols);
+            self.next = BitMatrix::new(rows, cols);
+        }
+        self.current.load(src);
+        self.current.step(rule, &mut self.next);
+        self.next.store(dst);
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::edge::Edge;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+#[cfg(feature = "native")]
+use crate::snapshot;
+
+/// Version written into new checkpoints. Checkpoints of any version up to
+/// this one can be loaded, fields added in later versions must have defaults.
+pub const CHECKPOINT_VERSION: u32 = 2;
+
+/// The first bytes of a checkpoint saved as a compressed snapshot, see the
+/// `snapshot` module.
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"GOLZ";
+
+/// The state of a simulation as saved to a checkpoint file.
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub version: u32,
+    pub ticks: usize,
+    pub seed: u64,
+    pub edge: Edge,
+    /// Added in version 2, older checkpoints are Conway's Game of Life.
+    #[serde(default)]
+    pub rule: Rule,
+    pub unbounded: bool,
+    /// Position of the initial top-left cell within `matrix`.
+    pub origin: (usize, usize),
+    pub matrix: Matrix,
+}
+
+impl Checkpoint {
+    /// Saves the checkpoint as JSON, or as a compressed snapshot if the
+    /// file name ends in `.snap`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path)?);
+        #[cfg(feature = "native")]
+        if path.extension().and_then(|ext| ext.to_str()) == Some(snapshot::EXTENSION) {
+            return snapshot::write(self, writer);
+        }
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Loads a checkpoint saved as JSON or as a compressed snapshot,
+    /// whatever the file is named.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        if reader.fill_buf()?.starts_with(&SNAPSHOT_MAGIC) {
+            #[cfg(feature = "native")]
+            return snapshot::read(reader);
+            #[cfg(not(feature = "native"))]
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "compressed snapshots need the native feature",
+            ));
+        }
+        serde_json::from_reader::<_, Checkpoint>(reader)?.check_version()
+    }
+
+    /// Parses a checkpoint from its JSON text.
+    pub fn parse(text: &str) -> io::Result<Self> {
+        serde_json::from_str::<Checkpoint>(text)?.check_version()
+    }
+
+    fn check_version(self) -> io::Result<Self> {
+        if self.version > CHECKPOINT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "checkpoint version {} is newer than the supported version {}",
+                    self.version, CHECKPOINT_VERSION
+                ),
+            ));
+        }
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,83 @@
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::analyze::AnalyzeArgs;
+use crate::batch::RunArgs;
+use crate::bench::BenchArgs;
+use crate::config::Config;
+use crate::convert::ConvertArgs;
+use crate::demo::DemoArgs;
+use crate::diff::DiffArgs;
+use crate::distributed::DistributedArgs;
+use crate::reverse::ReverseArgs;
+use crate::search::SoupSearchArgs;
+use crate::still::StillSearchArgs;
+use crate::tui::ViewArgs;
+use crate::verify::VerifyArgs;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// TOML or YAML file of default options, YAML if its name ends in .yaml or .yml
+    #[arg(long, global = true, env = crate::config::CONFIG_ENV)]
+    config: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+impl Cli {
+    /// Parses the command line, taking options it lacks from `GAMEOFLIFE_*`
+    /// environment variables and then from the config file.
+    pub fn parse_with_config() -> Self {
+        let args: Vec<OsString> = env::args_os().collect();
+        let config = match Config::path(&args) {
+            Some(path) => Config::load(path).expect("config exception"),
+            None => Config::default(),
+        };
+
+        let command = config
+            .apply(<Cli as CommandFactory>::command())
+            .expect("config exception");
+        let matches = command.get_matches_from(args);
+        Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit())
+    }
+
+    /// The config file the options were read from, if any.
+    pub fn config(&self) -> Option<&Path> {
+        self.config.as_deref()
+    }
+
+    pub fn command(&self) -> &Command {
+        &self.command
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Show the simulation in the terminal, interactively
+    View(ViewArgs),
+    /// Run the simulation without rendering and write out the final board
+    Run(RunArgs),
+    /// Convert a board between the json, RLE, plaintext, macrocell, MCell and Life 1.06 formats
+    Convert(ConvertArgs),
+    /// Measure how fast each engine ticks a random board
+    Bench(BenchArgs),
+    /// Run random soups and report the ones that live long, grow large or emit gliders
+    Search(SoupSearchArgs),
+    /// Try every board of small boxes, or random soups, for still lifes and oscillators and print each found as RLE
+    SearchStill(StillSearchArgs),
+    /// Tick the same initial board with two engines and report where they first diverge
+    Verify(VerifyArgs),
+    /// Split the board into objects and count the still lifes, oscillators and spaceships among them
+    Analyze(AnalyzeArgs),
+    /// Tick one horizontal strip of the board in each of several processes, which swap their boundary rows over TCP
+    Distributed(DistributedArgs),
+    /// Tour famous patterns of the built-in library, each shown running for a few seconds under a caption
+    Demo(DemoArgs),
+    /// Compare two saved boards cell by cell and show the cells born and died between them
+    Diff(DiffArgs),
+    /// Find a board that turns into a small pattern in one generation, or prove the pattern a Garden of Eden (experimental)
+    Reverse(ReverseArgs),
+}
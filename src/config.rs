@@ -0,0 +1,147 @@
+//! Options read from a TOML or YAML file and from `GAMEOFLIFE_*` environment
+//! variables, for repeated experiments. Any long option of a subcommand can
+//! be set, by its name with either dashes or underscores:
+//!
+//! ```toml
+//! rows = 64
+//! cols = 64
+//! rule = "B36/S23"
+//! edge = "wrap"
+//! engine = "rayon"
+//! alive_color = "#00ff00"
+//! place = ["glider@5,5", "blinker@20,20"]
+//! ```
+//!
+//! Options given on the command line take precedence over environment
+//! variables, which take precedence over the file.
+
+use clap::Command;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the config file, like `--config`.
+pub const CONFIG_ENV: &str = "GAMEOFLIFE_CONFIG";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Config {
+    options: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    /// Every value of an option given several times.
+    List(Vec<Value>),
+}
+
+impl Value {
+    /// The values as they would be written on the command line.
+    fn to_args(&self) -> Vec<String> {
+        match self {
+            Value::Bool(value) => vec![value.to_string()],
+            Value::Integer(value) => vec![value.to_string()],
+            Value::Float(value) => vec![value.to_string()],
+            Value::Text(value) => vec![value.clone()],
+            Value::List(values) => values.iter().flat_map(Value::to_args).collect(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads a YAML file if its name ends in `.yaml` or `.yml`, TOML otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let yaml = matches!(
+            path.extension().and_then(|extension| extension.to_str()),
+            Some("yaml" | "yml")
+        );
+
+        let config: Config = if yaml {
+            serde_yaml::from_str(&text).map_err(|err| invalid_data(path, err))?
+        } else {
+            toml::from_str(&text).map_err(|err| invalid_data(path, err))?
+        };
+        Ok(config)
+    }
+
+    /// The config file named by `--config` in `args` or else by the
+    /// environment, looked up before the command line is parsed since the
+    /// file changes how it is parsed.
+    pub fn path(args: &[OsString]) -> Option<PathBuf> {
+        let mut args = args.iter().map(|arg| arg.to_str());
+        while let Some(arg) = args.next() {
+            match arg {
+                Some("--config") => return args.next().flatten().map(PathBuf::from),
+                Some(arg) if arg.starts_with("--config=") => {
+                    return Some(PathBuf::from(&arg["--config=".len()..]))
+                }
+                _ => {}
+            }
+        }
+        std::env::var_os(CONFIG_ENV).map(PathBuf::from)
+    }
+
+    /// `command` whose subcommand options default to the settings of the
+    /// file and are also read from environment variables, e.g.
+    /// `GAMEOFLIFE_FILL_PROB` for `--fill-prob`. Fails on settings that
+    /// match no option.
+    pub fn apply(&self, mut command: Command) -> Result<Command, String> {
+        let options: BTreeMap<String, &Value> = self
+            .options
+            .iter()
+            .map(|(name, value)| (name.replace('_', "-"), value))
+            .collect();
+        let mut unknown: Vec<&String> = options.keys().collect();
+
+        let subcommands: Vec<String> = command
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect();
+        for name in subcommands {
+            command = command.mut_subcommand(name, |mut subcommand| {
+                let args: Vec<(String, String)> = subcommand
+                    .get_arguments()
+                    .filter_map(|arg| Some((arg.get_id().as_str().to_string(), arg.get_long()?)))
+                    .filter(|(_, long)| !matches!(*long, "help" | "version" | "config"))
+                    .map(|(id, long)| (id, long.to_string()))
+                    .collect();
+
+                for (id, long) in args {
+                    let value = options.get(&long);
+                    unknown.retain(|&name| *name != long);
+                    let env = format!("GAMEOFLIFE_{}", long.replace('-', "_").to_uppercase());
+                    subcommand = subcommand.mut_arg(id, |arg| {
+                        let arg = arg.env(env).hide_env(true);
+                        match value {
+                            Some(value) => arg.default_values(value.to_args()),
+                            None => arg,
+                        }
+                    });
+                }
+                subcommand
+            });
+        }
+
+        match unknown.first() {
+            Some(name) => Err(format!("unknown option {name:?} in config")),
+            None => Ok(command),
+        }
+    }
+}
+
+fn invalid_data(path: &Path, err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {err}", path.display()),
+    )
+}
@@ -0,0 +1,87 @@
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+
+use crate::format::{self, Format};
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+#[derive(Args, Debug)]
+pub struct ConvertArgs {
+    /// Board file to read, - for stdin
+    input: PathBuf,
+    /// Board file to write, - for stdout
+    output: PathBuf,
+    /// Format of the input, guessed from its extension, or on stdin its text, if not set
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+    /// Format of the output, guessed from its extension, or RLE on stdout, if not set
+    #[arg(long, value_enum)]
+    to: Option<Format>,
+    #[command(flatten)]
+    transform: Transform,
+}
+
+/// A quarter, half or three quarter turn clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Rotation {
+    #[value(name = "90")]
+    Quarter,
+    #[value(name = "180")]
+    Half,
+    #[value(name = "270")]
+    ThreeQuarters,
+}
+
+/// Changes made to a board on its way between formats, in the order of the
+/// fields.
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transform {
+    /// Crop the board to the bounding box of its live cells
+    #[arg(long, default_value_t = false)]
+    pub crop: bool,
+    /// Turn the board clockwise by this many degrees
+    #[arg(long, value_enum)]
+    pub rotate: Option<Rotation>,
+    /// Mirror the board left to right
+    #[arg(long, default_value_t = false)]
+    pub flip_h: bool,
+    /// Mirror the board top to bottom
+    #[arg(long, default_value_t = false)]
+    pub flip_v: bool,
+    /// Dead cells to add on every side of the board
+    #[arg(long, default_value_t = 0)]
+    pub pad: usize,
+}
+
+impl Transform {
+    pub fn apply(&self, mut matrix: Matrix) -> Matrix {
+        if self.crop {
+            matrix = matrix.crop_to_bounding_box();
+        }
+        matrix = match self.rotate {
+            None => matrix,
+            Some(Rotation::Quarter) => matrix.rotate90(),
+            Some(Rotation::Half) => matrix.rotate180(),
+            Some(Rotation::ThreeQuarters) => matrix.rotate270(),
+        };
+        if self.flip_h {
+            matrix = matrix.flip_h();
+        }
+        if self.flip_v {
+            matrix = matrix.flip_v();
+        }
+        if self.pad > 0 {
+            matrix = matrix.expand(self.pad, self.pad, self.pad, self.pad);
+        }
+        matrix
+    }
+}
+
+pub fn run(args: &ConvertArgs) {
+    let (matrix, rule) =
+        format::read_with_rule(&args.input, args.from).expect("board decode exception");
+    let matrix = args.transform.apply(matrix);
+    // the rule an RLE file names carries over to RLE output
+    let rule = rule.unwrap_or(Rule::CONWAY);
+    format::write_with_rule(&args.output, &matrix, args.to, &rule).expect("io exception");
+}
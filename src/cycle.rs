@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+
+/// A repeating sequence of states, found by comparing the hash of each new
+/// generation against the hashes of recent generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of ticks between two identical states, 1 means a still life.
+    pub period: usize,
+    /// The tick at which the repeating state was first seen.
+    pub start: usize,
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.period == 1 {
+            write!(f, "stabilized at tick {}", self.start)
+        } else {
+            write!(
+                f,
+                "cycle of period {} starting at tick {}",
+                self.period, self.start
+            )
+        }
+    }
+}
+
+/// Remembers the hashes of the last `capacity` states, so only cycles with a
+/// period up to `capacity` can be detected.
+pub struct CycleDetector {
+    capacity: usize,
+    history: VecDeque<(u64, usize)>,
+}
+
+impl CycleDetector {
+    pub fn new(capacity: usize) -> Self {
+        CycleDetector {
+            capacity,
+            history: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Records the state hash of generation `tick` and returns the cycle it
+    /// closes, if any.
+    pub fn observe(&mut self, hash: u64, tick: usize) -> Option<Cycle> {
+        let cycle = self
+            .history
+            .iter()
+            .rev()
+            .find(|(seen, _)| *seen == hash)
+            .map(|&(_, start)| Cycle {
+                period: tick - start,
+                start,
+            });
+
+        if self.history.len() == self.capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back((hash, tick));
+
+        cycle
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+}
@@ -0,0 +1,212 @@
+//! A tour of famous patterns of the built-in library, each shown running in
+//! the middle of the terminal for a few seconds under a caption, for
+//! teaching and presentations.
+
+use clap::Args;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::game_of_life::GameOfLife;
+use crate::matrix::{Matrix, Overflow};
+use crate::patterns::{self, Pattern};
+use crate::render::terminal::{Screen, TerminalRenderer};
+use crate::render::text::Renderer;
+use crate::render::theme::Theme;
+use crate::render::Frame;
+
+/// Lines of the tour besides the cells: the caption and the key help.
+const CHROME_LINES: usize = 2;
+
+/// The patterns of the tour in order, with their captions.
+const CAPTIONS: &[(&str, &str)] = &[
+    (
+        "glider",
+        "Glider: the smallest spaceship, moving one cell diagonally every 4 generations",
+    ),
+    (
+        "lwss",
+        "Lightweight spaceship: the smallest orthogonal spaceship, moving at half the speed of light",
+    ),
+    (
+        "beacon",
+        "Beacon: two blocks blinking their inner corners, period 2",
+    ),
+    (
+        "pulsar",
+        "Pulsar: the most common period-3 oscillator",
+    ),
+    (
+        "pentadecathlon",
+        "Pentadecathlon: a row of ten cells going through 15 phases",
+    ),
+    (
+        "gosper-glider-gun",
+        "Gosper glider gun: the first pattern known to grow forever, a glider every 30 generations",
+    ),
+    (
+        "r-pentomino",
+        "R-pentomino: five cells that take 1103 generations to settle",
+    ),
+    (
+        "diehard",
+        "Diehard: seven cells that vanish after 130 generations",
+    ),
+    (
+        "acorn",
+        "Acorn: seven cells that take 5206 generations to settle",
+    ),
+];
+
+#[derive(Args, Debug)]
+pub struct DemoArgs {
+    /// Seconds each pattern is shown for
+    #[arg(long, default_value_t = 10)]
+    seconds: u64,
+    /// Milliseconds between two generations
+    #[arg(long, default_value_t = 100)]
+    interval: u64,
+    /// Built-in patterns to show in order, separated by commas, e.g. glider,pulsar, the whole tour if not set
+    #[arg(long, value_delimiter = ',', value_parser = patterns::parse)]
+    patterns: Vec<&'static Pattern>,
+    /// Start over after the last pattern instead of exiting
+    #[arg(long, default_value_t = false)]
+    repeat: bool,
+    /// How cells are drawn in the terminal
+    #[arg(long, value_enum, default_value_t = Renderer::Ascii)]
+    renderer: Renderer,
+    /// Color live cells in the terminal by how many generations they have been alive
+    #[arg(long, value_enum, default_value_t = Theme::Mono)]
+    theme: Theme,
+}
+
+/// The patterns of the tour in order.
+pub fn tour() -> Vec<&'static Pattern> {
+    CAPTIONS
+        .iter()
+        .map(|(name, _)| patterns::find(name).expect("tour patterns are built in"))
+        .collect()
+}
+
+/// The caption shown over `pattern`, its name if it is not part of the tour.
+pub fn caption(pattern: &Pattern) -> &'static str {
+    CAPTIONS
+        .iter()
+        .find(|(name, _)| *name == pattern.name)
+        .map_or(pattern.name, |(_, caption)| caption)
+}
+
+/// An empty board of `rows` by `cols` with `pattern` in the middle, clipped
+/// if it does not fit.
+pub fn stage(pattern: &Pattern, rows: usize, cols: usize) -> Matrix {
+    let cells = pattern.matrix();
+    let mut matrix = Matrix::new(rows, cols);
+    matrix.blit(
+        &cells,
+        rows.saturating_sub(cells.row_count()) / 2,
+        cols.saturating_sub(cells.col_count()) / 2,
+        Overflow::Clip,
+    );
+    matrix
+}
+
+/// Runs the tour until its last pattern has been shown or the user quits
+/// with `q`, `Esc` or `Ctrl-C`. The right arrow, `n` and space skip to the
+/// next pattern, the left arrow and `p` go back to the previous one.
+pub fn run(args: &DemoArgs) {
+    let screen = Screen::enter(false).expect("io exception");
+    let result = show(args);
+    drop(screen);
+    result.expect("io exception");
+}
+
+fn show(args: &DemoArgs) -> io::Result<()> {
+    let tour = match args.patterns.is_empty() {
+        true => tour(),
+        false => args.patterns.clone(),
+    };
+    let shown_for = Duration::from_secs(args.seconds);
+    let interval = Duration::from_millis(args.interval);
+    let mut renderer = TerminalRenderer::stdout();
+    let mut stop = 0;
+
+    while stop < tour.len() {
+        let pattern = tour[stop];
+        let (mut game, mut columns) = staged_game(args, pattern)?;
+        let started = Instant::now();
+        // where to go once the pattern has been shown, the next one by default
+        let mut next = Some(stop + 1);
+
+        while started.elapsed() < shown_for {
+            let mut frame = Frame::default();
+            frame.push_text(&truncate(caption(pattern), columns));
+            frame.lines.extend(game.cells_frame().lines);
+            frame.push_text(&truncate(
+                &format!(
+                    "{}/{} generation {}  n next  p previous  q quits",
+                    stop + 1,
+                    tour.len(),
+                    game.ticks()
+                ),
+                columns,
+            ));
+            renderer.draw(frame)?;
+
+            if !event::poll(interval)? {
+                game.tick();
+                continue;
+            }
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        next = None;
+                        break;
+                    }
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        next = None;
+                        break;
+                    }
+                    KeyCode::Right | KeyCode::Char('n') | KeyCode::Char(' ') => break,
+                    KeyCode::Left | KeyCode::Char('p') => {
+                        next = Some(stop.saturating_sub(1));
+                        break;
+                    }
+                    _ => {}
+                },
+                Event::Resize(..) => {
+                    (game, columns) = staged_game(args, pattern)?;
+                    renderer.invalidate();
+                }
+                _ => {}
+            }
+        }
+
+        let Some(next) = next else {
+            return Ok(());
+        };
+        stop = match next == tour.len() && args.repeat {
+            true => 0,
+            false => next,
+        };
+    }
+    Ok(())
+}
+
+/// A game of `pattern` sized to the terminal, and the width of the terminal.
+fn staged_game(args: &DemoArgs, pattern: &Pattern) -> io::Result<(GameOfLife, usize)> {
+    let (columns, lines) = terminal::size()?;
+    let (rows, cols) = args.renderer.fit(
+        (lines as usize).saturating_sub(CHROME_LINES),
+        columns as usize,
+    );
+    let mut game = GameOfLife::new(stage(pattern, rows.max(1), cols.max(1)));
+    game.set_renderer(args.renderer);
+    game.set_theme(args.theme);
+    Ok((game, columns as usize))
+}
+
+/// The first `columns` characters of `text`, so that it takes a single line.
+fn truncate(text: &str, columns: usize) -> String {
+    text.chars().take(columns).collect()
+}
@@ -0,0 +1,166 @@
+//! Cell by cell comparison of two saved boards, e.g. the final boards of
+//! runs with different engines or settings.
+
+use clap::Args;
+use crossterm::style::Stylize;
+use std::fmt::{self, Display};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::checkpoint::Checkpoint;
+use crate::matrix::Matrix;
+use crate::render::terminal;
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Board file or checkpoint of the earlier state
+    before: PathBuf,
+    /// Board file or checkpoint of the later state
+    after: PathBuf,
+    /// Only print the counts, not the board of changes
+    #[arg(long, default_value_t = false)]
+    quiet: bool,
+    /// Color born cells green and dead ones red
+    #[arg(long, default_value_t = false)]
+    color: bool,
+}
+
+/// What became of a cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Change {
+    /// Dead in both boards
+    #[default]
+    Dead,
+    /// Alive in both boards
+    Alive,
+    /// Dead in the first board and alive in the second
+    Born,
+    /// Alive in the first board and dead in the second
+    Died,
+}
+
+/// The changes between two boards.
+pub struct Diff {
+    pub changes: Matrix<Change>,
+    pub born: usize,
+    pub died: usize,
+    /// Cells alive in both boards.
+    pub alive: usize,
+}
+
+impl Diff {
+    /// Compares the boards cell by cell, aligned at their top-left cells.
+    /// Boards of different sizes are compared over the larger size, cells
+    /// beyond the smaller one being dead.
+    pub fn new(before: &Matrix, after: &Matrix) -> Self {
+        let rows = before.row_count().max(after.row_count());
+        let cols = before.col_count().max(after.col_count());
+        let alive = |matrix: &Matrix, row: usize, col: usize| {
+            row < matrix.row_count() && col < matrix.col_count() && matrix.get(row, col) != 0
+        };
+
+        let mut diff = Diff {
+            changes: Matrix::new(rows, cols),
+            born: 0,
+            died: 0,
+            alive: 0,
+        };
+        for row in 0..rows {
+            for col in 0..cols {
+                let change = match (alive(before, row, col), alive(after, row, col)) {
+                    (false, false) => Change::Dead,
+                    (true, true) => Change::Alive,
+                    (false, true) => Change::Born,
+                    (true, false) => Change::Died,
+                };
+                match change {
+                    Change::Dead => {}
+                    Change::Alive => diff.alive += 1,
+                    Change::Born => diff.born += 1,
+                    Change::Died => diff.died += 1,
+                }
+                diff.changes.set(row, col, change);
+            }
+        }
+        diff
+    }
+
+    /// Whether both boards have the same live cells.
+    pub fn is_empty(&self) -> bool {
+        self.born == 0 && self.died == 0
+    }
+
+    /// The changes one row per line, `+` for born cells, `-` for dead ones,
+    /// `x` for cells alive in both boards and `.` for the others, with born
+    /// and dead cells in green and red if `color` is set.
+    pub fn render(&self, color: bool) -> String {
+        let mut out = String::new();
+        for row in self.changes.rows() {
+            for &change in row {
+                let cell = match (change, color) {
+                    (Change::Dead, _) => ".".to_string(),
+                    (Change::Alive, _) => "x".to_string(),
+                    (Change::Born, false) => "+".to_string(),
+                    (Change::Died, false) => "-".to_string(),
+                    (Change::Born, true) => "+".green().to_string(),
+                    (Change::Died, true) => "-".red().to_string(),
+                };
+                out.push_str(&cell);
+                out.push(' ');
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The counts of changes.
+impl Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} cells differ: {} born, {} died, {} alive in both",
+            self.born + self.died,
+            self.born,
+            self.died,
+            self.alive
+        )
+    }
+}
+
+/// Reads a board file, or the board of a checkpoint.
+fn load(path: &Path) -> io::Result<Matrix> {
+    Matrix::load(path).or_else(|err| match Checkpoint::load(path) {
+        Ok(checkpoint) => Ok(checkpoint.matrix),
+        Err(_) => Err(err),
+    })
+}
+
+/// Prints the changes between the boards and their counts, failing if they
+/// differ like `diff` does.
+pub fn run(args: &DiffArgs) -> ExitCode {
+    let before = load(&args.before).expect("board decode exception");
+    let after = load(&args.after).expect("board decode exception");
+    let diff = Diff::new(&before, &after);
+
+    if !args.quiet && !diff.is_empty() {
+        // colors would show up as escape codes on legacy Windows consoles
+        print!("{}", diff.render(args.color && terminal::supports_ansi()));
+    }
+    if (before.row_count(), before.col_count()) != (after.row_count(), after.col_count()) {
+        println!(
+            "boards of {} x {} and {} x {} cells, compared from their top-left cells",
+            before.row_count(),
+            before.col_count(),
+            after.row_count(),
+            after.col_count()
+        );
+    }
+    println!("{diff}");
+
+    match diff.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
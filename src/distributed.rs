@@ -0,0 +1,348 @@
+//! The board split into horizontal strips ticked by separate processes,
+//! possibly on separate hosts, in the manner of MPI. Each process, or rank,
+//! owns the rows of one strip and every generation swaps its top and bottom
+//! rows with the ranks above and below over TCP, as the halo its engine
+//! needs to tick the strip's boundary rows.
+//!
+//! Rank 0 builds the initial board and hands the strips down the chain of
+//! ranks, and after the last generation the strips travel back up the chain
+//! to it. With wrapping edges the last rank also exchanges rows with rank 0.
+
+use clap::Args;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::edge::Edge;
+use crate::engine::TickEngine;
+use crate::game_of_life::{GameOfLife, GameOfLifeArgs};
+use crate::matrix::Matrix;
+use crate::rle;
+use crate::rule::Rule;
+
+/// How long a rank keeps trying to reach its neighbor, which may not have
+/// started yet.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time between two attempts to reach a neighbor.
+const CONNECT_RETRY: Duration = Duration::from_millis(100);
+
+#[derive(Args, Debug)]
+pub struct DistributedArgs {
+    #[command(flatten)]
+    game: GameOfLifeArgs,
+    /// Addresses of all processes in rank order, separated by commas, e.g. node1:7000,node2:7000
+    #[arg(long, value_delimiter = ',', required = true)]
+    peers: Vec<String>,
+    /// Position of this process in --peers, rank 0 builds the board and writes out the final one
+    #[arg(long)]
+    rank: usize,
+    /// Address this process listens on, its entry of --peers if not set, e.g. 0.0.0.0:7000
+    #[arg(long)]
+    listen: Option<String>,
+    /// Number of generations to run for
+    #[arg(long, default_value_t = 1000)]
+    ticks: usize,
+    /// File rank 0 writes the final board to in the format its extension suggests, RLE on stdout if not set
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// The rows of the strip of `rank` out of `ranks` on a board of `rows` rows.
+pub fn strip_rows(rows: usize, ranks: usize, rank: usize) -> Range<usize> {
+    rank * rows / ranks..(rank + 1) * rows / ranks
+}
+
+/// The links of a rank to its neighbors.
+struct Links {
+    rank: usize,
+    ranks: usize,
+    /// The rank above, if any, or the last rank above rank 0 with wrapping edges.
+    up: Option<TcpStream>,
+    /// The rank below, if any, or rank 0 below the last rank with wrapping edges.
+    down: Option<TcpStream>,
+}
+
+impl Links {
+    /// Connects to the rank below and accepts the rank above on `listener`.
+    fn establish(
+        listener: &TcpListener,
+        peers: &[SocketAddr],
+        rank: usize,
+        wrap: bool,
+    ) -> io::Result<Self> {
+        let ranks = peers.len();
+        let ring = wrap && ranks > 1;
+
+        let down = match rank + 1 < ranks || ring {
+            true => {
+                let mut stream = connect(peers[(rank + 1) % ranks])?;
+                stream.write_all(&(rank as u32).to_le_bytes())?;
+                Some(stream)
+            }
+            false => None,
+        };
+
+        let up = match rank > 0 || ring {
+            true => {
+                let (mut stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                let mut hello = [0; 4];
+                stream.read_exact(&mut hello)?;
+                let expected = (rank + ranks - 1) % ranks;
+                if u32::from_le_bytes(hello) as usize != expected {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("rank {rank} expected rank {expected} above it"),
+                    ));
+                }
+                Some(stream)
+            }
+            false => None,
+        };
+
+        Ok(Links {
+            rank,
+            ranks,
+            up,
+            down,
+        })
+    }
+
+    /// The link to the rank above in the chain, not through the wrapping edge.
+    fn chain_up(&self) -> Option<&TcpStream> {
+        self.up.as_ref().filter(|_| self.rank > 0)
+    }
+
+    /// The link to the rank below in the chain, not through the wrapping edge.
+    fn chain_down(&self) -> Option<&TcpStream> {
+        self.down.as_ref().filter(|_| self.rank + 1 < self.ranks)
+    }
+
+    /// Sends `top` up and `bottom` down while receiving the halo rows from
+    /// the neighbors, which are left as they are where there is none.
+    fn exchange(
+        &self,
+        top: &[u8],
+        bottom: &[u8],
+        halo_top: &mut [u8],
+        halo_bottom: &mut [u8],
+    ) -> io::Result<()> {
+        // sending on a thread of its own keeps wide rows from filling both
+        // ends' buffers while neither is reading
+        thread::scope(|scope| {
+            let sending = scope.spawn(|| -> io::Result<()> {
+                if let Some(mut up) = self.up.as_ref() {
+                    up.write_all(top)?;
+                }
+                if let Some(mut down) = self.down.as_ref() {
+                    down.write_all(bottom)?;
+                }
+                Ok(())
+            });
+
+            if let Some(mut up) = self.up.as_ref() {
+                up.read_exact(halo_top)?;
+            }
+            if let Some(mut down) = self.down.as_ref() {
+                down.read_exact(halo_bottom)?;
+            }
+            sending.join().expect("halo sender panicked")
+        })
+    }
+}
+
+/// Connects to `addr`, retrying while the process there is starting up.
+fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
+    let deadline = Instant::now() + CONNECT_TIMEOUT;
+    loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => {
+                stream.set_nodelay(true)?;
+                return Ok(stream);
+            }
+            Err(_) if Instant::now() < deadline => thread::sleep(CONNECT_RETRY),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Ticks the strip of `rank` for `ticks` generations, exchanging halos with
+/// the other ranks of `peers`, which `listener` is the address of `rank`
+/// among. Rank 0 passes the initial `board` and gets the final one back,
+/// the other ranks pass none and get none.
+pub fn run_rank(
+    listener: &TcpListener,
+    peers: &[SocketAddr],
+    rank: usize,
+    board: Option<&Matrix>,
+    rule: &Rule,
+    engine: &mut dyn TickEngine,
+    ticks: usize,
+) -> io::Result<Option<Matrix>> {
+    let links = Links::establish(listener, peers, rank, rule.edge == Edge::Wrap)?;
+    let (rows, cols, strip) = scatter(&links, board)?;
+
+    // the strip between a halo row above and one below, as the engine sees it
+    let height = strip_rows(rows, links.ranks, rank).len();
+    let mut padded = Matrix::new(height + 2, cols);
+    padded.as_mut_slice()[cols..(height + 1) * cols].copy_from_slice(&strip);
+    let mut next = Matrix::new(height + 2, cols);
+
+    for _ in 0..ticks {
+        let cells = padded.as_mut_slice();
+        let (halo_top, rest) = cells.split_at_mut(cols);
+        let (inner, halo_bottom) = rest.split_at_mut(height * cols);
+        let (top, bottom) = (&inner[..cols], &inner[(height - 1) * cols..]);
+
+        // beyond the board the halo follows the edge mode
+        let beyond = |own: &[u8], opposite: &[u8], halo: &mut [u8]| match rule.edge {
+            Edge::Dead => halo.fill(0),
+            Edge::Alive => halo.fill(1),
+            Edge::Mirror => halo.copy_from_slice(own),
+            Edge::Wrap => halo.copy_from_slice(opposite),
+        };
+        if links.up.is_none() {
+            beyond(top, bottom, halo_top);
+        }
+        if links.down.is_none() {
+            beyond(bottom, top, halo_bottom);
+        }
+        links.exchange(top, bottom, halo_top, halo_bottom)?;
+
+        engine.step(&padded, &mut next, rule);
+        std::mem::swap(&mut padded, &mut next);
+    }
+
+    let strip = &padded.as_slice()[cols..(height + 1) * cols];
+    gather(&links, rows, cols, strip)
+}
+
+/// Hands the strips of the board of rank 0 down the chain, returns the size
+/// of the board and the cells of the strip of this rank.
+fn scatter(links: &Links, board: Option<&Matrix>) -> io::Result<(usize, usize, Vec<u8>)> {
+    let (rows, cols, cells) = match links.chain_up() {
+        None => {
+            let board = board.expect("rank 0 needs the initial board");
+            if board.row_count() < links.ranks {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{} rows cannot be split among {} ranks",
+                        board.row_count(),
+                        links.ranks
+                    ),
+                ));
+            }
+            (
+                board.row_count(),
+                board.col_count(),
+                board.as_slice().to_vec(),
+            )
+        }
+        Some(mut up) => {
+            let mut size = [0; 8];
+            up.read_exact(&mut size)?;
+            let rows = u32::from_le_bytes(size[..4].try_into().expect("4 bytes")) as usize;
+            let cols = u32::from_le_bytes(size[4..].try_into().expect("4 bytes")) as usize;
+            let start = strip_rows(rows, links.ranks, links.rank).start;
+            let mut cells = vec![0; (rows - start) * cols];
+            up.read_exact(&mut cells)?;
+            (rows, cols, cells)
+        }
+    };
+
+    // the cells received start with the strip of this rank
+    let strip = strip_rows(rows, links.ranks, links.rank);
+    let (own, below) = cells.split_at(strip.len() * cols);
+    if let Some(mut down) = links.chain_down() {
+        down.write_all(&(rows as u32).to_le_bytes())?;
+        down.write_all(&(cols as u32).to_le_bytes())?;
+        down.write_all(below)?;
+    }
+    Ok((rows, cols, own.to_vec()))
+}
+
+/// Passes the strips of this rank and those below up the chain, returns the
+/// whole board on rank 0.
+fn gather(links: &Links, rows: usize, cols: usize, strip: &[u8]) -> io::Result<Option<Matrix>> {
+    let end = strip_rows(rows, links.ranks, links.rank).end;
+    let mut cells = strip.to_vec();
+    if let Some(mut down) = links.chain_down() {
+        let mut below = vec![0; (rows - end) * cols];
+        down.read_exact(&mut below)?;
+        cells.extend_from_slice(&below);
+    }
+
+    match links.chain_up() {
+        Some(mut up) => {
+            up.write_all(&cells)?;
+            Ok(None)
+        }
+        None => Ok(Matrix::from_vec(rows, cols, cells)),
+    }
+}
+
+/// Runs the rank given on the command line. Rank 0 writes out the final
+/// board and a summary.
+pub fn run(args: &DistributedArgs) {
+    let peers: Vec<SocketAddr> = args
+        .peers
+        .iter()
+        .map(|peer| {
+            peer.to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .unwrap_or_else(|| panic!("invalid peer address {peer:?}"))
+        })
+        .collect();
+    assert!(
+        args.rank < peers.len(),
+        "rank {} is not among the {} peers",
+        args.rank,
+        peers.len()
+    );
+
+    let listen = args.listen.as_deref().unwrap_or(&args.peers[args.rank]);
+    let listener = TcpListener::bind(listen).expect("io exception");
+    let board = (args.rank == 0).then(|| GameOfLife::from_args(&args.game).matrix().clone());
+    let mut engine = args.game.build_engine(args.game.engine());
+
+    let board = run_rank(
+        &listener,
+        &peers,
+        args.rank,
+        board.as_ref(),
+        &args.game.rule(),
+        engine.as_mut(),
+        args.ticks,
+    )
+    .expect("io exception");
+
+    let Some(board) = board else {
+        return;
+    };
+    let rule = args.game.rule();
+    match &args.out {
+        Some(path) => board.save_with_rule(path, &rule).expect("io exception"),
+        None => print!("{}", rle::encode_with_rule(&board, &rule)),
+    }
+
+    let summary = format!(
+        "ticks: {}\nsize: {} x {}\npopulation: {}\nranks: {}\n",
+        args.ticks,
+        board.row_count(),
+        board.col_count(),
+        board.count_live(),
+        peers.len()
+    );
+    // the summary goes to stderr when the board is written to stdout
+    if args.out.is_some() {
+        print!("{summary}");
+    } else {
+        eprint!("{summary}");
+    }
+}
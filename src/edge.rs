@@ -0,0 +1,43 @@
+#[cfg(feature = "std")]
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::matrix::Matrix;
+
+/// How cells beyond the matrix boundaries are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Edge {
+    /// Cells outside the matrix are always dead
+    #[default]
+    Dead,
+    /// Cells outside the matrix are always alive
+    Alive,
+    /// The matrix loops back at its boundaries like a torus
+    Wrap,
+    /// Cells outside the matrix reflect the nearest boundary cell
+    Mirror,
+}
+
+impl Edge {
+    /// State of the cell at `(row, col)`, which may lie outside the matrix.
+    pub fn cell(self, matrix: &Matrix, row: isize, col: isize) -> u8 {
+        let rows = matrix.row_count() as isize;
+        let cols = matrix.col_count() as isize;
+
+        if (0..rows).contains(&row) && (0..cols).contains(&col) {
+            return matrix.get(row as usize, col as usize);
+        }
+
+        match self {
+            Edge::Dead => 0,
+            Edge::Alive => 1,
+            Edge::Wrap => matrix.get(row.rem_euclid(rows) as usize, col.rem_euclid(cols) as usize),
+            Edge::Mirror => matrix.get(
+                row.clamp(0, rows - 1) as usize,
+                col.clamp(0, cols - 1) as usize,
+            ),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Wolfram's elementary cellular automata: a row of cells, each of which
+//! looks at itself and its left and right neighbors, ticked with a Wolfram
+//! code such as 30 or 110. The board holds the latest generations, newest
+//! at the bottom, and scrolls up one row every tick, so a run is drawn like
+//! lines printed to a terminal.
+
+use crate::edge::Edge;
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// Computes the next generation from the bottom row and scrolls the board up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elementary {
+    code: u8,
+}
+
+impl Elementary {
+    pub fn new(code: u8) -> Self {
+        Elementary { code }
+    }
+
+    pub fn code(&self) -> u8 {
+        self.code
+    }
+
+    /// Next state of a cell from its left neighbor, itself and its right
+    /// neighbor: the bit of the code numbered by the three states read as a
+    /// binary number.
+    pub fn next_state(&self, left: u8, cell: u8, right: u8) -> u8 {
+        let pattern = (left != 0) as u8 * 4 + (cell != 0) as u8 * 2 + (right != 0) as u8;
+        (self.code >> pattern) & 1
+    }
+
+    /// The generation after `row`, with cells beyond its ends treated by `edge`.
+    pub fn next_row(&self, row: &[u8], next: &mut [u8], edge: Edge) {
+        let len = row.len();
+        let beyond = |idx: isize| -> u8 {
+            match edge {
+                Edge::Dead => 0,
+                Edge::Alive => 1,
+                Edge::Wrap => row[idx.rem_euclid(len as isize) as usize],
+                Edge::Mirror => row[idx.clamp(0, len as isize - 1) as usize],
+            }
+        };
+
+        for (col, value) in next.iter_mut().enumerate() {
+            let left = match col {
+                0 => beyond(-1),
+                col => row[col - 1],
+            };
+            let right = row
+                .get(col + 1)
+                .copied()
+                .unwrap_or_else(|| beyond(len as isize));
+            *value = self.next_state(left, row[col], right);
+        }
+    }
+}
+
+impl TickEngine for Elementary {
+    /// The births and survivals of the rule are ignored, only its edge mode is used.
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        let (rows, cols) = (src.row_count(), src.col_count());
+        if rows == 0 || cols == 0 {
+            return;
+        }
+
+        let bottom = (rows - 1) * cols;
+        let (src, dst) = (src.as_slice(), dst.as_mut_slice());
+        dst[..bottom].copy_from_slice(&src[cols..]);
+        self.next_row(&src[bottom..], &mut dst[bottom..], rule.edge);
+    }
+}
@@ -0,0 +1,564 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use clap::ValueEnum;
+#[cfg(feature = "native")]
+use rayon::prelude::*;
+#[cfg(feature = "native")]
+use std::thread;
+
+use crate::bitboard::BitParallel;
+use crate::edge::Edge;
+#[cfg(feature = "gpu")]
+use crate::gpu;
+use crate::lut::Lut;
+use crate::matrix::Matrix;
+use crate::padded::PaddedMatrix;
+use crate::rule::Rule;
+use crate::simd;
+use crate::tiled::Tiled;
+#[cfg(feature = "native")]
+use crate::tiled::TILE_SIZE;
+
+/// Bytes of a block of rows of the `rayon-chunked` engine when its size is
+/// not given, within the per-core L2 cache of current CPUs.
+pub const CACHE_BAND_BYTES: usize = 256 * 1024;
+
+/// Relative positions of the eight neighbors of a cell.
+const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// The built-in engines, selectable on the command line. The parallel ones
+/// only exist with the native feature and the GPU one with the gpu feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum EngineKind {
+    /// One cell after another on the current thread
+    #[default]
+    Serial,
+    /// Cells in parallel on the rayon thread pool
+    #[cfg(feature = "native")]
+    Rayon,
+    /// Blocks of rows in parallel on the rayon thread pool
+    #[cfg(feature = "native")]
+    RayonChunked,
+    /// Equal slices of the board on native OS threads
+    #[cfg(feature = "native")]
+    Threads,
+    /// A whole row at a time with SIMD instructions
+    Simd,
+    /// Only the neighborhoods of the cells that changed in the last generation
+    Incremental,
+    /// Only the tiles of 64 by 64 cells next to those that changed in the last generation, in parallel on the rayon thread pool
+    #[cfg(feature = "native")]
+    RayonTiles,
+    /// One tile of 64 by 64 cells after another on a copy of the board stored tile after tile
+    Tiled,
+    /// Each cell's 3x3 neighborhood looked up in a table of its 512 next states
+    Lut,
+    /// 64 cells per machine word on a copy of the board packed one bit per cell
+    BitParallel,
+    /// A compute shader on the GPU
+    #[cfg(feature = "gpu")]
+    Gpu,
+}
+
+impl EngineKind {
+    /// The engine, `chunk_rows` is the number of rows per task of
+    /// `rayon-chunked` and `workers` the number of threads of `threads`.
+    /// Either is picked for the machine and the board when `None`, see
+    /// [`band_rows`] and [`available_workers`].
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    pub fn build(self, chunk_rows: Option<usize>, workers: Option<usize>) -> Box<dyn TickEngine> {
+        match self {
+            EngineKind::Serial => Box::new(Serial::new()),
+            #[cfg(feature = "native")]
+            EngineKind::Rayon => Box::new(Rayon::new()),
+            #[cfg(feature = "native")]
+            EngineKind::RayonChunked => Box::new(RayonChunked::new(chunk_rows)),
+            #[cfg(feature = "native")]
+            EngineKind::Threads => Box::new(Threads::new(workers)),
+            #[cfg(feature = "native")]
+            EngineKind::RayonTiles => Box::new(RayonTiles::new()),
+            EngineKind::Simd => Box::new(Simd),
+            EngineKind::Incremental => Box::new(Incremental::new()),
+            EngineKind::Tiled => Box::new(Tiled::new()),
+            EngineKind::Lut => Box::new(Lut::new()),
+            EngineKind::BitParallel => Box::new(BitParallel::new()),
+            #[cfg(feature = "gpu")]
+            EngineKind::Gpu => Box::new(gpu::Gpu::new().expect("gpu exception")),
+        }
+    }
+}
+
+/// Number of rows of `cols` cells in a block fitting [`CACHE_BAND_BYTES`].
+pub fn band_rows(cols: usize) -> usize {
+    (CACHE_BAND_BYTES / cols.max(1)).max(1)
+}
+
+/// Number of threads the machine can run in parallel, 1 if unknown.
+#[cfg(feature = "native")]
+pub fn available_workers() -> usize {
+    thread::available_parallelism().map_or(1, |workers| workers.get())
+}
+
+/// A strategy computing the next generation of a board. Implement it to run
+/// a game on an engine of your own with `GameOfLife::set_engine`.
+pub trait TickEngine: Send {
+    /// Writes the generation after `src` under `rule` into `dst`, which has
+    /// the same size as `src`.
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule);
+}
+
+/// One cell after another on the current thread.
+pub struct Serial {
+    padded: PaddedMatrix,
+}
+
+impl Serial {
+    pub fn new() -> Self {
+        Serial {
+            padded: PaddedMatrix::new(0, 0),
+        }
+    }
+}
+
+impl Default for Serial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickEngine for Serial {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        self.padded.resize(src.row_count(), src.col_count());
+        self.padded.load(src, rule.edge);
+        let padded = &self.padded;
+
+        let cols = src.col_count();
+        for row in 0..src.row_count() {
+            let cells = &mut dst.as_mut_slice()[row * cols..(row + 1) * cols];
+            for (col, value) in cells.iter_mut().enumerate() {
+                *value = padded.next_state(row, col, rule);
+            }
+        }
+    }
+}
+
+/// Cells in parallel on the rayon thread pool.
+#[cfg(feature = "native")]
+pub struct Rayon {
+    padded: PaddedMatrix,
+}
+
+#[cfg(feature = "native")]
+impl Rayon {
+    pub fn new() -> Self {
+        Rayon {
+            padded: PaddedMatrix::new(0, 0),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl Default for Rayon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "native")]
+impl TickEngine for Rayon {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        self.padded.resize(src.row_count(), src.col_count());
+        self.padded.load(src, rule.edge);
+        let padded = &self.padded;
+        let cols = src.col_count();
+
+        dst.as_mut_slice()
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(idx, value)| {
+                *value = padded.next_state(idx / cols, idx % cols, rule);
+            });
+    }
+}
+
+/// Blocks of `chunk_rows` rows in parallel on the rayon thread pool, as
+/// many as fit in the cache if `None`.
+#[cfg(feature = "native")]
+pub struct RayonChunked {
+    padded: PaddedMatrix,
+    chunk_rows: Option<usize>,
+}
+
+#[cfg(feature = "native")]
+impl RayonChunked {
+    pub fn new(chunk_rows: Option<usize>) -> Self {
+        RayonChunked {
+            padded: PaddedMatrix::new(0, 0),
+            chunk_rows: chunk_rows.map(|chunk_rows| chunk_rows.max(1)),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl TickEngine for RayonChunked {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        self.padded.resize(src.row_count(), src.col_count());
+        self.padded.load(src, rule.edge);
+        let padded = &self.padded;
+        let cols = src.col_count();
+        let chunk_rows = self.chunk_rows.unwrap_or_else(|| band_rows(cols));
+
+        dst.as_mut_slice()
+            .par_chunks_mut((chunk_rows * cols).max(1))
+            .enumerate()
+            .for_each(|(chunk, cells)| {
+                let first_row = chunk * chunk_rows;
+                for (offset, row_cells) in cells.chunks_mut(cols).enumerate() {
+                    for (col, value) in row_cells.iter_mut().enumerate() {
+                        *value = padded.next_state(first_row + offset, col, rule);
+                    }
+                }
+            });
+    }
+}
+
+/// Equal bands of whole rows on `workers` native OS threads, as many as the
+/// machine runs in parallel if `None`. Each thread reads and writes a contiguous
+/// part of the boards, and never more threads are started than there are rows.
+#[cfg(feature = "native")]
+pub struct Threads {
+    padded: PaddedMatrix,
+    workers: usize,
+}
+
+#[cfg(feature = "native")]
+impl Threads {
+    pub fn new(workers: Option<usize>) -> Self {
+        let workers = workers.unwrap_or_else(available_workers);
+        Threads {
+            padded: PaddedMatrix::new(0, 0),
+            workers,
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl TickEngine for Threads {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        let (rows, cols) = (src.row_count(), src.col_count());
+        self.padded.resize(rows, cols);
+        self.padded.load(src, rule.edge);
+        let padded = &self.padded;
+        let workers = self.workers.clamp(1, rows.max(1));
+        let band_rows = rows.div_ceil(workers).max(1);
+
+        // scoped threads may borrow the matrices and are joined before the scope returns
+        thread::scope(|scope| {
+            for (band, cells) in dst
+                .as_mut_slice()
+                .chunks_mut((band_rows * cols).max(1))
+                .enumerate()
+            {
+                scope.spawn(move || {
+                    let first_row = band * band_rows;
+                    for (offset, row_cells) in cells.chunks_mut(cols).enumerate() {
+                        for (col, value) in row_cells.iter_mut().enumerate() {
+                            *value = padded.next_state(first_row + offset, col, rule);
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
+
+fn write_next_tick_state(rule: &Rule, matrix: &Matrix, idx: usize, value: &mut u8) {
+    let (row, col) = matrix.inverse_idx(idx);
+    let (row, col) = (row as isize, col as isize);
+
+    let mut live_count = 0;
+
+    for (row_offset, col_offset) in NEIGHBOR_OFFSETS {
+        live_count += rule.edge.cell(matrix, row + row_offset, col + col_offset);
+    }
+
+    *value = rule.next_state(matrix.get(row as usize, col as usize), live_count);
+}
+
+/// A whole row at a time with SIMD instructions.
+pub struct Simd;
+
+impl TickEngine for Simd {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        simd::tick(rule, src, dst);
+    }
+}
+
+/// Only recomputes the neighborhoods of the cells that changed in the last
+/// generation it computed, all other cells keep their state. Every cell is
+/// recomputed when the board was replaced or edited in between, or the rule
+/// changed, which is detected by comparing with a copy of that generation.
+pub struct Incremental {
+    serial: Serial,
+    /// The last generation computed and the rule it was computed with.
+    last: Option<(Matrix, Rule)>,
+    /// Indexes of the cells that changed in the last generation.
+    changed: Vec<usize>,
+    /// Indexes of the cells to recompute, `queued` marks them to avoid duplicates.
+    candidates: Vec<usize>,
+    queued: Vec<bool>,
+}
+
+impl Incremental {
+    pub fn new() -> Self {
+        Incremental {
+            serial: Serial::new(),
+            last: None,
+            changed: Vec::new(),
+            candidates: Vec::new(),
+            queued: Vec::new(),
+        }
+    }
+
+    fn step_all(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        self.serial.step(src, dst, rule);
+        self.changed.clear();
+        self.changed.extend(
+            src.as_slice()
+                .iter()
+                .zip(dst.as_slice())
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(idx, _)| idx),
+        );
+    }
+
+    fn step_changed(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        dst.as_mut_slice().copy_from_slice(src.as_slice());
+        self.queued.resize(src.size(), false);
+
+        let (rows, cols) = (src.row_count() as isize, src.col_count() as isize);
+        for &idx in &self.changed {
+            let (row, col) = src.inverse_idx(idx);
+            for (row_offset, col_offset) in NEIGHBOR_OFFSETS.iter().chain(&[(0, 0)]) {
+                let (row, col) = (row as isize + row_offset, col as isize + col_offset);
+                let inside = (0..rows).contains(&row) && (0..cols).contains(&col);
+                // with other edge modes the cells a change affects all lie within the board
+                let (row, col) = match rule.edge {
+                    _ if inside => (row, col),
+                    Edge::Wrap => (row.rem_euclid(rows), col.rem_euclid(cols)),
+                    _ => continue,
+                };
+
+                let neighbor = src.idx(row as usize, col as usize);
+                if !self.queued[neighbor] {
+                    self.queued[neighbor] = true;
+                    self.candidates.push(neighbor);
+                }
+            }
+        }
+
+        self.changed.clear();
+        let cells = dst.as_mut_slice();
+        for &idx in &self.candidates {
+            self.queued[idx] = false;
+            write_next_tick_state(rule, src, idx, &mut cells[idx]);
+            if cells[idx] != src.as_slice()[idx] {
+                self.changed.push(idx);
+            }
+        }
+        self.candidates.clear();
+    }
+}
+
+impl Default for Incremental {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickEngine for Incremental {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        match &self.last {
+            Some((last, last_rule)) if last == src && last_rule == rule => {
+                self.step_changed(src, dst, rule)
+            }
+            _ => self.step_all(src, dst, rule),
+        }
+
+        match &mut self.last {
+            Some((last, last_rule)) => {
+                last.clone_from(dst);
+                *last_rule = *rule;
+            }
+            None => self.last = Some((dst.clone(), *rule)),
+        }
+    }
+}
+
+/// Only recomputes the tiles of [`TILE_SIZE`] by [`TILE_SIZE`] cells next
+/// to a tile that changed in the last generation it computed, the active
+/// tiles in parallel on the rayon thread pool, which balances the uneven
+/// work of the tiles by stealing it between threads. Like [`Incremental`]
+/// it recomputes every tile when the board or the rule changed in between.
+#[cfg(feature = "native")]
+pub struct RayonTiles {
+    /// The last generation computed and the rule it was computed with.
+    last: Option<(Matrix, Rule)>,
+    /// Whether a cell of each tile changed in the last generation, tile row after tile row.
+    changed: Vec<bool>,
+}
+
+#[cfg(feature = "native")]
+impl RayonTiles {
+    pub fn new() -> Self {
+        RayonTiles {
+            last: None,
+            changed: Vec::new(),
+        }
+    }
+
+    /// The tiles to recompute: those next to a tile that changed, or all of them.
+    fn active_tiles(
+        &self,
+        src: &Matrix,
+        rule: &Rule,
+        tile_rows: usize,
+        tile_cols: usize,
+    ) -> Vec<bool> {
+        let tiles = tile_rows * tile_cols;
+        let unchanged_board =
+            matches!(&self.last, Some((last, last_rule)) if last == src && last_rule == rule);
+        if !unchanged_board || self.changed.len() != tiles {
+            return vec![true; tiles];
+        }
+
+        let mut active = vec![false; tiles];
+        let (rows, cols) = (tile_rows as isize, tile_cols as isize);
+        for tile in (0..tiles).filter(|&tile| self.changed[tile]) {
+            let (row, col) = ((tile / tile_cols) as isize, (tile % tile_cols) as isize);
+            for (row_offset, col_offset) in NEIGHBOR_OFFSETS.iter().chain(&[(0, 0)]) {
+                let (row, col) = (row + row_offset, col + col_offset);
+                let inside = (0..rows).contains(&row) && (0..cols).contains(&col);
+                // as with the incremental engine, only wrapping edges carry changes across the board
+                let (row, col) = match rule.edge {
+                    _ if inside => (row, col),
+                    Edge::Wrap => (row.rem_euclid(rows), col.rem_euclid(cols)),
+                    _ => continue,
+                };
+                active[row as usize * tile_cols + col as usize] = true;
+            }
+        }
+        active
+    }
+}
+
+#[cfg(feature = "native")]
+impl Default for RayonTiles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "native")]
+impl TickEngine for RayonTiles {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        let (rows, cols) = (src.row_count(), src.col_count());
+        let (tile_rows, tile_cols) = (rows.div_ceil(TILE_SIZE), cols.div_ceil(TILE_SIZE));
+        let active = self.active_tiles(src, rule, tile_rows, tile_cols);
+
+        dst.as_mut_slice().copy_from_slice(src.as_slice());
+
+        // the rows of every active tile, as disjoint slices of the board
+        let mut tiles: Vec<Vec<&mut [u8]>> = (0..active.len()).map(|_| Vec::new()).collect();
+        for (row, cells) in dst.as_mut_slice().chunks_mut(cols.max(1)).enumerate() {
+            for (tile_col, cells) in cells.chunks_mut(TILE_SIZE).enumerate() {
+                let tile = row / TILE_SIZE * tile_cols + tile_col;
+                if active[tile] {
+                    tiles[tile].push(cells);
+                }
+            }
+        }
+
+        let changed: Vec<usize> = tiles
+            .into_par_iter()
+            .enumerate()
+            .filter(|(_, tile_cells)| !tile_cells.is_empty())
+            .filter_map(|(tile, tile_cells)| {
+                let origin = (tile / tile_cols * TILE_SIZE, tile % tile_cols * TILE_SIZE);
+                step_tile(src, rule, origin, tile_cells).then_some(tile)
+            })
+            .collect();
+
+        self.changed.clear();
+        self.changed.resize(active.len(), false);
+        for tile in changed {
+            self.changed[tile] = true;
+        }
+
+        match &mut self.last {
+            Some((last, last_rule)) => {
+                last.clone_from(dst);
+                *last_rule = *rule;
+            }
+            None => self.last = Some((dst.clone(), *rule)),
+        }
+    }
+}
+
+/// Computes the next generation of the tile with its top-left cell at
+/// `origin`, whose rows `cells` hold the current generation, returns whether
+/// any cell changed. The tile is copied with a one cell wide border first, so
+/// only the border cells need the edge mode.
+#[cfg(feature = "native")]
+fn step_tile(src: &Matrix, rule: &Rule, origin: (usize, usize), cells: Vec<&mut [u8]>) -> bool {
+    let (height, width) = (cells.len(), cells[0].len());
+    let stride = width + 2;
+    let (first_row, first_col) = (origin.0 as isize - 1, origin.1 as isize - 1);
+    let mut padded = vec![0; (height + 2) * stride];
+    for (row, padded_row) in padded.chunks_mut(stride).enumerate() {
+        let src_row = first_row + row as isize;
+        if row == 0 || row == height + 1 {
+            for (col, cell) in padded_row.iter_mut().enumerate() {
+                *cell = rule.edge.cell(src, src_row, first_col + col as isize);
+            }
+            continue;
+        }
+        let start = src.idx(src_row as usize, origin.1);
+        padded_row[1..=width].copy_from_slice(&src.as_slice()[start..start + width]);
+        padded_row[0] = rule.edge.cell(src, src_row, first_col);
+        padded_row[width + 1] = rule
+            .edge
+            .cell(src, src_row, first_col + stride as isize - 1);
+    }
+
+    let mut changed = false;
+    for (row, cells) in cells.into_iter().enumerate() {
+        let (above, center, below) = (row * stride, (row + 1) * stride, (row + 2) * stride);
+        for (col, value) in cells.iter_mut().enumerate() {
+            let live_count = padded[above + col]
+                + padded[above + col + 1]
+                + padded[above + col + 2]
+                + padded[center + col]
+                + padded[center + col + 2]
+                + padded[below + col]
+                + padded[below + col + 1]
+                + padded[below + col + 2];
+            let next = rule.next_state(*value, live_count);
+            changed |= next != *value;
+            *value = next;
+        }
+    }
+    changed
+}
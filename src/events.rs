@@ -0,0 +1,99 @@
+//! Generations as JSON objects, one line each, for tools and notebooks
+//! following a simulation.
+
+use serde::Serialize;
+use std::io::{self, Write};
+
+use crate::matrix::{BoundingBox, Matrix};
+use crate::rle;
+use crate::stats::Generation;
+
+/// The figures of a generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Event {
+    pub tick: usize,
+    pub population: usize,
+    pub births: usize,
+    pub deaths: usize,
+    pub rows: usize,
+    pub cols: usize,
+    /// The smallest rectangle holding every live cell, `None` if all are dead.
+    pub bounding_box: Option<BoundingBox>,
+    /// The state hash of the board as 16 hex digits, equal for equal boards,
+    /// see `Matrix::state_hash`.
+    pub hash: String,
+    /// The cells that changed since the previous generation as RLE, the
+    /// whole board if it changed size, if changes are followed. The first
+    /// generation changed from an empty board.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<String>,
+}
+
+impl Event {
+    pub fn new(generation: &Generation, matrix: &Matrix) -> Self {
+        Event {
+            tick: generation.tick,
+            population: generation.population,
+            births: generation.births,
+            deaths: generation.deaths,
+            rows: matrix.row_count(),
+            cols: matrix.col_count(),
+            bounding_box: matrix.bounding_box(),
+            hash: format!("{:016x}", matrix.state_hash()),
+            changes: None,
+        }
+    }
+
+    /// Adds the cells that changed from `previous` to `matrix`, the board
+    /// of the event.
+    pub fn with_changes(mut self, previous: &Matrix, matrix: &Matrix) -> Self {
+        let same_size = (previous.row_count(), previous.col_count())
+            == (matrix.row_count(), matrix.col_count());
+        let changed = match same_size {
+            true => {
+                let mut changed = Matrix::new(matrix.row_count(), matrix.col_count());
+                let cells = previous.as_slice().iter().zip(matrix.as_slice());
+                for (cell, (&before, &after)) in changed.as_mut_slice().iter_mut().zip(cells) {
+                    *cell = u8::from((before != 0) != (after != 0));
+                }
+                rle::encode(&changed)
+            }
+            false => rle::encode(matrix),
+        };
+        self.changes = Some(changed);
+        self
+    }
+
+    /// Writes the event as a line of JSON.
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serde_json::to_writer(&mut writer, self)?;
+        writeln!(writer)
+    }
+}
+
+/// Writes an event for every generation to `writer` as they come, starting
+/// with the current generation of `game`.
+pub struct EventWriter<W: Write> {
+    writer: W,
+    /// The board of the previous generation, kept if changes are followed.
+    previous: Option<Matrix>,
+}
+
+impl<W: Write> EventWriter<W> {
+    pub fn new(writer: W, changes: bool, matrix: &Matrix) -> Self {
+        EventWriter {
+            writer,
+            previous: changes.then(|| Matrix::new(matrix.row_count(), matrix.col_count())),
+        }
+    }
+
+    pub fn write(&mut self, generation: &Generation, matrix: &Matrix) -> io::Result<()> {
+        let mut event = Event::new(generation, matrix);
+        if let Some(previous) = &mut self.previous {
+            event = event.with_changes(previous, matrix);
+            previous.clone_from(matrix);
+        }
+        event.write_json(&mut self.writer)?;
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,64 @@
+//! Patterns downloaded by name from the LifeWiki pattern archive. Every
+//! pattern is downloaded once and read from a local cache from then on.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::rle;
+
+const ARCHIVE_URL: &str = "https://conwaylife.com/patterns";
+
+/// Path of the RLE file of the pattern called `name`, e.g. `Gosper glider
+/// gun`, downloading it unless it is already cached.
+pub fn pattern(name: &str) -> io::Result<PathBuf> {
+    let name = archive_name(name)?;
+    let path = cache_dir().join(format!("{name}.rle"));
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let url = format!("{ARCHIVE_URL}/{name}.rle");
+    let text = ureq::get(&url)
+        .call()
+        .map_err(|err| io::Error::other(format!("{url}: {err}")))?
+        .into_string()?;
+    // an unknown name may be answered with a page rather than a pattern
+    rle::parse(&text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{url}: {err}")))?;
+
+    fs::create_dir_all(cache_dir())?;
+    // written under another name first, so an interrupted download is not cached
+    let partial = path.with_extension("rle.part");
+    fs::write(&partial, text)?;
+    fs::rename(partial, &path)?;
+    Ok(path)
+}
+
+/// The file name of a pattern in the archive, its name in lowercase without
+/// spaces or punctuation.
+fn archive_name(name: &str) -> io::Result<String> {
+    let archive_name: String = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect();
+
+    if archive_name.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid pattern name {name:?}"),
+        ));
+    }
+    Ok(archive_name)
+}
+
+/// Where downloaded patterns are kept, under the user's cache directory.
+fn cache_dir() -> PathBuf {
+    let cache = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir);
+    cache.join("conway-game-of-life").join("patterns")
+}
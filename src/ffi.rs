@@ -0,0 +1,105 @@
+//! A C ABI for embedding the game in C and C++ programs such as
+//! visualizers and game engines. Games are opaque pointers, made by
+//! `gol_new` and released by `gol_free`, and their cells are read straight
+//! from the board, one byte per cell, row after row:
+//!
+//! ```c
+//! GolGame *game = gol_new(64, 64, 42, 0.5);
+//! gol_tick(game, 10);
+//! size_t rows, cols;
+//! const uint8_t *cells = gol_cells(game, &rows, &cols);
+//! gol_free(game);
+//! ```
+//!
+//! Building with the `cdylib` feature writes the declarations to
+//! `include/conway_game_of_life.h`.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::game_of_life::GameOfLife;
+use crate::matrix::Matrix;
+
+/// A game behind a C pointer.
+pub struct GolGame {
+    game: GameOfLife,
+}
+
+/// A random board of `rows` by `cols` cells under Conway's rule, each alive
+/// with probability `fill`, 0 for an empty board. The game must be released
+/// with `gol_free`.
+#[no_mangle]
+pub extern "C" fn gol_new(rows: usize, cols: usize, seed: u64, fill: f64) -> *mut GolGame {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let matrix = Matrix::random(rows, cols, fill.clamp(0.0, 1.0), &mut rng);
+    Box::into_raw(Box::new(GolGame {
+        game: GameOfLife::new(matrix),
+    }))
+}
+
+/// Runs `ticks` generations.
+///
+/// # Safety
+///
+/// `game` must come from `gol_new` and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gol_tick(game: *mut GolGame, ticks: usize) {
+    let Some(game) = game.as_mut() else {
+        return;
+    };
+    for _ in 0..ticks {
+        game.game.tick();
+    }
+}
+
+/// Brings the cell at `(row, col)` to life, or kills it, if it lies on the
+/// board.
+///
+/// # Safety
+///
+/// `game` must come from `gol_new` and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gol_set_cell(game: *mut GolGame, row: usize, col: usize, alive: bool) {
+    if let Some(game) = game.as_mut() {
+        game.game.set_cell(row as isize, col as isize, alive);
+    }
+}
+
+/// Pointer to the first cell, 0 for dead and 1 for live cells, row after
+/// row, valid until the next call on the game. The size of the board is
+/// written to `rows` and `cols` unless they are null.
+///
+/// # Safety
+///
+/// `game` must come from `gol_new` and not be freed yet, `rows` and `cols`
+/// must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn gol_cells(
+    game: *const GolGame,
+    rows: *mut usize,
+    cols: *mut usize,
+) -> *const u8 {
+    let Some(game) = game.as_ref() else {
+        return std::ptr::null();
+    };
+    let matrix = game.game.matrix();
+    if let Some(rows) = rows.as_mut() {
+        *rows = matrix.row_count();
+    }
+    if let Some(cols) = cols.as_mut() {
+        *cols = matrix.col_count();
+    }
+    matrix.as_slice().as_ptr()
+}
+
+/// Releases a game, ignoring null.
+///
+/// # Safety
+///
+/// `game` must be null or come from `gol_new` and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gol_free(game: *mut GolGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
@@ -0,0 +1,150 @@
+use clap::ValueEnum;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+use crate::{life106, macrocell, mcell, plaintext, rle};
+
+/// File name standing for stdin when reading a board and for stdout when
+/// writing one.
+pub const STDIO: &str = "-";
+
+/// File formats boards are read from and written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// 2D array of 0 and 1
+    Json,
+    /// Run length encoded
+    Rle,
+    /// `O` for live and `.` for dead cells, one line per row
+    Plaintext,
+    /// Golly's quadtree of 8x8 leaves, for huge patterns
+    Macrocell,
+    /// MCell's board size and runs of cells
+    Mcell,
+    /// Coordinates of the live cells, one per line
+    #[value(name = "life106")]
+    Life106,
+}
+
+impl Format {
+    /// The format a file name suggests: RLE for `.rle`, plaintext for
+    /// `.cells` and `.txt`, macrocell for `.mc`, MCell for `.mcl` and `.l`,
+    /// Life 1.06 for `.lif` and `.life`, JSON otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("rle") => Format::Rle,
+            Some("cells" | "txt") => Format::Plaintext,
+            Some("mc") => Format::Macrocell,
+            Some("mcl" | "l") => Format::Mcell,
+            Some("lif" | "life") => Format::Life106,
+            _ => Format::Json,
+        }
+    }
+
+    /// The format a board read without a file name looks like, from its
+    /// first line: a header for macrocell, MCell and Life 1.06, `[` for
+    /// JSON, an `x = ` line after `#` comments for RLE, plaintext otherwise.
+    pub fn detect(text: &str) -> Self {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        match lines.clone().next() {
+            Some(line) if line.starts_with("[M2]") => return Format::Macrocell,
+            Some(line) if line.starts_with('[') => return Format::Json,
+            Some(line) if line.starts_with("#MCell") => return Format::Mcell,
+            Some(line) if line.starts_with("#Life") => return Format::Life106,
+            _ => {}
+        }
+        match lines.find(|line| !line.starts_with('#')) {
+            Some(line) if line.starts_with('x') && line.contains('=') => Format::Rle,
+            _ => Format::Plaintext,
+        }
+    }
+
+    pub fn decode(self, text: &str) -> Result<Matrix, String> {
+        self.decode_with_rule(text).map(|(matrix, _)| matrix)
+    }
+
+    /// Decodes a board along with the rule it names, which only RLE headers
+    /// do.
+    pub fn decode_with_rule(self, text: &str) -> Result<(Matrix, Option<Rule>), String> {
+        let matrix = match self {
+            Format::Json => serde_json::from_str(text).map_err(|err| err.to_string()),
+            Format::Rle => return rle::parse_with_rule(text),
+            Format::Plaintext => plaintext::parse(text),
+            Format::Macrocell => macrocell::parse(text),
+            Format::Mcell => mcell::parse(text),
+            Format::Life106 => life106::parse(text),
+        };
+        matrix.map(|matrix| (matrix, None))
+    }
+
+    pub fn encode(self, matrix: &Matrix) -> String {
+        self.encode_with_rule(matrix, &Rule::CONWAY)
+    }
+
+    /// Encodes a board evolving by `rule`, which only RLE headers name.
+    pub fn encode_with_rule(self, matrix: &Matrix, rule: &Rule) -> String {
+        match self {
+            Format::Json => serde_json::to_string(matrix).expect("json encode exception"),
+            Format::Rle => rle::encode_with_rule(matrix, rule),
+            Format::Plaintext => plaintext::encode(matrix),
+            Format::Macrocell => macrocell::encode(matrix),
+            Format::Mcell => mcell::encode(matrix),
+            Format::Life106 => life106::encode(matrix),
+        }
+    }
+}
+
+/// Reads a board from `path`, or from stdin if it is [`STDIO`], in `format`
+/// if set and otherwise in the format its name, or on stdin its text,
+/// suggests.
+pub fn read(path: &Path, format: Option<Format>) -> io::Result<Matrix> {
+    read_with_rule(path, format).map(|(matrix, _)| matrix)
+}
+
+/// Reads a board like `read`, along with the rule it names, if any.
+pub fn read_with_rule(path: &Path, format: Option<Format>) -> io::Result<(Matrix, Option<Rule>)> {
+    let (text, format) = if path == Path::new(STDIO) {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        let format = format.unwrap_or_else(|| Format::detect(&text));
+        (text, format)
+    } else {
+        let text = fs::read_to_string(path)?;
+        (text, format.unwrap_or_else(|| Format::from_path(path)))
+    };
+    format
+        .decode_with_rule(&text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Writes a board to `path`, or to stdout as RLE if it is [`STDIO`], in
+/// `format` if set and otherwise in the format its name suggests.
+pub fn write(path: &Path, matrix: &Matrix, format: Option<Format>) -> io::Result<()> {
+    write_with_rule(path, matrix, format, &Rule::CONWAY)
+}
+
+/// Writes a board evolving by `rule` like `write`, naming the rule if the
+/// format does.
+pub fn write_with_rule(
+    path: &Path,
+    matrix: &Matrix,
+    format: Option<Format>,
+    rule: &Rule,
+) -> io::Result<()> {
+    if path == Path::new(STDIO) {
+        let text = format.unwrap_or(Format::Rle).encode_with_rule(matrix, rule);
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(text.as_bytes())?;
+        return stdout.flush();
+    }
+    let format = format.unwrap_or_else(|| Format::from_path(path));
+    fs::write(path, format.encode_with_rule(matrix, rule))
+}
@@ -1,310 +1,1821 @@
 use clap::Parser;
-use rand::Rng;
-use rayon::prelude::*;
-use serde_json;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::borrow::Cow;
 use std::fs::File;
-use std::io::BufReader;
-use std::sync::Arc;
-use std::{fmt::Display, mem::swap, path::PathBuf, slice, thread};
+use std::io::{self, BufWriter, Write};
+#[cfg(any(feature = "metrics", feature = "remote"))]
+use std::net::SocketAddr;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+use std::{
+    fmt::Display,
+    mem::swap,
+    path::{Path, PathBuf},
+};
 
-use crate::matrix::Matrix;
+use crate::checkpoint::{Checkpoint, CHECKPOINT_VERSION};
+use crate::cycle::{Cycle, CycleDetector};
+use crate::edge::Edge;
+use crate::elementary::Elementary;
+use crate::engine::{EngineKind, TickEngine};
+#[cfg(feature = "fetch")]
+use crate::fetch;
+use crate::font;
+use crate::history::History;
+use crate::matrix::{Anchor, Matrix, Overflow};
+use crate::metrics::Metrics;
+use crate::noise::Noise;
+use crate::patterns::{self, Pattern};
+use crate::picture;
+#[cfg(feature = "metrics")]
+use crate::prometheus::Exporter;
+#[cfg(feature = "remote")]
+use crate::remote::{self, Broadcaster};
+use crate::render::image::{self, GifRecorder, ImageStyle};
+use crate::render::svg;
+use crate::render::text::Renderer;
+use crate::render::theme::Theme;
+use crate::render::video::VideoRecorder;
+use crate::render::Color;
+use crate::render::Frame;
+use crate::replay::{Edit, Replay, ReplayRecorder};
+use crate::rule::Rule;
+use crate::ruletable::{RuleTable, RuleTableEngine};
+use crate::scenario::{Action, Scenario};
+#[cfg(feature = "native")]
+use crate::snapshot;
+use crate::species::{self, SpeciesEngine};
+use crate::stats::{Generation, Stats};
+use crate::streaming::{self, Transition};
+use crate::symmetry::Symmetry;
+use crate::topology::{Topology, TopologyEngine};
+use crate::turmite::{Turmite, TurmiteEngine};
+use crate::viewport::Viewport;
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+/// Number of lines of a frame above the cells.
+const FRAME_HEADER_LINES: usize = 2;
+
+/// Number of lines of a frame besides the cells: the header, the stats, the
+/// population graph, a status line and the line below.
+pub const FRAME_CHROME_LINES: usize = 7;
+
+/// Label of the population graph below the stats.
+const GRAPH_LABEL: &str = "trend: ";
+
+/// Number of dead rows or columns added when live cells reach a boundary of an unbounded universe.
+const GROW_MARGIN: usize = 8;
+
+/// How many past generations are remembered for cycle detection.
+const CYCLE_HISTORY: usize = 64;
+
+/// Options of a simulation shared by the subcommands that run one. They can
+/// also be parsed on their own, e.g. to set up a game in benchmarks.
+#[derive(Parser, Debug, Clone)]
 pub struct GameOfLifeArgs {
-    /// The number of rows of the matrix, invalid if initial_file is specified
-    #[arg(long, default_value_t = 10)]
+    /// The number of rows of the matrix, invalid if initial_file is specified, the height --initial-image is scaled to
+    #[arg(long, default_value_t = 10, conflicts_with = "initial_file")]
     rows: usize,
-    /// The number of columns of the matrix, invalid if initial_file is specified
-    #[arg(long, default_value_t = 10)]
+    /// The number of columns of the matrix, invalid if initial_file is specified, the width --initial-image is scaled to
+    #[arg(long, default_value_t = 10, conflicts_with = "initial_file")]
     cols: usize,
-    /// Whether to loop back at matrix boundaries
+    /// How cells beyond the matrix boundaries are treated
+    #[arg(long, value_enum, default_value_t = Edge::Dead)]
+    edge: Edge,
+    /// Life-like rule in B/S notation, e.g. B36/S23 for HighLife
+    #[arg(long, default_value_t = Rule::CONWAY)]
+    rule: Rule,
+    /// Golly .rule file with an @TABLE or @TREE section, ticked instead of --rule by its own engine, its cells may have more than two states
+    #[arg(long, conflicts_with_all = ["rule", "low_memory"])]
+    rule_file: Option<PathBuf>,
+    /// Number of species sharing the rule, 2 for Immigration or 4 for QuadLife, newborn cells take the species of most of their parents
+    #[arg(long, value_parser = species::parse_species, conflicts_with_all = ["rule_file", "low_memory"])]
+    species: Option<u8>,
+    /// Wolfram code of an elementary cellular automaton, e.g. 30 or 110, every generation is a new bottom row computed from the one above as the board scrolls up
+    #[arg(long, conflicts_with_all = ["rule", "rule_file", "species", "unbounded", "low_memory"])]
+    rule_1d: Option<u8>,
+    /// Turmite walking over the board instead of the rule, turns per color such as RL for Langton's ant or a Golly turmite table such as "{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}"
+    #[arg(long, conflicts_with_all = ["rule", "rule_file", "species", "rule_1d", "unbounded", "low_memory"])]
+    turmite: Option<Turmite>,
+    /// Number of ants of the turmite, the first starts in the middle of the board and the others at random
+    #[arg(long, default_value_t = 1, requires = "turmite")]
+    ants: usize,
+    /// Bounded grid with its edges joined as in Golly, overrides rows, cols and edge, e.g. T100+20,50 for a torus shifted by 20 columns, K100*,50 for a Klein bottle, C100,50 for a cross-surface or S50 for a sphere
+    #[arg(long, conflicts_with_all = ["rows", "cols", "edge", "rule_file", "species", "rule_1d", "turmite", "unbounded", "low_memory"])]
+    topology: Option<Topology>,
+    /// Grow the universe whenever live cells reach its boundaries, the initial size is kept as viewport
     #[arg(long, default_value_t = false)]
-    loopback: bool,
-    /// 2D array json file of initial matrix state, if not set, a random matrix will be initialized.
+    unbounded: bool,
+    /// 2D array json, RLE, plaintext, macrocell, MCell or Life 1.06 file of initial matrix state, - for stdin, if not set, a random matrix will be initialized.
     #[arg(long)]
     initial_file: Option<PathBuf>,
+    /// PNG image scaled to --rows by --cols cells as initial state, its pixels darker than --threshold alive
+    #[arg(long, conflicts_with_all = ["initial_file", "pattern"])]
+    initial_image: Option<PathBuf>,
+    /// Luminance between 0 for black and 1 for white below which pixels of --initial-image are alive
+    #[arg(long, value_parser = parse_probability, default_value_t = 0.5, requires = "initial_image")]
+    threshold: f64,
+    /// Dither --initial-image, so that gray areas become live cells of matching density
+    #[arg(long, default_value_t = false, requires = "initial_image")]
+    dither: bool,
+    /// Name of a pattern to download from the LifeWiki pattern archive as initial state, e.g. "Gosper glider gun", cached after the first download
+    #[cfg(feature = "fetch")]
+    #[arg(long, conflicts_with_all = ["initial_file", "rows", "cols", "pattern"])]
+    fetch_pattern: Option<String>,
+    /// Seed of the random initial state, a random seed is used if not set
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Probability of each cell being alive in the random initial state
+    #[arg(long, value_parser = parse_probability, default_value_t = 0.5)]
+    fill_prob: f64,
+    /// Symmetry of the random initial state, c4 and d8 need as many rows as columns
+    #[arg(long, value_enum)]
+    symmetry: Option<Symmetry>,
+    /// Fill only a centered region of this many rows and columns of the random initial state, e.g. 20x20, the rest of the board starts dead
+    #[arg(long, value_parser = parse_size)]
+    fill_region: Option<(usize, usize)>,
+    /// Probability of every dead cell coming alive spontaneously each generation, drawn from --seed
+    #[arg(long, value_parser = parse_probability, default_value_t = 0.0)]
+    birth_noise: f64,
+    /// Probability of every live cell dying spontaneously each generation, drawn from --seed
+    #[arg(long, value_parser = parse_probability, default_value_t = 0.0)]
+    death_noise: f64,
+    /// Name of a built-in pattern to place on the board, e.g. glider or gosper-glider-gun
+    #[arg(long, value_parser = patterns::parse)]
+    pattern: Option<&'static Pattern>,
+    /// Text to write in live cells with the built-in 5x7 font, lines separated by newlines
+    #[arg(long, value_parser = parse_text, conflicts_with = "pattern")]
+    text: Option<String>,
+    /// Row and column of the top-left corner of the placed pattern or text
+    #[arg(long, value_parser = parse_position, default_value = "0,0")]
+    at: (usize, usize),
+    /// Built-in pattern to place at a row and column, e.g. glider@5,5, can be given several times
+    #[arg(long, value_parser = parse_placement)]
+    place: Vec<(&'static Pattern, (usize, usize))>,
+    /// What happens to pattern cells placed beyond the board boundaries
+    #[arg(long, value_enum, default_value_t = Overflow::Clip)]
+    overflow: Overflow,
+    /// How the next generation is computed
+    #[arg(long, value_enum, default_value_t = EngineKind::Serial)]
+    engine: EngineKind,
+    /// Number of rows per rayon task in the rayon-chunked engine, by default as many as fit in 256 KiB of cache
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    chunk_rows: Option<u32>,
+    /// Number of OS threads in the threads engine, by default as many as the machine runs in parallel, at most one per row
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    workers: Option<u32>,
+    /// Tick the board in place without a second copy of it, overrides the engine, use with --history 0 on very large boards
+    #[arg(long, default_value_t = false, conflicts_with_all = ["birth_noise", "death_noise"])]
+    low_memory: bool,
+    /// Keep the board in this file rather than in memory, one byte per cell, for boards larger than memory, e.g. --mmap board.bin --rows 100000 --cols 100000; a new file starts like any board, an existing one where it was left; implies --low-memory and --history 0
+    #[cfg(feature = "native")]
+    #[arg(long, conflicts_with_all = ["birth_noise", "death_noise", "rule_file", "species", "rule_1d", "turmite", "topology", "unbounded", "initial_file", "initial_image", "fill_region", "symmetry", "resume"])]
+    mmap: Option<PathBuf>,
+    /// Stop the simulation once the universe becomes static or starts repeating
     #[arg(long, default_value_t = false)]
-    /// whether to enable parallelism supported by rayon
-    parallel: bool,
-    #[arg(long, default_value_t = false)]
-    /// Whether to enable parallelism supported by native OS thread
-    parallel_naive: bool,
-    /// Number of OS threads in parallel_naive strategy
-    #[arg(long, default_value_t = 2)]
-    workers: usize,
+    stop_on_cycle: bool,
+    /// Stop the simulation after this many ticks
+    #[arg(long)]
+    max_ticks: Option<usize>,
+    /// Stop the simulation after this many seconds of wall-clock time, fractions allowed, whatever generation it reached
+    #[arg(long, value_parser = parse_seconds)]
+    max_seconds: Option<Duration>,
+    /// Number of past generations kept for stepping backwards
+    #[arg(long, default_value_t = 100)]
+    history: usize,
+    /// File of events scripted to happen at given generations, e.g. "100 place glider 5,5"
+    #[arg(long)]
+    scenario: Option<PathBuf>,
+    /// Resume the simulation from a checkpoint file
+    #[arg(long)]
+    resume: Option<PathBuf>,
+    /// Record the session to a replay file, its starting state and every later edit with its generation, to play it back exactly with --replay
+    #[arg(long, conflicts_with_all = ["birth_noise", "death_noise", "rule_file", "species", "rule_1d", "turmite", "topology"])]
+    record_replay: Option<PathBuf>,
+    /// Play back a session recorded with --record-replay instead of starting a new one
+    #[arg(long, conflicts_with_all = ["initial_file", "pattern", "place", "seed", "rule", "edge", "unbounded", "resume", "scenario", "birth_noise", "death_noise", "rule_file", "species", "rule_1d", "turmite", "topology"])]
+    replay: Option<PathBuf>,
+    /// Write per-generation population statistics to this CSV file
+    #[arg(long)]
+    stats_out: Option<PathBuf>,
+    /// Record the simulation as an animated GIF to this file
+    #[arg(long)]
+    record: Option<PathBuf>,
+    /// Number of generations drawn to record
+    #[arg(long, default_value_t = 100)]
+    record_frames: usize,
+    /// Record every generation drawn as a video to this file, e.g. out.mp4 or out.webm, encoded by ffmpeg which must be installed
+    #[arg(long)]
+    video: Option<PathBuf>,
+    /// Frames per second of the video
+    #[arg(long, default_value_t = 10)]
+    video_fps: u32,
+    /// Draw and record only every Nth generation, a time-lapse: the simulation runs at full speed in between and the tick count keeps counting every generation
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    render_every: u32,
+    /// Width and height of a cell in pixels when rendering images
+    #[arg(long, default_value_t = 8)]
+    cell_size: usize,
+    /// Color of live cells when rendering images
+    #[arg(long, default_value_t = Color::WHITE)]
+    alive_color: Color,
+    /// Color of dead cells when rendering images
+    #[arg(long, default_value_t = Color::BLACK)]
+    dead_color: Color,
+    /// Color of the outline of live cells in SVG images, none if not set
+    #[arg(long)]
+    stroke_color: Option<Color>,
+    /// Width of the outline of live cells in SVG images, in pixels at the cell size
+    #[arg(long, default_value_t = 1.0)]
+    stroke_width: f64,
+    /// Write a snapshot of the board every N ticks
+    #[arg(long)]
+    snapshot_every: Option<usize>,
+    /// Directory snapshots are written to
+    #[arg(long, default_value = "snapshots")]
+    snapshot_dir: PathBuf,
+    /// What snapshots are written as
+    #[arg(long, value_enum, default_value_t = SnapshotFormat::Png)]
+    snapshot_format: SnapshotFormat,
+    /// Address to serve the generation, population, tick latency and engine on in the Prometheus format, e.g. 0.0.0.0:9100
+    #[cfg(feature = "metrics")]
+    #[arg(long)]
+    metrics_addr: Option<SocketAddr>,
+    /// Address to broadcast every generation on over WebSocket, e.g. ws://0.0.0.0:9000, for view --connect or a browser
+    #[cfg(feature = "remote")]
+    #[arg(long, value_parser = remote::parse_serve_addr)]
+    serve: Option<SocketAddr>,
+}
+
+impl GameOfLifeArgs {
+    /// The same options reading the initial board from `path`.
+    pub fn with_initial_file(&self, path: &Path) -> Self {
+        GameOfLifeArgs {
+            initial_file: Some(path.to_path_buf()),
+            initial_image: None,
+            #[cfg(feature = "fetch")]
+            fetch_pattern: None,
+            ..self.clone()
+        }
+    }
+
+    /// The same options generating a board of `rows` by `cols` cells.
+    pub fn with_size(&self, rows: usize, cols: usize) -> Self {
+        GameOfLifeArgs {
+            rows,
+            cols,
+            ..self.clone()
+        }
+    }
+
+    pub fn engine(&self) -> EngineKind {
+        self.engine
+    }
+
+    /// Whether the board is kept in a file with `--mmap`.
+    fn mapped(&self) -> bool {
+        #[cfg(feature = "native")]
+        return self.mmap.is_some();
+        #[cfg(not(feature = "native"))]
+        return false;
+    }
+
+    /// Probability of each cell being alive in random boards.
+    pub fn fill_prob(&self) -> f64 {
+        self.fill_prob
+    }
+
+    /// The Life-like rule with the edge mode, regardless of other rules such
+    /// as rule files or species.
+    pub fn rule(&self) -> Rule {
+        self.rule.with_edge(self.edge)
+    }
+
+    /// Builds `kind` tuned by the engine options, e.g. to compare it with
+    /// the engine of the game.
+    pub fn build_engine(&self, kind: EngineKind) -> Box<dyn TickEngine> {
+        let option = |value: Option<u32>| value.map(|value| value as usize);
+        kind.build(option(self.chunk_rows), option(self.workers))
+    }
+
+    /// The file the initial board is read from, downloaded first if it is
+    /// a pattern of the archive.
+    fn initial_file(&self) -> Option<PathBuf> {
+        #[cfg(feature = "fetch")]
+        if let Some(name) = &self.fetch_pattern {
+            return Some(fetch::pattern(name).expect("pattern fetch exception"));
+        }
+        self.initial_file.clone()
+    }
+
+    /// Name of the engine ticking the game, as reported in metrics.
+    #[cfg(feature = "metrics")]
+    fn engine_name(&self) -> String {
+        let name = if self.rule_file.is_some() {
+            "rule-table"
+        } else if self.species.is_some() {
+            "species"
+        } else if self.rule_1d.is_some() {
+            "elementary"
+        } else if self.turmite.is_some() {
+            "turmite"
+        } else if self.topology.is_some() {
+            "topology"
+        } else if self.low_memory {
+            "in-place"
+        } else {
+            return clap::ValueEnum::to_possible_value(&self.engine).map_or_else(
+                || format!("{:?}", self.engine),
+                |value| value.get_name().to_string(),
+            );
+        };
+        name.to_string()
+    }
+
+    fn image_style(&self) -> ImageStyle {
+        ImageStyle {
+            cell_size: self.cell_size,
+            alive: self.alive_color,
+            dead: self.dead_color,
+            stroke: self.stroke_color,
+            stroke_width: self.stroke_width,
+        }
+    }
+}
+
+/// Parses a `row,col` position on the command line.
+fn parse_position(s: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("invalid position {s:?}, expected ROW,COL");
+    let (row, col) = s.split_once(',').ok_or_else(invalid)?;
+    let row = row.trim().parse().map_err(|_| invalid())?;
+    let col = col.trim().parse().map_err(|_| invalid())?;
+    Ok((row, col))
+}
+
+/// Parses a `name@row,col` pattern placement on the command line.
+fn parse_placement(s: &str) -> Result<(&'static Pattern, (usize, usize)), String> {
+    let (name, position) = s
+        .split_once('@')
+        .ok_or_else(|| format!("invalid placement {s:?}, expected NAME@ROW,COL"))?;
+    Ok((patterns::parse(name)?, parse_position(position)?))
+}
+
+/// The random initial board: cells alive with probability `--fill-prob` in
+/// the centered `--fill-region`, or all over the board, made symmetric by
+/// `--symmetry`.
+fn random_board(args: &GameOfLifeArgs, rows: usize, cols: usize, rng: &mut StdRng) -> Matrix {
+    let (region_rows, region_cols) = args.fill_region.unwrap_or((rows, cols));
+    if region_rows > rows || region_cols > cols {
+        invalid_value(format!(
+            "--fill-region {region_rows}x{region_cols} exceeds the board of {rows} x {cols} cells"
+        ));
+    }
+    let mut region = empty_board(region_rows, region_cols);
+    region.randomize(args.fill_prob, rng);
+    if let Some(symmetry) = args.symmetry {
+        if symmetry.needs_square() && region_rows != region_cols {
+            let name = format!("{symmetry:?}").to_lowercase();
+            invalid_value(format!(
+                "--symmetry {name} needs as many rows as columns, not {region_rows} x {region_cols}"
+            ));
+        }
+        symmetry.apply(&mut region);
+    }
+    if (region_rows, region_cols) == (rows, cols) {
+        return region;
+    }
+
+    let mut matrix = empty_board(rows, cols);
+    let (top, left) = ((rows - region_rows) / 2, (cols - region_cols) / 2);
+    matrix.blit(&region, top, left, Overflow::Clip);
+    matrix
+}
+
+/// A board of dead cells of the size given on the command line, exiting
+/// like clap does if it is empty or too large.
+fn empty_board(rows: usize, cols: usize) -> Matrix {
+    Matrix::try_new(rows, cols)
+        .unwrap_or_else(|err| invalid_value(format!("invalid board size: {err}")))
+}
+
+/// Exits like clap does on an invalid combination of options.
+fn invalid_value(message: String) -> ! {
+    clap::Error::raw(
+        clap::error::ErrorKind::ValueValidation,
+        format!("{message}\n"),
+    )
+    .exit()
+}
+
+/// Parses a `ROWSxCOLS` size on the command line.
+fn parse_size(s: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("invalid size {s:?}, expected ROWSxCOLS");
+    let (rows, cols) = s.split_once('x').ok_or_else(invalid)?;
+    let rows = rows.trim().parse().map_err(|_| invalid())?;
+    let cols = cols.trim().parse().map_err(|_| invalid())?;
+    Ok((rows, cols))
+}
+
+/// Checks that the font has a glyph for every character of a text on the
+/// command line.
+fn parse_text(s: &str) -> Result<String, String> {
+    font::render(s)?;
+    Ok(s.to_string())
+}
+
+/// Parses a positive number of seconds on the command line.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(duration)) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!(
+            "invalid number of seconds {s:?}, expected a number above 0"
+        )),
+    }
+}
+
+/// Parses a probability between 0 and 1 on the command line.
+pub(crate) fn parse_probability(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(p) if (0.0..=1.0).contains(&p) => Ok(p),
+        _ => Err(format!(
+            "invalid probability {s:?}, expected a number between 0 and 1"
+        )),
+    }
+}
+
+/// Reads the initial file, rejecting RLE patterns of another Life-like rule
+/// than the one the game runs.
+fn load_board(args: &GameOfLifeArgs, path: &Path) -> Matrix {
+    let life_like = args.rule_file.is_none() && args.rule_1d.is_none() && args.turmite.is_none();
+    match life_like {
+        true => Matrix::load_for_rule(path, &args.rule()),
+        false => Matrix::load(path),
+    }
+    .expect("board decode exception")
+}
+
+/// The board a game starts from: the initial file or image, a pattern, text
+/// or ants on an empty board or a random soup.
+fn initial_board(args: &GameOfLifeArgs, rows: usize, cols: usize, rng: &mut StdRng) -> Matrix {
+    let mut matrix = match args.initial_file() {
+        // the board of a topology has its size, files are placed in its top-left corner
+        Some(path) if args.topology.is_some() => {
+            let mut matrix = empty_board(rows, cols);
+            matrix.blit(&load_board(args, &path), 0, 0, args.overflow);
+            matrix
+        }
+        Some(path) => load_board(args, &path),
+        None => match &args.initial_image {
+            Some(path) => {
+                let mut matrix = empty_board(rows, cols);
+                let luminance = picture::load_luminance(path).expect("image decode exception");
+                let luminance = picture::scale(&luminance, rows, cols);
+                let image = picture::threshold(&luminance, args.threshold as f32, args.dither);
+                matrix.blit(&image, 0, 0, Overflow::Clip);
+                matrix
+            }
+            // a pattern is placed on an empty board rather than a random one, and so are ants
+            None if args.pattern.is_some()
+                || args.text.is_some()
+                || !args.place.is_empty()
+                || args.turmite.is_some() =>
+            {
+                empty_board(rows, cols)
+            }
+            None => random_board(args, rows, cols, rng),
+        },
+    };
+
+    place_patterns(args, &mut matrix);
+    // the first generation of a one-dimensional automaton is the bottom row
+    if args.rule_1d.is_some() {
+        let bottom = matrix.row_count().saturating_sub(1) * matrix.col_count();
+        matrix.as_mut_slice()[..bottom].fill(0);
+    }
+    if let Some(turmite) = &args.turmite {
+        turmite.place_ants(&mut matrix, args.ants, rng);
+    }
+    // boards of a single species are mixed up, others keep their species
+    if let Some(count) = args.species {
+        if matrix.as_slice().iter().all(|&cell| cell <= 1) {
+            species::scatter(&mut matrix, count, rng);
+        }
+    }
+    matrix
+}
+
+/// Puts the pattern, text and placed patterns of `args` on `matrix`.
+fn place_patterns(args: &GameOfLifeArgs, matrix: &mut Matrix) {
+    if let Some(pattern) = args.pattern {
+        let (row, col) = args.at;
+        matrix.blit(&pattern.matrix(), row, col, args.overflow);
+    }
+    if let Some(text) = &args.text {
+        let (row, col) = args.at;
+        let text = font::render(text).expect("text render exception");
+        matrix.blit(&text, row, col, args.overflow);
+    }
+    for (pattern, (row, col)) in &args.place {
+        matrix.blit(&pattern.matrix(), *row, *col, args.overflow);
+    }
 }
 
+/// The board kept in the file at `path` by `--mmap`. A new file is filled
+/// like any board, at random unless a pattern, text or placed patterns go on
+/// it; an existing one is picked up as it was left.
+#[cfg(feature = "native")]
+fn mapped_board(
+    args: &GameOfLifeArgs,
+    path: &Path,
+    rows: usize,
+    cols: usize,
+    rng: &mut StdRng,
+) -> Matrix {
+    #[cfg(not(unix))]
+    invalid_value(format!(
+        "--mmap {} needs a Unix system to map the board into memory",
+        path.display()
+    ));
+    #[cfg(unix)]
+    {
+        let (mut matrix, created) = Matrix::map_file(path, rows, cols).expect("mmap exception");
+        if !created {
+            return matrix;
+        }
+        if args.pattern.is_none() && args.text.is_none() && args.place.is_empty() {
+            matrix.randomize(args.fill_prob, rng);
+        }
+        place_patterns(args, &mut matrix);
+        matrix
+    }
+}
+
+/// A seed for when none is given.
+#[cfg(feature = "native")]
+fn random_seed() -> u64 {
+    use rand::Rng;
+    rand::thread_rng().gen()
+}
+
+/// Without the native feature there is no source of entropy, so the seed is fixed.
+#[cfg(not(feature = "native"))]
+fn random_seed() -> u64 {
+    0
+}
+
+/// A generation as kept in the history for stepping backwards and forwards.
+struct State {
+    matrix: Matrix,
+    origin: (usize, usize),
+    ticks: usize,
+    ages: Option<Matrix>,
+    activity: Option<Matrix<u32>>,
+    generation: Generation,
+}
+
+/// What the snapshots written every few ticks are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SnapshotFormat {
+    /// A PNG image of the board
+    Png,
+    /// A checkpoint compressed with zstd, to resume from with --resume
+    #[cfg(feature = "native")]
+    Snap,
+}
+
+/// Why a simulation ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    MaxTicks(usize),
+    /// The time limit ran out after `generations` generations.
+    MaxSeconds {
+        limit: Duration,
+        generations: usize,
+    },
+    Cycle(Cycle),
+    /// A tick hook asked to stop.
+    Hook,
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StopReason::MaxTicks(max_ticks) => write!(f, "reached max ticks ({max_ticks})"),
+            StopReason::MaxSeconds { limit, generations } => write!(
+                f,
+                "reached max seconds ({}) after {generations} generations",
+                limit.as_secs_f64()
+            ),
+            StopReason::Cycle(cycle) => write!(f, "{cycle}"),
+            StopReason::Hook => write!(f, "stopped by a tick hook"),
+        }
+    }
+}
+
+/// A limit on the wall-clock time a simulation runs for.
+#[derive(Debug, Clone, Copy)]
+struct TimeLimit {
+    limit: Duration,
+    deadline: Instant,
+    /// The generation the limit was set at.
+    first_tick: usize,
+}
+
+/// A callback run after every tick with the statistics of the new generation
+/// and the board, which stops the simulation by returning `ControlFlow::Break`.
+pub type TickHook = Box<dyn FnMut(&Generation, &Matrix) -> ControlFlow<()> + Send>;
+
 pub struct GameOfLife {
     rows: usize,
     cols: usize,
     matrix: Matrix,
+    /// The buffer the engine writes the next generation to, empty in low memory mode.
     backup_matrix: Matrix,
+    low_memory: bool,
     ticks: usize,
-    parallel: bool,
-    parallel_naive: bool,
-    workers: usize,
-    loopback: bool,
+    seed: u64,
+    engine: Box<dyn TickEngine>,
+    rule: Rule,
+    /// Random births and deaths after every generation, drawn from the seed
+    noise: Noise,
+    unbounded: bool,
+    /// Position of the initial top-left cell inside the grown matrix
+    origin: (usize, usize),
+    /// The initial board, which is what images show of an unbounded universe.
+    board_viewport: Viewport,
+    /// The part of the universe shown in the terminal.
+    viewport: Viewport,
+    stop_on_cycle: bool,
+    max_ticks: Option<usize>,
+    time_limit: Option<TimeLimit>,
+    /// Generations run per frame by `step`.
+    step_size: usize,
+    /// Only generations that are a multiple of it are drawn and recorded.
+    render_every: usize,
+    cycle_detector: CycleDetector,
+    cycle: Option<Cycle>,
+    history: History<State>,
+    scenario: Scenario,
+    /// Where the changes made to the board are recorded, if anywhere.
+    replay: Option<ReplayRecorder>,
+    stats: Stats,
+    metrics: Metrics,
+    hooks: Vec<TickHook>,
+    /// Whether a hook asked to stop.
+    stopped: bool,
+    recorder: Option<GifRecorder<BufWriter<File>>>,
+    video: Option<VideoRecorder>,
+    renderer: Renderer,
+    theme: Theme,
+    /// Number of generations each cell has been alive, only tracked for colored themes
+    ages: Option<Matrix>,
+    /// Number of generations each cell has ever been alive, only tracked for
+    /// the heatmap renderer
+    activity: Option<Matrix<u32>>,
+    image_style: ImageStyle,
+    snapshot_every: Option<usize>,
+    snapshot_dir: PathBuf,
+    snapshot_format: SnapshotFormat,
+    /// Where the progress of the simulation is published for Prometheus.
+    #[cfg(feature = "metrics")]
+    exporter: Option<Exporter>,
 }
 
 impl GameOfLife {
     pub fn from_args(args: &GameOfLifeArgs) -> Self {
-        let matrix = match &args.initial_file {
-            Some(path) => {
-                let file = File::open(path).expect("io exception");
-                let reader = BufReader::new(file);
-                let matrix: Matrix =
-                    serde_json::from_reader(reader).expect("json decode exception");
-                matrix
-            }
-            None => {
-                let mut rng = rand::thread_rng();
-                let mut matrix = Matrix::new(args.rows, args.cols);
+        let seed = args.seed.unwrap_or_else(random_seed);
+        let (rows, cols) = match args.topology {
+            Some(topology) => (topology.rows, topology.cols),
+            None => (args.rows, args.cols),
+        };
+        let mut rng = StdRng::seed_from_u64(seed);
+        #[cfg(feature = "native")]
+        let matrix = match &args.mmap {
+            Some(path) => mapped_board(args, path, rows, cols, &mut rng),
+            None => initial_board(args, rows, cols, &mut rng),
+        };
+        #[cfg(not(feature = "native"))]
+        let matrix = initial_board(args, rows, cols, &mut rng);
 
-                for row in 0..args.rows {
-                    for col in 0..args.cols {
-                        let val = rng.gen_range(0..=1);
-                        matrix.set(row, col, val);
-                    }
-                }
+        // a worker of the threads engine without a row of its own would idle
+        #[cfg(feature = "native")]
+        {
+            let workers = args.workers.map_or(0, |workers| workers as usize);
+            if args.engine == EngineKind::Threads && workers > matrix.row_count() {
+                invalid_value(format!(
+                    "--workers {workers} exceeds the {} rows of the board, the threads engine gives each worker at least one row",
+                    matrix.row_count()
+                ));
+            }
+        }
 
-                matrix
+        let mut game = Self::new(matrix);
+        game.seed = seed;
+        game.engine = match &args.rule_file {
+            Some(path) => {
+                let table = RuleTable::load(path).expect("rule table exception");
+                Box::new(RuleTableEngine::new(table))
             }
+            None => match (args.species, args.rule_1d, &args.turmite, args.topology) {
+                (Some(count), ..) => Box::new(SpeciesEngine::new(count)),
+                (None, Some(code), ..) => Box::new(Elementary::new(code)),
+                (None, None, Some(turmite), _) => Box::new(TurmiteEngine::new(turmite.clone())),
+                (None, None, None, Some(topology)) => Box::new(TopologyEngine::new(topology)),
+                (None, None, None, None) => args.build_engine(args.engine),
+            },
         };
+        game.set_low_memory(args.low_memory || args.mapped());
+        game.rule = args.rule.with_edge(args.edge);
+        game.noise = Noise::new(args.birth_noise, args.death_noise);
+        game.set_unbounded(args.unbounded);
+        game.stop_on_cycle = args.stop_on_cycle;
+        game.max_ticks = args.max_ticks;
+        game.set_max_seconds(args.max_seconds);
+        // every generation kept would be a copy of a board larger than memory
+        game.history = History::new(if args.mapped() { 0 } else { args.history });
+        if let Some(path) = &args.resume {
+            game.restore_checkpoint(path).expect("checkpoint exception");
+        }
+        if let Some(path) = &args.scenario {
+            game.set_scenario(Scenario::load(path).expect("scenario exception"));
+        }
+        if let Some(path) = &args.replay {
+            game.play_replay(Replay::load(path).expect("replay exception"));
+        }
+        if let Some(path) = &args.record_replay {
+            game.record_replay(path).expect("io exception");
+        }
+        game.image_style = args.image_style();
+        game.snapshot_every = args.snapshot_every;
+        game.snapshot_dir = args.snapshot_dir.clone();
+        game.snapshot_format = args.snapshot_format;
+        game.render_every = args.render_every as usize;
 
-        let rows = matrix.rows;
-        let cols = matrix.cols;
+        if let Some(path) = &args.stats_out {
+            let file = File::create(path).expect("io exception");
+            let mut writer = BufWriter::new(file);
+            writeln!(writer, "{}", Generation::CSV_HEADER).expect("io exception");
+            game.stats
+                .latest()
+                .write_csv(&mut writer)
+                .expect("io exception");
+            game.on_tick(move |generation, _| {
+                generation.write_csv(&mut writer).expect("io exception");
+                writer.flush().expect("io exception");
+                ControlFlow::Continue(())
+            });
+        }
 
-        GameOfLife {
+        game.recorder = args.record.as_ref().map(|path| {
+            GifRecorder::create(path, &game.matrix, args.image_style(), args.record_frames)
+                .expect("gif encode exception")
+        });
+        game.video = args.video.as_ref().map(|path| {
+            VideoRecorder::create(path, &game.matrix, args.image_style(), args.video_fps)
+                .expect("video encode exception")
+        });
+
+        if args.snapshot_every.is_some() {
+            std::fs::create_dir_all(&args.snapshot_dir).expect("io exception");
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(addr) = args.metrics_addr {
+            let exporter = Exporter::serve(addr).expect("metrics endpoint exception");
+            exporter.info(
+                "gameoflife_engine_info",
+                "Engine computing the generations",
+                &[("engine", &args.engine_name())],
+            );
+            game.set_exporter(exporter);
+        }
+
+        #[cfg(feature = "remote")]
+        if let Some(addr) = args.serve {
+            let broadcaster = Broadcaster::serve(addr).expect("websocket exception");
+            broadcaster.send(&game.matrix, game.ticks as u64);
+            game.on_tick(move |generation, matrix| {
+                broadcaster.send(matrix, generation.tick as u64);
+                ControlFlow::Continue(())
+            });
+        }
+
+        game.record_frame();
+        game.write_snapshot();
+        game
+    }
+
+    /// A serial simulation of `matrix` with dead edges and no stop conditions or outputs.
+    pub fn new(matrix: Matrix) -> Self {
+        let rows = matrix.row_count();
+        let cols = matrix.col_count();
+
+        let mut game = GameOfLife {
             rows,
             cols,
-            matrix,
-            backup_matrix: Matrix::new(rows, cols),
+            // allocated by the first tick, never for boards ticked in place
+            backup_matrix: Matrix::new(0, 0),
+            low_memory: false,
             ticks: 0,
-            parallel: args.parallel,
-            parallel_naive: args.parallel_naive,
-            workers: args.workers,
-            loopback: args.loopback,
+            seed: 0,
+            engine: EngineKind::Serial.build(None, None),
+            rule: Rule::CONWAY,
+            noise: Noise::default(),
+            unbounded: false,
+            origin: (0, 0),
+            board_viewport: Viewport::new(rows, cols),
+            viewport: Viewport::new(rows, cols),
+            stop_on_cycle: false,
+            max_ticks: None,
+            time_limit: None,
+            step_size: 1,
+            render_every: 1,
+            cycle_detector: CycleDetector::new(CYCLE_HISTORY),
+            cycle: None,
+            history: History::new(0),
+            scenario: Scenario::default(),
+            replay: None,
+            stats: Stats::new(&matrix),
+            metrics: Metrics::default(),
+            hooks: Vec::new(),
+            stopped: false,
+            recorder: None,
+            video: None,
+            renderer: Renderer::Ascii,
+            theme: Theme::Mono,
+            ages: None,
+            activity: None,
+            image_style: ImageStyle::default(),
+            snapshot_every: None,
+            snapshot_dir: PathBuf::from("snapshots"),
+            snapshot_format: SnapshotFormat::Png,
+            #[cfg(feature = "metrics")]
+            exporter: None,
+            matrix,
+        };
+        game.detect_cycle();
+        game
+    }
+
+    /// Grow the universe whenever live cells reach its boundaries, which
+    /// implies dead edges.
+    pub fn set_unbounded(&mut self, unbounded: bool) {
+        self.unbounded = unbounded;
+        if unbounded {
+            self.rule.edge = Edge::Dead;
         }
     }
 
-    pub fn tick(&mut self) {
-        if self.parallel_naive {
-            self.parallel_naive_tick();
-        } else if self.parallel {
-            self.parallel_tick();
-        } else {
-            self.serial_tick();
+    /// Ticks the game with `engine` from now on, e.g. an engine of your own.
+    pub fn set_engine(&mut self, engine: Box<dyn TickEngine>) {
+        self.engine = engine;
+    }
+
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    /// Changes the rule, edge mode included. Unbounded games keep dead edges.
+    pub fn set_rule(&mut self, rule: Rule) {
+        if rule.with_edge(self.rule.edge) != self.rule {
+            self.record_edit(Edit::Rule(rule));
         }
+        self.rule = rule;
+        self.set_unbounded(self.unbounded);
+        self.restart_cycle_detection();
     }
 
-    fn serial_tick(&mut self) {
-        self.ticks += 1;
+    pub fn noise(&self) -> &Noise {
+        &self.noise
+    }
+
+    /// Perturbs every following generation with `noise`, reproducibly for
+    /// the same seed. In low memory mode, cells the rule and the noise both
+    /// flip count as a birth and a death.
+    pub fn set_noise(&mut self, noise: Noise) {
+        self.noise = noise;
+    }
+
+    /// Publishes the progress of the simulation to `exporter` after every tick.
+    #[cfg(feature = "metrics")]
+    pub fn set_exporter(&mut self, exporter: Exporter) {
+        self.exporter = Some(exporter);
+        self.export_metrics();
+    }
+
+    /// Ticks the board in place instead of with the engine, which needs a
+    /// second board to write the next generation to.
+    pub fn set_low_memory(&mut self, low_memory: bool) {
+        self.low_memory = low_memory;
+        self.backup_matrix = self.new_backup_matrix();
+    }
 
-        for idx in 0..self.matrix.size() {
-            let value = self.backup_matrix.matrix.get_mut(idx).unwrap();
-            Self::write_next_tick_state(self.loopback, &self.matrix, idx, value);
+    /// Plays the events of `scenario` from now on, those of the current
+    /// generation right away.
+    pub fn set_scenario(&mut self, scenario: Scenario) {
+        self.scenario = scenario;
+        if self.play_scenario() {
+            self.edited();
         }
+    }
 
-        swap(&mut self.matrix, &mut self.backup_matrix);
+    /// Starts over from the state `replay` starts from and plays its changes
+    /// at their generations.
+    pub fn play_replay(&mut self, replay: Replay) {
+        self.resume(replay.start);
+        self.set_scenario(replay.scenario);
     }
 
-    fn parallel_tick(&mut self) {
-        self.ticks += 1;
+    /// Records the changes made to the board from now on, along with the
+    /// current state, to a replay file at `path`, which is written when the
+    /// game goes away.
+    pub fn record_replay<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.replay = Some(ReplayRecorder::create(path, self.checkpoint())?);
+        Ok(())
+    }
 
-        let matrix = &self.matrix;
-        let loopback = self.loopback;
-
-        self.backup_matrix
-            .matrix
-            .par_iter_mut()
-            .enumerate()
-            .for_each(|(idx, value)| {
-                Self::write_next_tick_state(loopback, &matrix, idx, value);
-            });
+    /// Runs `hook` after every tick from now on, e.g. to collect metrics or
+    /// stream frames. Once a hook returns `ControlFlow::Break` the simulation
+    /// is finished.
+    pub fn on_tick<F>(&mut self, hook: F)
+    where
+        F: FnMut(&Generation, &Matrix) -> ControlFlow<()> + Send + 'static,
+    {
+        self.hooks.push(Box::new(hook));
+    }
 
-        swap(&mut self.matrix, &mut self.backup_matrix);
+    /// Runs `step_size` generations per frame from now on, at least one.
+    pub fn set_step_size(&mut self, step_size: usize) {
+        self.step_size = step_size.max(1);
     }
 
-    fn parallel_naive_tick(&mut self) {
-        self.ticks += 1;
+    pub fn step_size(&self) -> usize {
+        self.step_size
+    }
 
-        // pass reference to a stack-allocated variable to threads
-        thread::scope(|scope| {
-            let mut workers = vec![];
-            let chunk_size = self.matrix.size() / self.workers;
-            let matrix_arc = Arc::new(&self.matrix);
-
-            for i in 0..self.workers {
-                let start = i * chunk_size;
-                let end = if i == self.workers - 1 {
-                    self.matrix.size()
-                } else {
-                    start + chunk_size
-                };
-                let loopback = self.loopback;
-                let backup_matrix_ptr_wrapper =
-                    ThreadPtrWrapper(self.backup_matrix.matrix.as_mut_ptr());
-                let matrix = matrix_arc.clone();
-
-                let worker = scope.spawn(move || unsafe {
-                    // inside of the closure to avoid the smarter new fine-grained closure capturing.
-                    let _ = &backup_matrix_ptr_wrapper;
-                    let backup_matrix_ptr = backup_matrix_ptr_wrapper.0;
-                    let slice =
-                        slice::from_raw_parts_mut(backup_matrix_ptr.add(start), end - start);
-
-                    for idx in start..end {
-                        let value = &mut slice[idx - start];
-                        Self::write_next_tick_state(loopback, &matrix, idx, value);
-                    }
-                });
+    /// Draws and records only every `render_every`th generation from now on,
+    /// at least one, for a time-lapse. GIFs and videos get a frame for each
+    /// generation drawn, and `step` runs up to the next one.
+    pub fn set_render_every(&mut self, render_every: usize) {
+        self.render_every = render_every.max(1);
+    }
 
-                workers.push(worker)
+    pub fn render_every(&self) -> usize {
+        self.render_every
+    }
+
+    /// Whether the current generation is one to draw and record.
+    pub fn is_rendered(&self) -> bool {
+        self.ticks.is_multiple_of(self.render_every)
+    }
+
+    /// Runs the generations of a frame, `step_size` of them, or up to the
+    /// next generation drawn in a time-lapse, unless the game finishes
+    /// earlier, so that frames are drawn less often than generations on
+    /// slow terminals.
+    pub fn step(&mut self) {
+        let last = match self.render_every {
+            1 => self.ticks + self.step_size,
+            every => (self.ticks / every + 1) * every,
+        };
+        while self.ticks < last {
+            self.tick();
+            if self.is_finished() {
+                break;
             }
+        }
+    }
+
+    pub fn set_stop_on_cycle(&mut self, stop_on_cycle: bool) {
+        self.stop_on_cycle = stop_on_cycle;
+    }
+
+    pub fn set_max_ticks(&mut self, max_ticks: Option<usize>) {
+        self.max_ticks = max_ticks;
+    }
+
+    /// Stops the simulation once `limit` of wall-clock time has passed from
+    /// now, at whatever generation it reached. A limit beyond the clock's
+    /// range never passes.
+    pub fn set_max_seconds(&mut self, limit: Option<Duration>) {
+        self.time_limit = limit.and_then(|limit| {
+            Some(TimeLimit {
+                limit,
+                deadline: Instant::now().checked_add(limit)?,
+                first_tick: self.ticks,
+            })
+        });
+    }
+
+    /// Keeps up to `capacity` past generations for stepping backwards.
+    pub fn set_history(&mut self, capacity: usize) {
+        self.history = History::new(capacity);
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(replay) = &mut self.replay {
+            replay.discard_undone(self.ticks);
+        }
+        if self.history.capacity() > 0 {
+            let state = self.state();
+            self.history.push(state);
+        }
+
+        if self.unbounded {
+            self.expand_to_fit();
+        }
 
-            for worker in workers {
-                worker.join().unwrap();
+        self.ticks += 1;
+        self.metrics.start_tick();
+        let transition = if self.low_memory {
+            Some(streaming::tick_in_place(&mut self.matrix, &self.rule))
+        } else {
+            if self.backup_matrix.size() != self.matrix.size() {
+                self.backup_matrix = Matrix::new(self.rows, self.cols);
             }
+            self.engine
+                .step(&self.matrix, &mut self.backup_matrix, &self.rule);
+            swap(&mut self.matrix, &mut self.backup_matrix);
+            None
+        };
+        let (births, deaths) = self.noise.apply(&mut self.matrix, self.seed, self.ticks);
+        self.metrics.end_tick(self.matrix.size());
+        let transition = transition.map(|transition| Transition {
+            population: transition.population + births - deaths,
+            births: transition.births + births,
+            deaths: transition.deaths + deaths,
         });
 
-        swap(&mut self.matrix, &mut self.backup_matrix);
+        self.record_stats(transition);
+        self.update_ages(true);
+        self.update_activity();
+        if self.play_scenario() {
+            self.edited();
+        } else {
+            self.detect_cycle();
+        }
+        if self.is_rendered() {
+            self.record_frame();
+        }
+        self.write_snapshot();
+        #[cfg(feature = "metrics")]
+        self.export_metrics();
+        self.run_hooks();
     }
 
-    fn write_next_tick_state(loopback: bool, matrix: &Matrix, idx: usize, value: &mut u8) {
-        if loopback {
-            Self::write_loopback_next_tick_state(matrix, idx, value);
+    /// Steps back to the previous generation, returns false if no earlier
+    /// generation is kept.
+    pub fn rewind(&mut self) -> bool {
+        let current = self.state();
+        match self.history.undo(current) {
+            Some(previous) => {
+                self.stats.truncate(previous.ticks);
+                self.restore(previous);
+                if let Some(replay) = &mut self.replay {
+                    replay.rewind(self.ticks);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Steps forward to the next generation, replaying a rewound generation
+    /// if there is one.
+    pub fn forward(&mut self) {
+        let current = self.state();
+        match self.history.redo(current) {
+            Some(next) => {
+                self.stats.push(next.generation);
+                self.restore(next);
+                if let Some(replay) = &mut self.replay {
+                    replay.forward(self.ticks);
+                }
+            }
+            None => self.tick(),
+        }
+    }
+
+    /// The full state of the simulation, from which it can be resumed.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            version: CHECKPOINT_VERSION,
+            ticks: self.ticks,
+            seed: self.seed,
+            edge: self.rule.edge,
+            rule: self.rule,
+            unbounded: self.unbounded,
+            origin: self.origin,
+            matrix: self.matrix.clone(),
+        }
+    }
+
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.checkpoint().save(path)
+    }
+
+    pub fn restore_checkpoint<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let checkpoint = Checkpoint::load(path)?;
+        self.resume(checkpoint);
+        Ok(())
+    }
+
+    /// Continues the simulation from a checkpoint. Statistics, ages, activity
+    /// and the history start over from the checkpointed generation.
+    pub fn resume(&mut self, checkpoint: Checkpoint) {
+        self.replace_matrix(checkpoint.matrix);
+        self.origin = checkpoint.origin;
+        self.ticks = checkpoint.ticks;
+        self.seed = checkpoint.seed;
+        self.rule = checkpoint.rule.with_edge(checkpoint.edge);
+        self.set_unbounded(checkpoint.unbounded);
+
+        self.stats = Stats::starting_at(self.ticks, &self.matrix);
+        self.history.clear();
+        if let Some(ages) = &mut self.ages {
+            *ages = Matrix::new(self.rows, self.cols);
+        }
+        self.update_ages(false);
+        if let Some(activity) = &mut self.activity {
+            *activity = Matrix::new(self.rows, self.cols);
+        }
+        self.update_activity();
+        self.restart_cycle_detection();
+        // earlier changes no longer lead to the current state
+        let checkpoint = self.replay.is_some().then(|| self.checkpoint());
+        if let (Some(replay), Some(checkpoint)) = (&mut self.replay, checkpoint) {
+            replay.restart(checkpoint);
+        }
+    }
+
+    /// A simulation of the current board under `rule`, edge mode included,
+    /// ticked by `engine`, with the stop conditions, history, step size and
+    /// looks of this one but none of its outputs, to run side by side with it.
+    pub fn fork(&self, rule: Rule, engine: Box<dyn TickEngine>) -> GameOfLife {
+        let mut checkpoint = self.checkpoint();
+        checkpoint.edge = rule.edge;
+        checkpoint.rule = rule;
+
+        let mut game = GameOfLife::new(Matrix::new(0, 0));
+        game.engine = engine;
+        game.set_low_memory(self.low_memory);
+        game.noise = self.noise;
+        game.stop_on_cycle = self.stop_on_cycle;
+        game.max_ticks = self.max_ticks;
+        game.time_limit = self.time_limit;
+        game.step_size = self.step_size;
+        game.render_every = self.render_every;
+        game.history = History::new(self.history.capacity());
+        game.resume(checkpoint);
+        game.viewport = self.viewport;
+        game.board_viewport = self.board_viewport;
+        game.set_renderer(self.renderer);
+        game.set_theme(self.theme);
+        game
+    }
+
+    /// Writes the current board as a PNG image, `cell_size` pixels per cell.
+    pub fn render_png<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cell_size: usize,
+    ) -> Result<(), png::EncodingError> {
+        let style = ImageStyle {
+            cell_size,
+            ..self.image_style
+        };
+        image::save_png(path, &self.board(), &style)
+    }
+
+    /// Writes the current board as an SVG image in the style of PNG images.
+    pub fn render_svg<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        svg::save_svg(path, &self.board(), &self.image_style)
+    }
+
+    /// The board as recorded in images, for an unbounded universe the region
+    /// of the initial board.
+    pub fn board(&self) -> Cow<'_, Matrix> {
+        if self.unbounded {
+            Cow::Owned(self.board_viewport.extract(&self.matrix, self.origin))
+        } else {
+            Cow::Borrowed(&self.matrix)
+        }
+    }
+
+    /// The part of the universe shown in the terminal.
+    pub fn view(&self) -> Cow<'_, Matrix> {
+        self.view_of(&self.matrix)
+    }
+
+    /// The part of a matrix laid out like the board that is shown in the terminal.
+    fn view_of<'a, T: Copy + Default + Ord>(&self, matrix: &'a Matrix<T>) -> Cow<'a, Matrix<T>> {
+        if self.viewport.covers(matrix, self.origin) {
+            Cow::Borrowed(matrix)
         } else {
-            Self::write_terminate_next_tick_state(matrix, idx, value);
+            Cow::Owned(self.viewport.extract(matrix, self.origin))
+        }
+    }
+
+    /// Where the top-left cell of the initial board lies in the matrix,
+    /// which moves as an unbounded universe grows.
+    pub fn origin(&self) -> (usize, usize) {
+        self.origin
+    }
+
+    pub fn viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    pub fn viewport_mut(&mut self) -> &mut Viewport {
+        &mut self.viewport
+    }
+
+    pub fn renderer(&self) -> Renderer {
+        self.renderer
+    }
+
+    /// Switches how cells are drawn, the heatmap renderer starts tracking
+    /// how often cells are alive from the current generation on.
+    pub fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+        if renderer != Renderer::Heatmap {
+            self.activity = None;
+        } else if self.activity.is_none() {
+            self.activity = Some(Matrix::new(self.rows, self.cols));
+            self.update_activity();
         }
     }
 
-    fn write_terminate_next_tick_state(matrix: &Matrix, idx: usize, value: &mut u8) {
-        let rows = matrix.rows;
-        let cols = matrix.cols;
-        let (row, col) = matrix.inverse_idx(idx);
+    /// Resizes the viewport to fill a terminal of `lines` by `columns`
+    /// characters, a bounded board is never shown with dead space around it.
+    pub fn fit_viewport(&mut self, lines: usize, columns: usize) {
+        let (mut rows, mut cols) = self
+            .renderer
+            .fit(lines.saturating_sub(FRAME_CHROME_LINES), columns);
 
-        let mut live_count = 0;
+        if !self.unbounded {
+            let zoom = self.viewport.zoom;
+            rows = rows.min(self.rows.div_ceil(zoom));
+            cols = cols.min(self.cols.div_ceil(zoom));
+        }
+
+        self.viewport.rows = rows.max(1);
+        self.viewport.cols = cols.max(1);
+    }
 
-        if row < rows - 1 && col < cols - 1 && matrix.get(row + 1, col + 1) == 1 {
-            live_count += 1
+    /// Adds dead margins on every side where live cells touch the boundary, so
+    /// the next generation can grow beyond it.
+    fn expand_to_fit(&mut self) {
+        let matrix = &self.matrix;
+        let (rows, cols) = (matrix.row_count(), matrix.col_count());
+        if rows == 0 || cols == 0 {
+            return;
         }
 
-        if row > 0 && col > 0 && matrix.get(row - 1, col - 1) == 1 {
-            live_count += 1
+        let margin = |touches: bool| if touches { GROW_MARGIN } else { 0 };
+        let top = margin((0..cols).any(|col| matrix.get(0, col) != 0));
+        let bottom = margin((0..cols).any(|col| matrix.get(rows - 1, col) != 0));
+        let left = margin((0..rows).any(|row| matrix.get(row, 0) != 0));
+        let right = margin((0..rows).any(|row| matrix.get(row, cols - 1) != 0));
+
+        if top + bottom + left + right == 0 {
+            return;
         }
 
-        if row < rows - 1 && col > 0 && matrix.get(row + 1, col - 1) == 1 {
-            live_count += 1
+        self.matrix = matrix.expand(top, bottom, left, right);
+        if let Some(ages) = &mut self.ages {
+            *ages = ages.expand(top, bottom, left, right);
+        }
+        if let Some(activity) = &mut self.activity {
+            *activity = activity.expand(top, bottom, left, right);
         }
+        self.rows = self.matrix.row_count();
+        self.cols = self.matrix.col_count();
+        self.origin = (self.origin.0 + top, self.origin.1 + left);
+        self.backup_matrix = self.new_backup_matrix();
+    }
+
+    /// Changes the size of the board, keeping the cells around `anchor` in
+    /// place. The resized board becomes the one images show, and cell
+    /// coordinates are relative to its top-left cell from now on.
+    pub fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor) {
+        // the terminal keeps showing the same cells
+        let (row_offset, col_offset) = anchor.offset((self.rows, self.cols), (rows, cols));
+        self.viewport.row += self.origin.0 as isize + row_offset;
+        self.viewport.col += self.origin.1 as isize + col_offset;
 
-        if row > 0 && col < cols - 1 && matrix.get(row - 1, col + 1) == 1 {
-            live_count += 1
+        self.matrix.resize(rows, cols, anchor);
+        if let Some(ages) = &mut self.ages {
+            ages.resize(rows, cols, anchor);
+        }
+        if let Some(activity) = &mut self.activity {
+            activity.resize(rows, cols, anchor);
         }
+        self.rows = rows;
+        self.cols = cols;
+        self.origin = (0, 0);
+        self.board_viewport = Viewport::new(rows, cols);
+        self.backup_matrix = self.new_backup_matrix();
+        self.edited();
+    }
+
+    /// Copies `pattern` onto the board with its top-left corner at `(row, col)`.
+    ///
+    /// Cycle detection starts over, since earlier generations no longer lead
+    /// to the current one.
+    pub fn place_pattern(&mut self, pattern: &Matrix, row: usize, col: usize, overflow: Overflow) {
+        self.matrix.blit(pattern, row, col, overflow);
+        self.edited();
+    }
 
-        if col < cols - 1 && matrix.get(row, col + 1) == 1 {
-            live_count += 1
+    /// Index into the matrix of the cell at `row`, `col` relative to the
+    /// top-left cell of the initial board, if the universe holds that cell.
+    pub fn cell_at(&self, row: isize, col: isize) -> Option<(usize, usize)> {
+        let row = row + self.origin.0 as isize;
+        let col = col + self.origin.1 as isize;
+
+        let inside =
+            (0..self.rows as isize).contains(&row) && (0..self.cols as isize).contains(&col);
+        inside.then_some((row as usize, col as usize))
+    }
+
+    /// Flips the cell at `row`, `col` relative to the top-left cell of the
+    /// initial board, cells outside of the universe are left alone.
+    pub fn toggle_cell(&mut self, row: isize, col: isize) {
+        if let Some((matrix_row, matrix_col)) = self.cell_at(row, col) {
+            let alive = self.matrix.get(matrix_row, matrix_col) == 0;
+            self.set_cell(row, col, alive);
         }
+    }
+
+    /// Sets the cell at `row`, `col` relative to the top-left cell of the
+    /// initial board, cells outside of the universe are left alone.
+    pub fn set_cell(&mut self, row: isize, col: isize, alive: bool) {
+        let Some((matrix_row, matrix_col)) = self.cell_at(row, col) else {
+            return;
+        };
 
-        if col > 0 && matrix.get(row, col - 1) == 1 {
-            live_count += 1
+        if (self.matrix.get(matrix_row, matrix_col) != 0) != alive {
+            self.matrix.set(matrix_row, matrix_col, alive as u8);
+            self.record_edit(Edit::Toggle { row, col });
+            self.edited();
         }
+    }
+
+    /// The cell drawn by the character at `line`, `column` of the frame,
+    /// relative to the top-left cell of the initial board.
+    pub fn cell_on_screen(&self, line: usize, column: usize) -> Option<(isize, isize)> {
+        let line = line.checked_sub(FRAME_HEADER_LINES)?;
+        let (row, col) = self.renderer.cell_position(line, column);
+        self.viewport.universe_position(row, col)
+    }
+
+    /// Colors the background of the glyph drawing the cell at `row`, `col`
+    /// relative to the top-left cell of the initial board, if it is in view.
+    pub fn highlight(&self, frame: &mut Frame, row: isize, col: isize, color: u8) {
+        let Some((row, col)) = self.viewport.cell_position(row, col) else {
+            return;
+        };
 
-        if row < rows - 1 && matrix.get(row + 1, col) == 1 {
-            live_count += 1
+        let (line, index) = self.renderer.glyph_position(row, col);
+        if let Some(glyph) = frame
+            .lines
+            .get_mut(FRAME_HEADER_LINES + line)
+            .and_then(|line| line.get_mut(index))
+        {
+            glyph.bg = Some(color);
         }
+    }
+
+    /// Colors live cells by age or species, which starts tracking ages for
+    /// the themes coloring by age.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.ages = theme
+            .colors_by_age()
+            .then(|| Matrix::new(self.rows, self.cols));
+        self.update_ages(false);
+    }
+
+    /// Ages of live cells in generations, if tracked.
+    pub fn ages(&self) -> Option<&Matrix> {
+        self.ages.as_ref()
+    }
+
+    /// Number of generations each cell has been alive, if tracked.
+    pub fn activity(&self) -> Option<&Matrix<u32>> {
+        self.activity.as_ref()
+    }
+
+    /// The header, board and statistics as drawn in the terminal.
+    pub fn frame(&self) -> Frame {
+        let mut frame = Frame::default();
+        frame.push_text(&self.header());
+        frame.lines.extend(self.cells_frame().lines);
+
+        frame.push_text(&self.stats.to_string());
+
+        // as wide as the rest of the frame, one generation per character
+        let width = frame.width();
+        let graph = self
+            .stats
+            .sparkline(width.saturating_sub(GRAPH_LABEL.len()));
+        frame.push_text(&format!("{GRAPH_LABEL}{graph}"));
+        frame
+    }
+
+    /// Only the cells in view as drawn in the terminal, without the header
+    /// and statistics.
+    pub fn cells_frame(&self) -> Frame {
+        let mut frame = Frame::default();
+        let view = self.view();
+        let ages = match self.theme {
+            Theme::Species => Some(view.clone()),
+            _ => self.ages.as_ref().map(|ages| self.view_of(ages)),
+        };
+        let activity = self
+            .activity
+            .as_ref()
+            .map(|activity| self.view_of(activity));
+        frame.lines.extend(self.renderer.render_glyphs(
+            &view,
+            ages.as_deref(),
+            activity.as_deref(),
+            self.theme,
+        ));
+        frame
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// How long the engine took to compute the recent generations.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
 
-        if row > 0 && matrix.get(row - 1, col) == 1 {
-            live_count += 1
+    /// The first cycle the universe has entered, if any.
+    pub fn cycle(&self) -> Option<&Cycle> {
+        self.cycle.as_ref()
+    }
+
+    /// Ticks until `condition` holds for the current generation.
+    pub fn run_until<F>(&mut self, mut condition: F)
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        while !condition(self) {
+            self.tick();
         }
+    }
 
-        *value = if matrix.get(row, col) == 1 {
-            if live_count < 2 || live_count > 3 {
-                0
-            } else {
-                1
+    pub fn matrix(&self) -> &Matrix {
+        &self.matrix
+    }
+
+    /// A hash of the board and its dimensions, see `Matrix::state_hash`.
+    /// The generation, rule and position of an unbounded board are left
+    /// out, so that a board hashes the same whenever it comes back.
+    pub fn state_hash(&self) -> u64 {
+        self.matrix.state_hash()
+    }
+
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    /// The seed of the random initial state, which also seeds the noise.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The stop condition the simulation has reached, if any.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        if self.stopped {
+            return Some(StopReason::Hook);
+        }
+        match (self.max_ticks, self.cycle) {
+            (Some(max_ticks), _) if self.ticks >= max_ticks => {
+                Some(StopReason::MaxTicks(max_ticks))
             }
-        } else {
-            if live_count == 3 {
-                1
-            } else {
-                0
+            (_, Some(cycle)) if self.stop_on_cycle => Some(StopReason::Cycle(cycle)),
+            _ => self
+                .time_limit
+                .filter(|time_limit| Instant::now() >= time_limit.deadline)
+                .map(|time_limit| StopReason::MaxSeconds {
+                    limit: time_limit.limit,
+                    generations: self.ticks.saturating_sub(time_limit.first_tick),
+                }),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.stop_reason().is_some()
+    }
+
+    fn run_hooks(&mut self) {
+        let generation = self.stats.latest();
+        for hook in &mut self.hooks {
+            if hook(generation, &self.matrix).is_break() {
+                self.stopped = true;
             }
         }
     }
 
-    fn write_loopback_next_tick_state(matrix: &Matrix, idx: usize, value: &mut u8) {
-        let rows = matrix.rows;
-        let cols = matrix.cols;
-        let (row, col) = matrix.inverse_idx(idx);
+    /// Applies the scenario events of the current generation, returns
+    /// whether there were any.
+    fn play_scenario(&mut self) -> bool {
+        let scenario = std::mem::take(&mut self.scenario);
+        let events = scenario.events_at(self.ticks);
+        for event in events {
+            self.apply(&event.action);
+        }
+        let played = !events.is_empty();
+        self.scenario = scenario;
+        played
+    }
 
-        let mut live_count = 0;
+    /// Changes the board as `action` says, cells outside of the universe
+    /// are left alone.
+    fn apply(&mut self, action: &Action) {
+        match action {
+            Action::Place { pattern, row, col } => {
+                for ((pattern_row, pattern_col), &state) in pattern.iter_cells() {
+                    let (row, col) = (row + pattern_row as isize, col + pattern_col as isize);
+                    self.write_cell(row, col, Some(state));
+                }
+            }
+            Action::Clear {
+                row,
+                col,
+                rows,
+                cols,
+            } => {
+                for row in *row..row + *rows as isize {
+                    for col in *col..col + *cols as isize {
+                        self.write_cell(row, col, Some(0));
+                    }
+                }
+            }
+            Action::Toggle { row, col } => self.write_cell(*row, *col, None),
+            Action::Rule(rule) => self.set_rule(rule.with_edge(self.rule.edge)),
+        }
+    }
 
-        // loopback
-        let row_inc = if row == rows - 1 { 0 } else { row + 1 };
-        let row_dec = if row == 0 { rows - 1 } else { row - 1 };
-        let col_inc = if col == cols - 1 { 0 } else { col + 1 };
-        let col_dec = if col == 0 { cols - 1 } else { col - 1 };
+    /// Sets the cell at `row`, `col` relative to the top-left cell of the
+    /// initial board to `state`, or flips it without one, unlike `set_cell`
+    /// leaving the restart of age tracking and cycle detection to the caller.
+    fn write_cell(&mut self, row: isize, col: isize, state: Option<u8>) {
+        if let Some((matrix_row, matrix_col)) = self.cell_at(row, col) {
+            let current = self.matrix.get(matrix_row, matrix_col);
+            let state = state.unwrap_or(u8::from(current == 0));
+            if state != current {
+                self.matrix.set(matrix_row, matrix_col, state);
+                self.record_edit(Edit::Toggle { row, col });
+            }
+        }
+    }
 
-        if matrix.get(row_inc, col_inc) == 1 {
-            live_count += 1
+    /// Records a change made to the board at the current generation to the
+    /// replay, if one is being recorded.
+    fn record_edit(&mut self, edit: Edit) {
+        if let Some(replay) = &mut self.replay {
+            replay.record(self.ticks, edit);
         }
+    }
 
-        if matrix.get(row_dec, col_dec) == 1 {
-            live_count += 1
+    /// Restarts age tracking and cycle detection after cells were changed
+    /// by hand, since earlier generations no longer lead to the current one.
+    fn edited(&mut self) {
+        self.update_ages(false);
+        self.history.clear_future();
+        if let Some(replay) = &mut self.replay {
+            replay.discard_undone(self.ticks);
         }
+        self.restart_cycle_detection();
+    }
 
-        if matrix.get(row_inc, col_dec) == 1 {
-            live_count += 1
+    fn state(&self) -> State {
+        State {
+            matrix: self.matrix.clone(),
+            origin: self.origin,
+            ticks: self.ticks,
+            ages: self.ages.clone(),
+            activity: self.activity.clone(),
+            generation: *self.stats.latest(),
         }
+    }
+
+    /// Returns to a generation kept in the history. Cycle detection starts
+    /// over, since the hashes it remembers belong to other generations.
+    fn restore(&mut self, state: State) {
+        self.replace_matrix(state.matrix);
+        self.origin = state.origin;
+        self.ticks = state.ticks;
+        self.ages = state.ages;
+        self.activity = state.activity;
+        self.restart_cycle_detection();
+    }
 
-        if matrix.get(row_dec, col_inc) == 1 {
-            live_count += 1
+    /// Swaps in a matrix of possibly another size, along with the buffers
+    /// the engines tick it with.
+    fn replace_matrix(&mut self, matrix: Matrix) {
+        // a board kept in a file stays there
+        if self.matrix.is_mapped()
+            && (matrix.row_count(), matrix.col_count()) == (self.rows, self.cols)
+        {
+            self.matrix.clone_from(&matrix);
+            return;
         }
+        if (matrix.row_count(), matrix.col_count()) != (self.rows, self.cols) {
+            self.rows = matrix.row_count();
+            self.cols = matrix.col_count();
+            self.backup_matrix = self.new_backup_matrix();
+        }
+        self.matrix = matrix;
+    }
 
-        if matrix.get(row, col_inc) == 1 {
-            live_count += 1
+    fn new_backup_matrix(&self) -> Matrix {
+        if self.low_memory {
+            Matrix::new(0, 0)
+        } else {
+            Matrix::new(self.rows, self.cols)
         }
+    }
+
+    fn restart_cycle_detection(&mut self) {
+        self.cycle_detector.clear();
+        self.cycle = None;
+        self.detect_cycle();
+    }
 
-        if matrix.get(row, col_dec) == 1 {
-            live_count += 1
+    /// Resets the age of dead cells and starts newborn cells at one generation,
+    /// with `grow` every other live cell gets a generation older.
+    fn update_ages(&mut self, grow: bool) {
+        let Some(ages) = &mut self.ages else {
+            return;
+        };
+
+        for (age, &cell) in ages.as_mut_slice().iter_mut().zip(self.matrix.as_slice()) {
+            *age = match (cell != 0, *age) {
+                (false, _) => 0,
+                (true, 0) => 1,
+                (true, age) if grow => age.saturating_add(1),
+                (true, age) => age,
+            };
         }
+    }
 
-        if matrix.get(row_inc, col) == 1 {
-            live_count += 1
+    /// Counts another generation alive for every live cell.
+    fn update_activity(&mut self) {
+        let Some(activity) = &mut self.activity else {
+            return;
+        };
+
+        for (count, &cell) in activity
+            .as_mut_slice()
+            .iter_mut()
+            .zip(self.matrix.as_slice())
+        {
+            if cell != 0 {
+                *count = count.saturating_add(1);
+            }
         }
+    }
 
-        if matrix.get(row_dec, col) == 1 {
-            live_count += 1
+    /// Records the generation just computed, from the `transition` counted
+    /// while ticking in place or else by comparing with the previous one.
+    fn record_stats(&mut self, transition: Option<Transition>) {
+        match transition {
+            Some(transition) => self.stats.push(Generation {
+                tick: self.ticks,
+                population: transition.population,
+                births: transition.births,
+                deaths: transition.deaths,
+            }),
+            // after a tick the backup matrix holds the previous generation
+            None => {
+                self.stats
+                    .record(self.ticks, &self.backup_matrix, &self.matrix);
+            }
         }
+    }
+
+    fn record_frame(&mut self) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder
+                .record(&self.board_viewport.extract(&self.matrix, self.origin))
+                .expect("gif encode exception");
 
-        *value = if matrix.get(row, col) == 1 {
-            if live_count < 2 || live_count > 3 {
-                0
-            } else {
-                1
+            // dropping the encoder writes the GIF trailer
+            if recorder.is_done() {
+                self.recorder = None;
             }
-        } else {
-            if live_count == 3 {
-                1
-            } else {
-                0
+        }
+
+        if let Some(video) = &mut self.video {
+            video
+                .record(&self.board_viewport.extract(&self.matrix, self.origin))
+                .expect("video encode exception");
+        }
+    }
+
+    fn write_snapshot(&self) {
+        match self.snapshot_every {
+            Some(every) if every > 0 && self.ticks.is_multiple_of(every) => {
+                match self.snapshot_format {
+                    SnapshotFormat::Png => {
+                        let path = self
+                            .snapshot_dir
+                            .join(format!("tick-{:06}.png", self.ticks));
+                        self.render_png(path, self.image_style.cell_size)
+                            .expect("png encode exception");
+                    }
+                    #[cfg(feature = "native")]
+                    SnapshotFormat::Snap => {
+                        let path = self.snapshot_dir.join(format!(
+                            "tick-{:06}.{}",
+                            self.ticks,
+                            snapshot::EXTENSION
+                        ));
+                        self.checkpoint().save(path).expect("snapshot exception");
+                    }
+                }
             }
+            _ => {}
         }
     }
+
+    #[cfg(feature = "metrics")]
+    fn export_metrics(&self) {
+        let Some(exporter) = &self.exporter else {
+            return;
+        };
+
+        exporter.gauge(
+            "gameoflife_generation",
+            "Number of the current generation",
+            self.ticks as f64,
+        );
+        exporter.gauge(
+            "gameoflife_population",
+            "Live cells of the current generation",
+            self.stats.latest().population as f64,
+        );
+        exporter.gauge(
+            "gameoflife_cells",
+            "Cells of the board, live or dead",
+            self.matrix.size() as f64,
+        );
+        if let Some(average) = self.metrics.average_tick() {
+            exporter.gauge(
+                "gameoflife_tick_seconds",
+                "Mean time the engine took to compute each of the recent generations",
+                average.as_secs_f64(),
+            );
+        }
+        exporter.counter(
+            "gameoflife_tick_seconds_total",
+            "Time the engine took to compute all generations",
+            self.metrics.total().as_secs_f64(),
+        );
+    }
+
+    /// The size, the generation and the pace of ticking.
+    fn header(&self) -> String {
+        let mut header = format!(
+            "size: {} x {} \nticks: {} ",
+            self.rows, self.cols, self.ticks
+        );
+        if self.render_every > 1 {
+            header.push_str(&format!("(every {} drawn) ", self.render_every));
+        } else if self.step_size > 1 {
+            header.push_str(&format!("(+{} per frame) ", self.step_size));
+        }
+        if self.metrics.ticks() > 0 {
+            header.push_str(&format!("({}) ", self.metrics));
+        }
+        header
+    }
+
+    fn detect_cycle(&mut self) {
+        if self.cycle.is_some() {
+            return;
+        }
+
+        self.cycle = self.cycle_detector.observe(self.state_hash(), self.ticks);
+    }
 }
 
 impl Display for GameOfLife {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "size: {} x {} \nticks: {} \n{}",
-            self.rows, self.cols, self.ticks, self.matrix
+            "{}\n{}{}",
+            self.header(),
+            self.renderer.render(&self.view()),
+            self.stats
         )
     }
 }
-
-// sharing raw pointer wrappers among threads
-struct ThreadPtrWrapper(*mut u8);
-unsafe impl Sync for ThreadPtrWrapper {}
-unsafe impl Send for ThreadPtrWrapper {}
@@ -0,0 +1,247 @@
+//! Tick computed by a wgpu compute shader, see `gpu.wgsl`. The board is
+//! uploaded as is, four cells to a word, and read back after every tick
+//! since the game needs it for statistics and rendering, so the GPU only
+//! pays off on large boards, where computing the generation dominates.
+
+use std::sync::mpsc;
+
+use crate::edge::Edge;
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+const WORKGROUP_SIZE: u32 = 64;
+/// Number of workgroups along x, more rows of workgroups are dispatched for
+/// larger boards to stay below the per dimension limit.
+const WORKGROUPS_X: u32 = 256;
+
+pub struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    /// Largest board buffer in bytes the device can bind to the shader.
+    max_size: u64,
+    buffers: Option<Buffers>,
+}
+
+/// Buffers sized for a board, reallocated whenever its size changes.
+struct Buffers {
+    rows: usize,
+    cols: usize,
+    /// Size in bytes of the board rounded up to whole words.
+    size: u64,
+    params: wgpu::Buffer,
+    current: wgpu::Buffer,
+    next: wgpu::Buffer,
+    staging: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Gpu {
+    /// Sets up the first high performance adapter found.
+    pub fn new() -> Result<Self, String> {
+        pollster::block_on(Self::request())
+    }
+
+    async fn request() -> Result<Self, String> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .ok_or("no GPU adapter found")?;
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: wgpu::Features::empty(),
+                    // the largest boards need the largest buffers the adapter supports
+                    required_limits: adapter.limits(),
+                },
+                None,
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("tick"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gpu.wgsl").into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("tick"),
+            layout: None,
+            module: &shader,
+            entry_point: "tick",
+        });
+
+        let limits = device.limits();
+        let max_size =
+            u64::from(limits.max_storage_buffer_binding_size).min(limits.max_buffer_size);
+        Ok(Gpu {
+            device,
+            queue,
+            pipeline,
+            max_size,
+            buffers: None,
+        })
+    }
+
+    /// Reallocates the buffers for a board of `rows` by `cols` cells,
+    /// failing if the board is larger than the device can bind.
+    fn resize(&mut self, rows: usize, cols: usize) -> Result<(), String> {
+        if matches!(&self.buffers, Some(buffers) if (buffers.rows, buffers.cols) == (rows, cols)) {
+            return Ok(());
+        }
+        let size = ((rows * cols).div_ceil(4) * 4) as u64;
+        if size > self.max_size {
+            return Err(format!(
+                "a board of {rows}x{cols} cells needs {size} bytes, beyond the {} bytes of the largest storage buffer of the GPU",
+                self.max_size
+            ));
+        }
+        self.buffers = Some(self.create_buffers(rows, cols, size));
+        Ok(())
+    }
+
+    fn create_buffers(&self, rows: usize, cols: usize, size: u64) -> Buffers {
+        let buffer = |label, size, usage| {
+            self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+
+        let params = buffer(
+            "params",
+            32,
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        );
+        let current = buffer(
+            "current",
+            size,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        let next = buffer(
+            "next",
+            size,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let staging = buffer(
+            "staging",
+            size,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: current.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: next.as_entire_binding(),
+                },
+            ],
+        });
+
+        Buffers {
+            rows,
+            cols,
+            size,
+            params,
+            current,
+            next,
+            staging,
+            bind_group,
+        }
+    }
+}
+
+impl TickEngine for Gpu {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        if src.size() == 0 {
+            return;
+        }
+
+        let words = src.size().div_ceil(4) as u32;
+        let invocations_x = WORKGROUP_SIZE * WORKGROUPS_X;
+        let workgroups = (
+            words.min(invocations_x).div_ceil(WORKGROUP_SIZE),
+            words.div_ceil(invocations_x),
+        );
+        let edge = match rule.edge {
+            Edge::Dead => 0,
+            Edge::Alive => 1,
+            Edge::Wrap => 2,
+            Edge::Mirror => 3,
+        };
+        let params: Vec<u8> = [
+            src.row_count() as u32,
+            src.col_count() as u32,
+            edge,
+            u32::from(rule.birth_mask()),
+            u32::from(rule.survival_mask()),
+            invocations_x,
+            0,
+            0,
+        ]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+
+        self.resize(src.row_count(), src.col_count())
+            .expect("gpu exception");
+        let (device, queue, pipeline) = (&self.device, &self.queue, &self.pipeline);
+        let buffers = self.buffers.as_ref().unwrap();
+
+        queue.write_buffer(&buffers.params, 0, &params);
+        // writes must be whole words
+        if src.size() as u64 == buffers.size {
+            queue.write_buffer(&buffers.current, 0, src.as_slice());
+        } else {
+            let mut cells = src.as_slice().to_vec();
+            cells.resize(buffers.size as usize, 0);
+            queue.write_buffer(&buffers.current, 0, &cells);
+        }
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &buffers.bind_group, &[]);
+            pass.dispatch_workgroups(workgroups.0, workgroups.1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&buffers.next, 0, &buffers.staging, 0, buffers.size);
+        queue.submit(Some(encoder.finish()));
+
+        let slice = buffers.staging.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            sender.send(result).ok();
+        });
+        device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .expect("gpu exception")
+            .expect("gpu exception");
+
+        let len = dst.size();
+        dst.as_mut_slice()
+            .copy_from_slice(&slice.get_mapped_range()[..len]);
+        buffers.staging.unmap();
+    }
+}
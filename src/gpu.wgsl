@@ -0,0 +1,79 @@
+// One invocation computes four cells, the bytes of one word of the board.
+
+struct Params {
+    rows: u32,
+    cols: u32,
+    // 0 dead, 1 alive, 2 wrap, 3 mirror
+    edge: u32,
+    birth: u32,
+    survival: u32,
+    // Number of invocations along x, the index of the word is id.y * stride + id.x
+    stride: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> current: array<u32>;
+@group(0) @binding(2) var<storage, read_write> next: array<u32>;
+
+fn cell(row: i32, col: i32) -> u32 {
+    let rows = i32(params.rows);
+    let cols = i32(params.cols);
+    var r = row;
+    var c = col;
+
+    if (r < 0 || r >= rows || c < 0 || c >= cols) {
+        switch params.edge {
+            case 0u: {
+                return 0u;
+            }
+            case 1u: {
+                return 1u;
+            }
+            case 2u: {
+                r = (r + rows) % rows;
+                c = (c + cols) % cols;
+            }
+            default: {
+                r = clamp(r, 0, rows - 1);
+                c = clamp(c, 0, cols - 1);
+            }
+        }
+    }
+
+    let index = u32(r * cols + c);
+    return (current[index / 4u] >> ((index % 4u) * 8u)) & 1u;
+}
+
+@compute @workgroup_size(64)
+fn tick(@builtin(global_invocation_id) id: vec3<u32>) {
+    let word = id.y * params.stride + id.x;
+    let cells = params.rows * params.cols;
+    if (word * 4u >= cells) {
+        return;
+    }
+
+    var packed = 0u;
+    for (var byte = 0u; byte < 4u; byte++) {
+        let index = word * 4u + byte;
+        if (index >= cells) {
+            break;
+        }
+
+        let row = i32(index / params.cols);
+        let col = i32(index % params.cols);
+        var live_count = 0u;
+        for (var dr = -1; dr <= 1; dr++) {
+            for (var dc = -1; dc <= 1; dc++) {
+                if (dr != 0 || dc != 0) {
+                    live_count += cell(row + dr, col + dc);
+                }
+            }
+        }
+
+        let mask = select(params.birth, params.survival, cell(row, col) == 1u);
+        packed |= ((mask >> live_count) & 1u) << (byte * 8u);
+    }
+
+    next[word] = packed;
+}
@@ -0,0 +1,62 @@
+use std::collections::VecDeque;
+
+/// Bounded undo and redo stacks of states.
+///
+/// The `capacity` most recent past states are kept, older ones are dropped.
+/// Undone states can be redone until a new state is pushed.
+pub struct History<T> {
+    capacity: usize,
+    past: VecDeque<T>,
+    future: Vec<T>,
+}
+
+impl<T> History<T> {
+    pub fn new(capacity: usize) -> Self {
+        History {
+            capacity,
+            past: VecDeque::with_capacity(capacity),
+            future: Vec::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Remembers a state that is being left, which discards the undone states.
+    pub fn push(&mut self, state: T) {
+        self.future.clear();
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(state);
+    }
+
+    /// Returns the previous state, `current` can be redone afterwards.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.past.pop_back()?;
+        self.future.push(current);
+        Some(previous)
+    }
+
+    /// Returns the last undone state, `current` can be undone again afterwards.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.future.pop()?;
+        self.past.push_back(current);
+        Some(next)
+    }
+
+    /// Discards the undone states, for example when the current state changed.
+    pub fn clear_future(&mut self) {
+        self.future.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+    }
+}
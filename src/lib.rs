@@ -1,2 +1,119 @@
+//! Conway's Game of Life. Without the default `std` feature only the core
+//! is built, `no_std` with `alloc`: boards, rules and the serial and SIMD
+//! engines, enough to drive an LED matrix from a microcontroller.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod analyze;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod bench;
+pub mod bitboard;
+#[cfg(feature = "std")]
+pub mod checkpoint;
+#[cfg(feature = "native")]
+pub mod cli;
+#[cfg(feature = "native")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod convert;
+#[cfg(feature = "std")]
+pub mod cycle;
+#[cfg(feature = "native")]
+pub mod demo;
+#[cfg(feature = "native")]
+pub mod diff;
+#[cfg(feature = "native")]
+pub mod distributed;
+pub mod edge;
+pub mod elementary;
+pub mod engine;
+#[cfg(feature = "std")]
+pub mod events;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+#[cfg(feature = "cdylib")]
+pub mod ffi;
+pub mod font;
+#[cfg(feature = "std")]
+pub mod format;
+#[cfg(feature = "std")]
 pub mod game_of_life;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod life106;
+pub mod lut;
+#[cfg(feature = "std")]
+pub mod macrocell;
 pub mod matrix;
+#[cfg(feature = "std")]
+pub mod mcell;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(all(feature = "native", unix))]
+pub mod mmap;
+#[cfg(feature = "std")]
+pub mod noise;
+#[cfg(feature = "std")]
+pub mod pacing;
+pub mod padded;
+#[cfg(feature = "std")]
+pub mod patterns;
+#[cfg(feature = "std")]
+pub mod picture;
+#[cfg(feature = "std")]
+pub mod plaintext;
+#[cfg(feature = "metrics")]
+pub mod prometheus;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "native")]
+pub mod reverse;
+#[cfg(feature = "std")]
+pub mod rle;
+pub mod rule;
+#[cfg(feature = "std")]
+pub mod ruletable;
+#[cfg(feature = "std")]
+pub mod scenario;
+#[cfg(feature = "native")]
+pub mod search;
+#[cfg(feature = "std")]
+pub mod shutdown;
+pub mod simd;
+#[cfg(feature = "native")]
+pub mod simulator;
+#[cfg(feature = "native")]
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod species;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "native")]
+pub mod still;
+pub mod streaming;
+pub mod symmetry;
+pub mod tiled;
+#[cfg(feature = "std")]
+pub mod topology;
+#[cfg(feature = "native")]
+pub mod tui;
+#[cfg(feature = "std")]
+pub mod turmite;
+#[cfg(feature = "native")]
+pub mod verify;
+#[cfg(feature = "std")]
+pub mod viewport;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,72 @@
+//! The Life 1.06 format, the coordinates of the live cells one per line.
+
+use std::fmt::Write;
+
+use crate::matrix::Matrix;
+
+/// First line of a Life 1.06 file.
+const LIFE_106_HEADER: &str = "#Life 1.06";
+
+/// Parses a pattern in Life 1.06 format: a `#Life 1.06` line followed by
+/// one `x y` line per live cell, `x` its column and `y` its row, either of
+/// which may be negative. The matrix is just large enough for the cells.
+pub fn parse(text: &str) -> Result<Matrix, String> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    match lines.next() {
+        Some(line) if line.starts_with(LIFE_106_HEADER) => {}
+        Some(line) if line.starts_with("#Life") => {
+            return Err(format!("unsupported version {line:?}, only Life 1.06 is"))
+        }
+        _ => return Err("missing #Life 1.06 header".to_string()),
+    }
+
+    let mut cells = Vec::new();
+    for line in lines.filter(|line| !line.starts_with('#')) {
+        let coordinates = line
+            .split_whitespace()
+            .map(str::parse::<isize>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("invalid cell {line:?}: {err}"))?;
+        match coordinates[..] {
+            [x, y] => cells.push((y, x)),
+            _ => return Err(format!("invalid cell {line:?}, expected x y")),
+        }
+    }
+
+    let (Some(top), Some(left)) = (
+        cells.iter().map(|&(row, _)| row).min(),
+        cells.iter().map(|&(_, col)| col).min(),
+    ) else {
+        return Matrix::try_new(0, 0);
+    };
+    let bottom = cells.iter().map(|&(row, _)| row).max().unwrap();
+    let right = cells.iter().map(|&(_, col)| col).max().unwrap();
+
+    let span = |first: isize, last: isize| {
+        last.checked_sub(first)
+            .and_then(|span| (span as usize).checked_add(1))
+            .ok_or_else(|| format!("cells from {left} {top} to {right} {bottom} are too far apart"))
+    };
+    let (rows, cols) = (span(top, bottom)?, span(left, right)?);
+    let mut matrix = Matrix::try_new(rows, cols)?;
+    for (row, col) in cells {
+        matrix.set(row.abs_diff(top), col.abs_diff(left), 1);
+    }
+    Ok(matrix)
+}
+
+/// Encodes a matrix in Life 1.06 format, row after row, with the top-left
+/// cell at `0 0`.
+pub fn encode(matrix: &Matrix) -> String {
+    let mut out = format!("{LIFE_106_HEADER}\n");
+
+    for row in 0..matrix.row_count() {
+        for col in 0..matrix.col_count() {
+            if matrix.get(row, col) != 0 {
+                writeln!(out, "{col} {row}").unwrap();
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,337 @@
+//! Golly's macrocell format, which stores a pattern as a quadtree whose
+//! identical subtrees are written once, so huge but regular patterns stay
+//! small on disk. Boards are dense, so loading is limited to patterns whose
+//! live cells fit in memory as a matrix, whatever the size of the tree.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::matrix::Matrix;
+
+/// Width and height of the leaves of two state patterns.
+const LEAF_SIZE: usize = 8;
+/// Deepest tree whose coordinates fit in a `u64`.
+const MAX_LEVEL: u32 = 63;
+
+#[derive(Clone)]
+enum Node {
+    /// An 8x8 block, one bit per cell, the top row in the lowest byte.
+    Leaf(u64),
+    /// A 2x2 block of cell states, for multi-state patterns.
+    Cells([u8; 4]),
+    /// Nodes of the level below for the nw, ne, sw and se quadrants, 0 for
+    /// empty ones.
+    Tree { level: u32, children: [usize; 4] },
+}
+
+impl Node {
+    fn level(&self) -> u32 {
+        match self {
+            Node::Leaf(_) => 3,
+            Node::Cells(_) => 1,
+            Node::Tree { level, .. } => *level,
+        }
+    }
+}
+
+/// Live cells of a node from `top` to `bottom` and `left` to `right`, inclusive.
+#[derive(Clone, Copy)]
+struct Bounds {
+    top: u64,
+    left: u64,
+    bottom: u64,
+    right: u64,
+}
+
+impl Bounds {
+    fn union(self, other: Bounds) -> Bounds {
+        Bounds {
+            top: self.top.min(other.top),
+            left: self.left.min(other.left),
+            bottom: self.bottom.max(other.bottom),
+            right: self.right.max(other.right),
+        }
+    }
+
+    fn shift(self, rows: u64, cols: u64) -> Bounds {
+        Bounds {
+            top: self.top + rows,
+            left: self.left + cols,
+            bottom: self.bottom + rows,
+            right: self.right + cols,
+        }
+    }
+}
+
+/// Parses a pattern in macrocell format: a `[M2]` line, `#` comment lines
+/// and one node per line, numbered from 1. Leaves are 8x8 blocks given row
+/// by row, `.` for dead and `*` for live cells with `$` ending a row, other
+/// nodes are `<level> <nw> <ne> <sw> <se>` with 0 for empty quadrants. The
+/// last node is the whole pattern, which is cropped to its live cells.
+pub fn parse(text: &str) -> Result<Matrix, String> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    match lines.next() {
+        Some(line) if line.starts_with("[M2]") => {}
+        _ => return Err("missing macrocell header [M2]".to_string()),
+    }
+
+    // node 0 is the empty node of any level
+    let mut nodes = vec![Node::Leaf(0)];
+    for line in lines.filter(|line| !line.starts_with('#')) {
+        let node = if line.starts_with(['.', '*', '$']) {
+            parse_leaf(line)?
+        } else {
+            parse_tree(line, &nodes)?
+        };
+        nodes.push(node);
+    }
+
+    let root = nodes.len() - 1;
+    let mut bounds = vec![None; nodes.len()];
+    for node in 1..nodes.len() {
+        bounds[node] = node_bounds(&nodes, &bounds, node);
+    }
+    let Some(pattern) = bounds[root] else {
+        return Ok(Matrix::new(0, 0));
+    };
+
+    let size = |first: u64, last: u64| {
+        usize::try_from(last - first + 1).map_err(|_| "macrocell pattern is too large".to_string())
+    };
+    let mut matrix = Matrix::try_new(
+        size(pattern.top, pattern.bottom)?,
+        size(pattern.left, pattern.right)?,
+    )?;
+    paint(&nodes, &bounds, root, (0, 0), pattern, &mut matrix);
+    Ok(matrix)
+}
+
+fn parse_leaf(line: &str) -> Result<Node, String> {
+    let mut cells = 0;
+    let (mut row, mut col) = (0, 0);
+
+    for ch in line.chars() {
+        match ch {
+            '$' => {
+                row += 1;
+                col = 0;
+                continue;
+            }
+            '*' if row < LEAF_SIZE && col < LEAF_SIZE => cells |= 1 << (row * LEAF_SIZE + col),
+            '.' => {}
+            '*' => return Err(format!("macrocell leaf {line:?} is larger than 8x8")),
+            ch => return Err(format!("unexpected character {ch:?} in macrocell leaf")),
+        }
+        col += 1;
+    }
+
+    Ok(Node::Leaf(cells))
+}
+
+fn parse_tree(line: &str, nodes: &[Node]) -> Result<Node, String> {
+    let invalid = || format!("invalid macrocell node {line:?}");
+    let fields = line
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| invalid())?;
+    let [level, nw, ne, sw, se] = fields[..] else {
+        return Err(invalid());
+    };
+    let level = u32::try_from(level).map_err(|_| invalid())?;
+
+    if level == 1 {
+        let state = |state: usize| u8::try_from(state).map_err(|_| invalid());
+        return Ok(Node::Cells([
+            state(nw)?,
+            state(ne)?,
+            state(sw)?,
+            state(se)?,
+        ]));
+    }
+    if !(2..=MAX_LEVEL).contains(&level) {
+        return Err(format!("macrocell node {line:?} has an unsupported level"));
+    }
+
+    let children = [nw, ne, sw, se];
+    for &child in &children {
+        match nodes.get(child) {
+            Some(node) if child == 0 || node.level() == level - 1 => {}
+            Some(_) => return Err(format!("macrocell node {line:?} mixes levels")),
+            None => return Err(format!("macrocell node {line:?} refers to a later node")),
+        }
+    }
+    Ok(Node::Tree { level, children })
+}
+
+/// Live cells of `node` relative to its top-left corner, from the bounds
+/// of the nodes before it.
+fn node_bounds(nodes: &[Node], bounds: &[Option<Bounds>], node: usize) -> Option<Bounds> {
+    let cell = |row: usize, col: usize| Bounds {
+        top: row as u64,
+        left: col as u64,
+        bottom: row as u64,
+        right: col as u64,
+    };
+
+    match nodes[node] {
+        Node::Leaf(cells) => (0..LEAF_SIZE * LEAF_SIZE)
+            .filter(|bit| cells & (1 << bit) != 0)
+            .map(|bit| cell(bit / LEAF_SIZE, bit % LEAF_SIZE))
+            .reduce(Bounds::union),
+        Node::Cells(states) => (0..4)
+            .filter(|&quadrant| states[quadrant] != 0)
+            .map(|quadrant| cell(quadrant / 2, quadrant % 2))
+            .reduce(Bounds::union),
+        Node::Tree { level, children } => {
+            let half = 1 << (level - 1);
+            (0..4)
+                .filter_map(|quadrant| {
+                    let (row, col) = quadrant_corner(quadrant, half);
+                    Some(bounds[children[quadrant]]?.shift(row, col))
+                })
+                .reduce(Bounds::union)
+        }
+    }
+}
+
+/// Top-left corner of the nw, ne, sw or se quadrant of a node whose
+/// quadrants are `half` cells wide.
+fn quadrant_corner(quadrant: usize, half: u64) -> (u64, u64) {
+    ((quadrant / 2) as u64 * half, (quadrant % 2) as u64 * half)
+}
+
+/// Sets the live cells of `node`, whose top-left corner is at `corner` in
+/// the tree, in the matrix holding the `pattern` part of the tree.
+fn paint(
+    nodes: &[Node],
+    bounds: &[Option<Bounds>],
+    node: usize,
+    corner: (u64, u64),
+    pattern: Bounds,
+    matrix: &mut Matrix,
+) {
+    if bounds[node].is_none() {
+        return;
+    }
+    let mut set = |row: u64, col: u64| {
+        let (row, col) = (corner.0 + row - pattern.top, corner.1 + col - pattern.left);
+        matrix.set(row as usize, col as usize, 1);
+    };
+
+    match nodes[node] {
+        Node::Leaf(cells) => {
+            for bit in (0..LEAF_SIZE * LEAF_SIZE).filter(|bit| cells & (1 << bit) != 0) {
+                set((bit / LEAF_SIZE) as u64, (bit % LEAF_SIZE) as u64);
+            }
+        }
+        Node::Cells(states) => {
+            for quadrant in (0..4).filter(|&quadrant| states[quadrant] != 0) {
+                set((quadrant / 2) as u64, (quadrant % 2) as u64);
+            }
+        }
+        Node::Tree { level, children } => {
+            let half = 1 << (level - 1);
+            for (quadrant, &child) in children.iter().enumerate() {
+                let (row, col) = quadrant_corner(quadrant, half);
+                paint(
+                    nodes,
+                    bounds,
+                    child,
+                    (corner.0 + row, corner.1 + col),
+                    pattern,
+                    matrix,
+                );
+            }
+        }
+    }
+}
+
+/// Encodes a matrix in macrocell format, as a tree just large enough to
+/// hold it with the matrix in its top-left corner.
+pub fn encode(matrix: &Matrix) -> String {
+    let mut level = 3;
+    while 1 << level < matrix.row_count().max(matrix.col_count()) {
+        level += 1;
+    }
+
+    let mut encoder = Encoder {
+        matrix,
+        lines: Vec::new(),
+        ids: HashMap::new(),
+    };
+    encoder.node(level, 0, 0);
+
+    let mut out = String::from("[M2] (conway-game-of-life)\n#R B3/S23\n");
+    for line in encoder.lines {
+        writeln!(out, "{line}").unwrap();
+    }
+    out
+}
+
+struct Encoder<'a> {
+    matrix: &'a Matrix,
+    /// The nodes written so far, node `n` on line `n - 1`.
+    lines: Vec<String>,
+    /// Number of every distinct node written so far.
+    ids: HashMap<String, usize>,
+}
+
+impl Encoder<'_> {
+    /// Writes the node of size `2^level` with its top-left corner at
+    /// `(row, col)` unless an identical one was already written, and
+    /// returns its number, 0 if all its cells are dead.
+    fn node(&mut self, level: u32, row: usize, col: usize) -> usize {
+        let line = if level == 3 {
+            self.leaf(row, col)
+        } else {
+            let half = 1 << (level - 1);
+            let children = [
+                self.node(level - 1, row, col),
+                self.node(level - 1, row, col + half),
+                self.node(level - 1, row + half, col),
+                self.node(level - 1, row + half, col + half),
+            ];
+            if children == [0; 4] {
+                return 0;
+            }
+            let [nw, ne, sw, se] = children;
+            format!("{level} {nw} {ne} {sw} {se}")
+        };
+        if line.is_empty() {
+            return 0;
+        }
+
+        let next = self.lines.len() + 1;
+        *self.ids.entry(line).or_insert_with_key(|line| {
+            self.lines.push(line.clone());
+            next
+        })
+    }
+
+    /// The 8x8 block with its top-left corner at `(row, col)`, without
+    /// trailing dead cells and rows, empty if all cells are dead.
+    fn leaf(&self, row: usize, col: usize) -> String {
+        let alive = |row: usize, col: usize| {
+            row < self.matrix.row_count()
+                && col < self.matrix.col_count()
+                && self.matrix.get(row, col) != 0
+        };
+
+        let mut rows: Vec<String> = (row..row + LEAF_SIZE)
+            .map(|row| {
+                let len = (col..col + LEAF_SIZE)
+                    .rposition(|col| alive(row, col))
+                    .map_or(0, |len| len + 1);
+                (col..col + len)
+                    .map(|col| if alive(row, col) { '*' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        while rows.last().is_some_and(String::is_empty) {
+            rows.pop();
+        }
+
+        rows.iter().map(|row| format!("{row}$")).collect()
+    }
+}
@@ -1,16 +1,24 @@
-use ansi_escapes;
-use clap::Parser;
-use conway_game_of_life::game_of_life::{GameOfLife, GameOfLifeArgs};
-use std::{thread, time::Duration};
+use conway_game_of_life::cli::{Cli, Command};
+use conway_game_of_life::{
+    analyze, batch, bench, convert, demo, diff, distributed, reverse, search, still, tui, verify,
+};
+use std::process::ExitCode;
 
-fn main() {
-    let args = GameOfLifeArgs::parse();
-    let mut game = GameOfLife::from_args(&args);
-
-    loop {
-        print!("{}", ansi_escapes::ClearScreen);
-        println!("{game}");
-        game.tick();
-        thread::sleep(Duration::from_secs(1));
+fn main() -> ExitCode {
+    match Cli::parse_with_config().command() {
+        Command::View(args) => tui::run(args),
+        Command::Run(args) => return batch::run(args),
+        Command::Convert(args) => convert::run(args),
+        Command::Bench(args) => bench::run(args),
+        Command::Search(args) => search::run(args),
+        Command::SearchStill(args) => still::run(args),
+        Command::Verify(args) => return verify::run(args),
+        Command::Analyze(args) => analyze::run(args),
+        Command::Distributed(args) => distributed::run(args),
+        Command::Demo(args) => demo::run(args),
+        Command::Diff(args) => return diff::run(args),
+        Command::Reverse(args) => return reverse::run(args),
     }
+
+    ExitCode::SUCCESS
 }
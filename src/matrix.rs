@@ -1,25 +1,266 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use clap::ValueEnum;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+#[cfg(feature = "ndarray")]
+use ndarray::{Array2, ArrayView2, ArrayViewMut2};
+use rand::Rng;
 use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
-use std::fmt::{self, Display};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+#[cfg(feature = "std")]
+use std::{io, path::Path};
+use xxhash_rust::xxh3::Xxh3;
 
-pub struct Matrix {
+#[cfg(feature = "std")]
+use crate::format;
+#[cfg(all(feature = "native", unix))]
+use crate::mmap::Mapping;
+#[cfg(feature = "std")]
+use crate::rule::Rule;
+
+/// What happens to cells written beyond the matrix boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum Overflow {
+    /// Cells beyond the boundaries are dropped
+    #[default]
+    Clip,
+    /// Cells beyond the boundaries loop back to the opposite side
+    Wrap,
+}
+
+/// The part of a matrix that stays in place when it is resized, cells are
+/// added or dropped on the opposite sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// How far the cells move when a matrix of `from` rows and columns is
+    /// resized to `to`, negative where they move up or left.
+    pub fn offset(self, from: (usize, usize), to: (usize, usize)) -> (isize, isize) {
+        use Side::*;
+
+        let (vertical, horizontal) = match self {
+            Anchor::TopLeft => (Start, Start),
+            Anchor::Top => (Start, Middle),
+            Anchor::TopRight => (Start, End),
+            Anchor::Left => (Middle, Start),
+            Anchor::Center => (Middle, Middle),
+            Anchor::Right => (Middle, End),
+            Anchor::BottomLeft => (End, Start),
+            Anchor::Bottom => (End, Middle),
+            Anchor::BottomRight => (End, End),
+        };
+        (vertical.shift(from.0, to.0), horizontal.shift(from.1, to.1))
+    }
+}
+
+/// Where an anchor lies along one axis.
+#[derive(Clone, Copy)]
+enum Side {
+    Start,
+    Middle,
+    End,
+}
+
+impl Side {
+    fn shift(self, from: usize, to: usize) -> isize {
+        let delta = to as isize - from as isize;
+        match self {
+            Side::Start => 0,
+            Side::Middle => delta / 2,
+            Side::End => delta,
+        }
+    }
+}
+
+/// A rectangle of cells, with its top-left corner at `(row, col)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct BoundingBox {
+    pub row: usize,
+    pub col: usize,
     pub rows: usize,
     pub cols: usize,
-    pub matrix: Vec<u8>,
 }
 
-impl Matrix {
+/// A grid of cells stored row after row. Boards hold `u8` cells, 0 for dead
+/// and 1 for alive, other cell types serve e.g. multi-state cells or ages.
+///
+/// The cells always number `rows * cols`, which is why they are only
+/// reachable as a slice of that length. They are kept in memory, or for
+/// boards larger than that in a file mapped into memory.
+#[derive(PartialEq, Eq, Hash)]
+pub struct Matrix<T = u8> {
+    rows: usize,
+    cols: usize,
+    matrix: Cells<T>,
+}
+
+/// Where the cells of a matrix are kept.
+enum Cells<T> {
+    Heap(Vec<T>),
+    /// The bytes of a file, only ever holding `u8` cells.
+    #[cfg(all(feature = "native", unix))]
+    Mapped(Mapping, PhantomData<T>),
+}
+
+impl<T> Deref for Cells<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Cells::Heap(cells) => cells,
+            // SAFETY: mapped cells are `u8`s, see `Matrix::map_file`
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(mapping, _) => {
+                let bytes = mapping.as_slice();
+                unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len()) }
+            }
+        }
+    }
+}
+
+impl<T> DerefMut for Cells<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            Cells::Heap(cells) => cells,
+            // SAFETY: as for `deref`
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(mapping, _) => {
+                let bytes = mapping.as_mut_slice();
+                unsafe { core::slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), bytes.len()) }
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Cells<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for Cells<T> {}
+
+impl<T: Hash> Hash for Cells<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+/// Copies of mapped cells are kept in memory.
+impl<T: Clone> Clone for Cells<T> {
+    fn clone(&self) -> Self {
+        Cells::Heap(self.to_vec())
+    }
+
+    /// Reuses the allocation of the cells, and writes mapped cells to their
+    /// file.
+    fn clone_from(&mut self, source: &Self) {
+        match self {
+            Cells::Heap(cells) => {
+                cells.clear();
+                cells.extend_from_slice(source);
+            }
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(..) if self.len() == source.len() => self.clone_from_slice(source),
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(..) => *self = source.clone(),
+        }
+    }
+}
+
+impl<T: Copy + Default> Matrix<T> {
+    /// A matrix of default cells, dead ones for boards.
     pub fn new(rows: usize, cols: usize) -> Self {
         Matrix {
             rows,
             cols,
-            matrix: vec![0; rows * cols],
+            matrix: Cells::Heap(vec![T::default(); rows * cols]),
         }
     }
 
+    /// Like `new`, but fails on an empty matrix or one with more cells than
+    /// can be addressed or allocated.
+    pub fn try_new(rows: usize, cols: usize) -> Result<Self, String> {
+        if rows == 0 || cols == 0 {
+            return Err(format!("matrix of {rows}x{cols} cells is empty"));
+        }
+        let too_large = || format!("matrix of {rows}x{cols} cells is too large");
+        let size = rows
+            .checked_mul(cols)
+            .filter(|&size| size <= isize::MAX as usize / mem::size_of::<T>().max(1))
+            .ok_or_else(too_large)?;
+        let mut cells = Vec::new();
+        cells.try_reserve_exact(size).map_err(|_| too_large())?;
+        cells.resize(size, T::default());
+
+        Ok(Matrix {
+            rows,
+            cols,
+            matrix: Cells::Heap(cells),
+        })
+    }
+
+    /// A matrix of `rows` by `cols` cells given row after row, or `None`
+    /// if there are not exactly `rows * cols` of them.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        (rows.checked_mul(cols) == Some(cells.len())).then_some(Matrix {
+            rows,
+            cols,
+            matrix: Cells::Heap(cells),
+        })
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.cols
+    }
+
     pub fn size(&self) -> usize {
         self.rows * self.cols
     }
 
+    /// The cells row after row.
+    pub fn as_slice(&self) -> &[T] {
+        &self.matrix
+    }
+
+    /// The cells row after row, which can be changed but not added or removed.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.matrix
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        match self.matrix {
+            Cells::Heap(cells) => cells,
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(..) => self.matrix.to_vec(),
+        }
+    }
+
     pub fn inverse_idx(&self, idx: usize) -> (usize, usize) {
         let row = idx / self.cols;
         let col = idx % self.cols;
@@ -30,56 +271,456 @@ impl Matrix {
         row * self.cols + col
     }
 
-    pub fn get(&self, row: usize, col: usize) -> u8 {
+    pub fn get(&self, row: usize, col: usize) -> T {
         let idx = self.idx(row, col);
         self.matrix[idx]
     }
 
-    pub fn get_mut(&mut self, row: usize, col: usize) -> &mut u8 {
+    pub fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
         let idx = self.idx(row, col);
         &mut self.matrix[idx]
     }
 
-    pub fn set(&mut self, row: usize, col: usize, val: u8) {
+    pub fn set(&mut self, row: usize, col: usize, val: T) {
         let idx = self.idx(row, col);
         self.matrix[idx] = val;
     }
+
+    /// The rows from top to bottom, each a slice of `cols` cells.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks of an empty matrix are never taken, whatever their size
+        self.matrix.chunks(self.cols.max(1))
+    }
+
+    /// The cells row after row, along with their `(row, col)` position.
+    pub fn iter_cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.matrix
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.inverse_idx(idx), cell))
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.matrix.fill(value);
+    }
+
+    /// Resets every cell to the default, dead for boards.
+    pub fn clear(&mut self) {
+        self.fill(T::default());
+    }
+
+    /// Copies all cells of `other` into this matrix with its top-left corner at `(row, col)`.
+    pub fn blit(&mut self, other: &Matrix<T>, row: usize, col: usize, overflow: Overflow) {
+        if self.rows == 0 || self.cols == 0 {
+            return;
+        }
+
+        for other_row in 0..other.rows {
+            for other_col in 0..other.cols {
+                let (target_row, target_col) = (row + other_row, col + other_col);
+                let (target_row, target_col) = match overflow {
+                    Overflow::Clip if target_row >= self.rows || target_col >= self.cols => {
+                        continue
+                    }
+                    Overflow::Clip => (target_row, target_col),
+                    Overflow::Wrap => (target_row % self.rows, target_col % self.cols),
+                };
+                self.set(target_row, target_col, other.get(other_row, other_col));
+            }
+        }
+    }
+
+    /// A copy of the matrix with the given number of default rows and columns added on each side.
+    pub fn expand(&self, top: usize, bottom: usize, left: usize, right: usize) -> Matrix<T> {
+        let mut expanded = Matrix::new(top + self.rows + bottom, left + self.cols + right);
+
+        for row in 0..self.rows {
+            let start = expanded.idx(top + row, left);
+            expanded.matrix[start..start + self.cols]
+                .copy_from_slice(&self.matrix[row * self.cols..(row + 1) * self.cols]);
+        }
+
+        expanded
+    }
+
+    /// The `rows` by `cols` cells with their top-left corner at `(row, col)`,
+    /// which must lie within the matrix.
+    pub fn crop(&self, row: usize, col: usize, rows: usize, cols: usize) -> Matrix<T> {
+        assert!(row + rows <= self.rows && col + cols <= self.cols);
+
+        let mut cropped = Vec::with_capacity(rows * cols);
+        for row in row..row + rows {
+            let start = self.idx(row, col);
+            cropped.extend_from_slice(&self.matrix[start..start + cols]);
+        }
+        Matrix {
+            rows,
+            cols,
+            matrix: Cells::Heap(cropped),
+        }
+    }
+
+    /// The matrix turned a quarter clockwise.
+    pub fn rotate90(&self) -> Matrix<T> {
+        self.transposed(|row, col| (self.rows - 1 - col, row))
+    }
+
+    pub fn rotate180(&self) -> Matrix<T> {
+        self.transformed(|row, col| (self.rows - 1 - row, self.cols - 1 - col))
+    }
+
+    /// The matrix turned a quarter counterclockwise.
+    pub fn rotate270(&self) -> Matrix<T> {
+        self.transposed(|row, col| (col, self.cols - 1 - row))
+    }
+
+    /// The matrix mirrored left to right.
+    pub fn flip_h(&self) -> Matrix<T> {
+        self.transformed(|row, col| (row, self.cols - 1 - col))
+    }
+
+    /// The matrix mirrored top to bottom.
+    pub fn flip_v(&self) -> Matrix<T> {
+        self.transformed(|row, col| (self.rows - 1 - row, col))
+    }
+
+    /// A matrix of the same size whose cell at `(row, col)` is the cell of
+    /// this one at `source(row, col)`.
+    fn transformed(&self, source: impl Fn(usize, usize) -> (usize, usize)) -> Matrix<T> {
+        self.remapped(self.rows, self.cols, source)
+    }
+
+    /// Like `transformed`, for a matrix with rows and columns swapped.
+    fn transposed(&self, source: impl Fn(usize, usize) -> (usize, usize)) -> Matrix<T> {
+        self.remapped(self.cols, self.rows, source)
+    }
+
+    fn remapped(
+        &self,
+        rows: usize,
+        cols: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Matrix<T> {
+        let mut cells = Vec::with_capacity(rows * cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                let (source_row, source_col) = source(row, col);
+                cells.push(self.get(source_row, source_col));
+            }
+        }
+        Matrix {
+            rows,
+            cols,
+            matrix: Cells::Heap(cells),
+        }
+    }
+
+    /// Changes the size of the matrix, keeping the cells around `anchor` in
+    /// place. Cells pushed beyond the new boundaries are dropped and new
+    /// cells are default ones.
+    pub fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor) {
+        if (rows, cols) == (self.rows, self.cols) {
+            return;
+        }
+
+        let (row_offset, col_offset) = anchor.offset((self.rows, self.cols), (rows, cols));
+        let mut resized = Matrix::new(rows, cols);
+        // the columns of the old matrix that remain, and where they end up
+        let first_col = (-col_offset).max(0) as usize;
+        let target_col = col_offset.max(0) as usize;
+        let width = self
+            .cols
+            .saturating_sub(first_col)
+            .min(cols.saturating_sub(target_col));
+
+        for row in 0..self.rows {
+            let target_row = row as isize + row_offset;
+            if width == 0 || !(0..rows as isize).contains(&target_row) {
+                continue;
+            }
+            let start = resized.idx(target_row as usize, target_col);
+            let source = self.idx(row, first_col);
+            resized.matrix[start..start + width]
+                .copy_from_slice(&self.matrix[source..source + width]);
+        }
+
+        *self = resized;
+    }
+}
+
+impl<T: Clone> Clone for Matrix<T> {
+    fn clone(&self) -> Self {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            matrix: self.matrix.clone(),
+        }
+    }
+
+    /// Reuses the allocation of the cells, e.g. for a copy kept every tick.
+    fn clone_from(&mut self, source: &Self) {
+        self.rows = source.rows;
+        self.cols = source.cols;
+        self.matrix.clone_from(&source.matrix);
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T> Matrix<T> {
+    /// The cells as a `rows` by `cols` ndarray view.
+    pub fn view(&self) -> ArrayView2<'_, T> {
+        ArrayView2::from_shape((self.rows, self.cols), &self.matrix[..]).expect("shape exception")
+    }
+
+    /// The cells as a mutable `rows` by `cols` ndarray view.
+    pub fn view_mut(&mut self) -> ArrayViewMut2<'_, T> {
+        ArrayViewMut2::from_shape((self.rows, self.cols), &mut self.matrix[..])
+            .expect("shape exception")
+    }
+}
+
+/// A matrix of the cells of an ndarray array, in any memory layout.
+#[cfg(feature = "ndarray")]
+impl<T: Clone> From<Array2<T>> for Matrix<T> {
+    fn from(array: Array2<T>) -> Self {
+        let (rows, cols) = array.dim();
+        Matrix {
+            rows,
+            cols,
+            matrix: Cells::Heap(array.iter().cloned().collect()),
+        }
+    }
+}
+
+/// The cell at `(row, col)`.
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.matrix[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.matrix[row * self.cols + col]
+    }
+}
+
+impl Matrix {
+    /// Number of live cells.
+    pub fn count_live(&self) -> usize {
+        self.matrix.iter().filter(|&&cell| cell != 0).count()
+    }
+
+    /// A 64-bit XXH3 hash of the dimensions and cells, equal for equal
+    /// boards wherever they are kept, to compare or deduplicate boards
+    /// without keeping them around. The cells are hashed as they are, so
+    /// the states of multi-state rules tell boards apart too.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = Xxh3::new();
+        hasher.update(&(self.rows as u64).to_le_bytes());
+        hasher.update(&(self.cols as u64).to_le_bytes());
+        hasher.update(&self.matrix);
+        hasher.digest()
+    }
+
+    /// The smallest rectangle holding every live cell, `None` if all are dead.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let live_rows =
+            || (0..self.rows).filter(|&row| (0..self.cols).any(|col| self.get(row, col) != 0));
+        let top = live_rows().next()?;
+        let bottom = live_rows().next_back()?;
+        let live_cols =
+            || (0..self.cols).filter(|&col| (top..=bottom).any(|row| self.get(row, col) != 0));
+        let left = live_cols().next()?;
+        let right = live_cols().next_back()?;
+
+        Some(BoundingBox {
+            row: top,
+            col: left,
+            rows: bottom - top + 1,
+            cols: right - left + 1,
+        })
+    }
+
+    /// The bounding box of the live cells, an empty matrix if all are dead.
+    pub fn crop_to_bounding_box(&self) -> Matrix {
+        match self.bounding_box() {
+            Some(bounds) => self.crop(bounds.row, bounds.col, bounds.rows, bounds.cols),
+            None => Matrix::new(0, 0),
+        }
+    }
+
+    /// A matrix whose cells are each alive with probability `fill_prob`.
+    pub fn random<R: Rng>(rows: usize, cols: usize, fill_prob: f64, rng: &mut R) -> Self {
+        let mut matrix = Matrix::new(rows, cols);
+        matrix.randomize(fill_prob, rng);
+        matrix
+    }
+
+    /// Makes each cell alive with probability `fill_prob`, drawing the cells
+    /// row after row like `random`.
+    pub fn randomize<R: Rng>(&mut self, fill_prob: f64, rng: &mut R) {
+        for cell in self.matrix.iter_mut() {
+            *cell = u8::from(rng.gen_bool(fill_prob));
+        }
+    }
+
+    /// A board of `rows` by `cols` cells kept in the file at `path` rather
+    /// than in memory, one byte per cell row after row, for boards larger
+    /// than memory. The file is created with dead cells if it doesn't exist
+    /// or is empty, returns whether it was; an existing file must hold
+    /// `rows * cols` bytes and keeps its board. Changes to the board are
+    /// written to the file as they happen, as far as the operating system
+    /// sees fit, and completely when the board is dropped or flushed.
+    #[cfg(all(feature = "native", unix))]
+    pub fn map_file<P: AsRef<Path>>(path: P, rows: usize, cols: usize) -> io::Result<(Self, bool)> {
+        let size = rows.checked_mul(cols).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("matrix of {rows}x{cols} cells is too large"),
+            )
+        })?;
+        let (mapping, created) = Mapping::open(path, size)?;
+        let matrix = Matrix {
+            rows,
+            cols,
+            matrix: Cells::Mapped(mapping, PhantomData),
+        };
+        Ok((matrix, created))
+    }
+
+    /// Whether the cells are kept in a file, see `map_file`.
+    pub fn is_mapped(&self) -> bool {
+        match self.matrix {
+            Cells::Heap(_) => false,
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(..) => true,
+        }
+    }
+
+    /// Writes the cells of a board kept in a file to it, waiting until
+    /// they are. Boards in memory have nothing to write.
+    #[cfg(feature = "std")]
+    pub fn flush(&self) -> io::Result<()> {
+        match &self.matrix {
+            Cells::Heap(_) => Ok(()),
+            #[cfg(all(feature = "native", unix))]
+            Cells::Mapped(mapping, _) => mapping.flush(),
+        }
+    }
+
+    /// Reads a board from a file in the format its name suggests, or from
+    /// stdin in the format its text suggests if the path is `-`.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        format::read(path.as_ref(), None)
+    }
+
+    /// Reads a board like `load`, along with the rule its file names, if
+    /// any.
+    #[cfg(feature = "std")]
+    pub fn load_with_rule<P: AsRef<Path>>(path: P) -> io::Result<(Self, Option<Rule>)> {
+        format::read_with_rule(path.as_ref(), None)
+    }
+
+    /// Reads a board like `load` to run it under `rule`, failing if its file
+    /// names another rule.
+    #[cfg(feature = "std")]
+    pub fn load_for_rule<P: AsRef<Path>>(path: P, rule: &Rule) -> io::Result<Self> {
+        match Self::load_with_rule(&path)? {
+            (_, Some(named)) if named.with_edge(rule.edge) != *rule => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} is a pattern of rule {named}, not {rule}",
+                    path.as_ref().display()
+                ),
+            )),
+            (matrix, _) => Ok(matrix),
+        }
+    }
+
+    /// Writes the board to a file in the format its name suggests, or to
+    /// stdout as RLE if the path is `-`.
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        format::write(path.as_ref(), self, None)
+    }
+
+    /// Writes the board like `save`, naming `rule` if the format does.
+    #[cfg(feature = "std")]
+    pub fn save_with_rule<P: AsRef<Path>>(&self, path: P, rule: &Rule) -> io::Result<()> {
+        format::write_with_rule(path.as_ref(), self, None, rule)
+    }
 }
 
 impl Display for Matrix {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for row in 0..self.rows {
             for col in 0..self.cols {
                 let cell = if self.get(row, col) == 0 { "." } else { "x" };
                 write!(f, "{} ", cell)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
-impl<'de> Deserialize<'de> for Matrix {
+/// Formats continuous cells with two decimals, one row per line.
+macro_rules! impl_display_float {
+    ($($float:ty),*) => {$(
+        impl Display for Matrix<$float> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for row in self.rows() {
+                    for cell in row {
+                        write!(f, "{cell:.2} ")?;
+                    }
+                    writeln!(f)?;
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_display_float!(f32, f64);
+
+impl<T: Serialize> Serialize for Matrix<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.rows))?;
+        for row in 0..self.rows {
+            seq.serialize_element(&self.matrix[row * self.cols..(row + 1) * self.cols])?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Matrix<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct MatrixVisitor;
+        struct MatrixVisitor<T>(PhantomData<T>);
 
-        impl<'de> Visitor<'de> for MatrixVisitor {
-            type Value = Matrix;
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for MatrixVisitor<T> {
+            type Value = Matrix<T>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("a 2D matrix represented as a nested list")
             }
 
-            fn visit_seq<A>(self, mut seq: A) -> Result<Matrix, A::Error>
+            fn visit_seq<A>(self, mut seq: A) -> Result<Matrix<T>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
-                let mut matrix: Vec<Vec<u8>> = Vec::new();
+                let mut matrix: Vec<Vec<T>> = Vec::new();
 
-                while let Some(row) = seq.next_element::<Vec<u8>>()? {
+                while let Some(row) = seq.next_element::<Vec<T>>()? {
                     matrix.push(row);
                 }
 
@@ -91,11 +732,11 @@ impl<'de> Deserialize<'de> for Matrix {
                 Ok(Matrix {
                     rows,
                     cols,
-                    matrix: matrix_data,
+                    matrix: Cells::Heap(matrix_data),
                 })
             }
         }
 
-        deserializer.deserialize_seq(MatrixVisitor)
+        deserializer.deserialize_seq(MatrixVisitor(PhantomData))
     }
 }
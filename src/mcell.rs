@@ -0,0 +1,143 @@
+//! MCell's pattern format, a board size followed by runs of cells much like RLE.
+
+use std::fmt::Write;
+
+use crate::matrix::Matrix;
+
+/// Longest line written in the body of an MCell file.
+const LINE_WIDTH: usize = 70;
+
+/// Parses a pattern in MCell format: a `#MCell` line, `#L` lines holding
+/// runs of `.` (dead) and letter (alive) cells with `$` ending a row, and
+/// other `#` lines such as `#BOARD <cols>x<rows>`, the size of the board
+/// the runs start at the top-left of. Without a board the matrix is just
+/// large enough for the cells.
+pub fn parse(text: &str) -> Result<Matrix, String> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    match lines.next() {
+        Some(line) if line.starts_with("#MCell") => {}
+        _ => return Err("missing #MCell header".to_string()),
+    }
+
+    let mut board = None;
+    let mut cells = Vec::new();
+    let (mut row, mut col) = (0, 0);
+    let mut count = String::new();
+
+    for line in lines {
+        if let Some(size) = line.strip_prefix("#BOARD") {
+            board = Some(parse_board(size.trim())?);
+            continue;
+        }
+        let Some(body) = line.strip_prefix("#L") else {
+            continue;
+        };
+
+        for ch in body.trim().chars() {
+            if ch.is_ascii_digit() {
+                count.push(ch);
+                continue;
+            }
+            // lowercase letters pick the range of the state that follows
+            if ch.is_ascii_lowercase() {
+                continue;
+            }
+
+            let run = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .map_err(|_| format!("invalid run length {count:?}"))?
+            };
+            count.clear();
+
+            match ch {
+                '.' => col += run,
+                '$' => {
+                    row += run;
+                    col = 0;
+                }
+                ch if ch.is_ascii_uppercase() => {
+                    cells.extend((col..col + run).map(|col| (row, col)));
+                    col += run;
+                }
+                ch => return Err(format!("unexpected character {ch:?}")),
+            }
+        }
+    }
+
+    let (rows, cols) = board.unwrap_or_else(|| {
+        let rows = cells.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+        let cols = cells.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+        (rows, cols)
+    });
+    let mut matrix = Matrix::try_new(rows, cols)?;
+    for (row, col) in cells {
+        if row >= rows || col >= cols {
+            return Err(format!("cells beyond the {cols}x{rows} board"));
+        }
+        matrix.set(row, col, 1);
+    }
+
+    Ok(matrix)
+}
+
+/// Reads the size of a board such as `100x80`, 100 columns and 80 rows.
+fn parse_board(size: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("invalid MCell board {size:?}");
+    let (cols, rows) = size.split_once('x').ok_or_else(invalid)?;
+    let rows = rows.trim().parse().map_err(|_| invalid())?;
+    let cols = cols.trim().parse().map_err(|_| invalid())?;
+    Ok((rows, cols))
+}
+
+/// Encodes a matrix in MCell format, the matrix being the whole board.
+pub fn encode(matrix: &Matrix) -> String {
+    let mut tokens = Vec::new();
+    let mut pending_rows = 0;
+
+    for row in matrix.rows() {
+        let len = row
+            .iter()
+            .rposition(|&cell| cell != 0)
+            .map_or(0, |col| col + 1);
+        if len == 0 {
+            pending_rows += 1;
+            continue;
+        }
+        if pending_rows > 0 {
+            tokens.push(run_token(pending_rows, '$'));
+        }
+        pending_rows = 1;
+
+        for run in row[..len].chunk_by(|a, b| (*a != 0) == (*b != 0)) {
+            tokens.push(run_token(run.len(), if run[0] != 0 { 'A' } else { '.' }));
+        }
+    }
+
+    let mut out = String::from("#MCell 4.20\n#GAME Life\n#RULE 23/3\n");
+    writeln!(out, "#BOARD {}x{}", matrix.col_count(), matrix.row_count()).unwrap();
+
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + token.len() > LINE_WIDTH {
+            writeln!(out, "#L {line}").unwrap();
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    if !line.is_empty() {
+        writeln!(out, "#L {line}").unwrap();
+    }
+
+    out
+}
+
+fn run_token(len: usize, tag: char) -> String {
+    if len == 1 {
+        tag.to_string()
+    } else {
+        format!("{len}{tag}")
+    }
+}
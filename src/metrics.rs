@@ -0,0 +1,94 @@
+//! Wall-clock time the engine takes per tick and the throughput it makes,
+//! to tell whether a parallel engine pays off on the board at hand.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// Number of recent ticks the averages are taken over.
+const WINDOW: usize = 32;
+
+/// Timings of the ticks so far. Only computing the next generation is
+/// timed, not recording, hooks or history.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    recent: VecDeque<Duration>,
+    /// Number of cells of the board at the latest tick.
+    cells: usize,
+    total: Duration,
+    ticks: usize,
+    started: Option<Instant>,
+}
+
+impl Metrics {
+    /// Starts timing a tick. WebAssembly without JavaScript has no clock, so
+    /// nothing is timed there.
+    pub(crate) fn start_tick(&mut self) {
+        self.started = (!cfg!(target_arch = "wasm32")).then(Instant::now);
+    }
+
+    /// Finishes timing the tick of a board of `cells` cells.
+    pub(crate) fn end_tick(&mut self, cells: usize) {
+        if let Some(started) = self.started.take() {
+            self.record(started.elapsed(), cells);
+        }
+    }
+
+    /// Counts a tick of a board of `cells` cells that took `duration`.
+    pub fn record(&mut self, duration: Duration, cells: usize) {
+        if self.recent.len() == WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(duration);
+        self.cells = cells;
+        self.total += duration;
+        self.ticks += 1;
+    }
+
+    /// Number of ticks timed.
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    /// Time spent ticking in all.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    pub fn last_tick(&self) -> Option<Duration> {
+        self.recent.back().copied()
+    }
+
+    /// The mean duration of the recent ticks.
+    pub fn average_tick(&self) -> Option<Duration> {
+        let sum: Duration = self.recent.iter().sum();
+        (!self.recent.is_empty()).then(|| sum / self.recent.len() as u32)
+    }
+
+    /// Ticks per second at the recent pace.
+    pub fn ticks_per_sec(&self) -> Option<f64> {
+        self.average_tick()
+            .filter(|average| !average.is_zero())
+            .map(|average| 1.0 / average.as_secs_f64())
+    }
+
+    /// Cells computed per second at the recent pace.
+    pub fn cells_per_sec(&self) -> Option<f64> {
+        self.ticks_per_sec()
+            .map(|ticks_per_sec| ticks_per_sec * self.cells as f64)
+    }
+}
+
+/// Formats the recent pace, e.g. `1.234 ms/tick 8.1 Mcells/s`, or nothing
+/// before the first tick.
+impl Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(average) = self.average_tick() {
+            write!(f, "{:.3} ms/tick", average.as_secs_f64() * 1000.0)?;
+        }
+        if let Some(cells_per_sec) = self.cells_per_sec() {
+            write!(f, " {:.1} Mcells/s", cells_per_sec / 1e6)?;
+        }
+        Ok(())
+    }
+}
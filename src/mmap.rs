@@ -0,0 +1,103 @@
+//! Files mapped into memory, for boards larger than RAM: the operating
+//! system pages the cells in from the file as they are used and writes them
+//! back when it needs the memory, so whatever the board holds ends up in the
+//! file, also if the process dies.
+
+use std::fs::OpenOptions;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::path::Path;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+/// The bytes of a file, shared with it: changes to them are changes to the
+/// file.
+pub struct Mapping {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// the mapping is owned memory like a `Vec<u8>`, only backed by a file
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    /// Maps the `len` bytes of the file at `path`, which is created with
+    /// `len` zero bytes if it doesn't exist or is empty. Returns whether it
+    /// was, and fails if the file holds another number of bytes.
+    pub fn open<P: AsRef<Path>>(path: P, len: usize) -> io::Result<(Mapping, bool)> {
+        if len == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "an empty file cannot be mapped",
+            ));
+        }
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let created = match file.metadata()?.len() {
+            0 => {
+                file.set_len(len as u64)?;
+                true
+            }
+            size if size == len as u64 => false,
+            size => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} holds {size} bytes, expected {len}", path.display()),
+                ))
+            }
+        };
+
+        // SAFETY: a fresh shared mapping of the whole file, which outlives
+        // the file descriptor it was made from
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let ptr = NonNull::new(ptr.cast()).ok_or_else(io::Error::last_os_error)?;
+        Ok((Mapping { ptr, len }, created))
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: the mapping is `len` bytes long and lives as long as `self`
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: as for `as_slice`, and borrowed mutably through `self`
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Writes the changed bytes back to the file and waits until they are.
+    pub fn flush(&self) -> io::Result<()> {
+        // SAFETY: the whole mapping, which is still mapped
+        match unsafe { libc::msync(self.ptr.as_ptr().cast(), self.len, libc::MS_SYNC) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        let _ = self.flush();
+        // SAFETY: the whole mapping, which nothing borrows any more
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().cast(), self.len);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Random births and deaths on top of the rule, for studying how robust
+//! patterns are to noise. The noise of a generation only depends on the
+//! seed of the game and the generation, so runs with the same `--seed` are
+//! noisy in the same way, also after stepping backwards or resuming.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::Matrix;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Noise {
+    /// Probability of every dead cell coming alive in a generation.
+    pub birth: f64,
+    /// Probability of every live cell dying in a generation.
+    pub death: f64,
+}
+
+impl Noise {
+    pub fn new(birth: f64, death: f64) -> Self {
+        Noise { birth, death }
+    }
+
+    pub fn is_silent(&self) -> bool {
+        self.birth == 0.0 && self.death == 0.0
+    }
+
+    /// Flips the cells of generation `tick` that the noise seeded by `seed`
+    /// picks, and returns how many were born and how many died.
+    pub fn apply(&self, matrix: &mut Matrix, seed: u64, tick: usize) -> (usize, usize) {
+        let (mut births, mut deaths) = (0, 0);
+        if self.is_silent() {
+            return (births, deaths);
+        }
+
+        // spread consecutive generations over unrelated streams
+        let mut rng =
+            StdRng::seed_from_u64(seed ^ (tick as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        for cell in matrix.as_mut_slice() {
+            if *cell == 0 {
+                if rng.gen_bool(self.birth) {
+                    *cell = 1;
+                    births += 1;
+                }
+            } else if rng.gen_bool(self.death) {
+                *cell = 0;
+                deaths += 1;
+            }
+        }
+        (births, deaths)
+    }
+}
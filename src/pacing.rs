@@ -0,0 +1,86 @@
+//! Frames shown at a steady rate: every frame is due a whole interval after
+//! the previous one was due rather than after it was drawn, so the time spent
+//! ticking and drawing is taken out of the wait instead of slowing the rate
+//! down.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Number of latest frames the achieved rate is measured over.
+const MEASURED_FRAMES: usize = 16;
+
+/// The schedule of frames at a target rate, and the rate they were actually
+/// shown at.
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    interval: Duration,
+    /// When the next frame is due.
+    deadline: Instant,
+    /// When the latest frames were shown, oldest first.
+    shown: VecDeque<Instant>,
+}
+
+impl FramePacer {
+    /// Paces `fps` frames per second, the first due an interval after `now`.
+    pub fn new(fps: f64, now: Instant) -> Self {
+        let interval = Duration::from_secs_f64(1.0 / fps);
+        FramePacer {
+            interval,
+            deadline: now + interval,
+            shown: VecDeque::with_capacity(MEASURED_FRAMES),
+        }
+    }
+
+    /// Shows frames as fast as they come, e.g. for a time-lapse running at
+    /// full speed, still measuring the rate achieved.
+    pub fn unlimited(now: Instant) -> Self {
+        FramePacer {
+            interval: Duration::ZERO,
+            deadline: now,
+            shown: VecDeque::with_capacity(MEASURED_FRAMES),
+        }
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        now >= self.deadline
+    }
+
+    /// Time left until the next frame is due at `now`, zero if it is.
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.deadline.saturating_duration_since(now)
+    }
+
+    /// Records a frame shown at `now` and schedules the next one. A frame
+    /// running late is caught up with, unless it is a whole interval late,
+    /// then the schedule starts over from `now` rather than rushing out the
+    /// frames missed.
+    pub fn frame_shown(&mut self, now: Instant) {
+        if self.shown.len() == MEASURED_FRAMES {
+            self.shown.pop_front();
+        }
+        self.shown.push_back(now);
+
+        self.deadline += self.interval;
+        if self.deadline <= now {
+            self.deadline = now + self.interval;
+        }
+    }
+
+    /// Starts the schedule over an interval after `now`, e.g. after a pause,
+    /// which the achieved rate then leaves out.
+    pub fn restart(&mut self, now: Instant) {
+        self.deadline = now + self.interval;
+        self.shown.clear();
+    }
+
+    /// Frames per second shown over the latest frames, `None` before two
+    /// frames were.
+    pub fn achieved_fps(&self) -> Option<f64> {
+        let (first, last) = (self.shown.front()?, self.shown.back()?);
+        let elapsed = last.duration_since(*first);
+        match elapsed.is_zero() {
+            true => None,
+            false => Some((self.shown.len() - 1) as f64 / elapsed.as_secs_f64()),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edge::Edge;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// A copy of a [`Matrix`] surrounded by a one cell wide ghost border.
+///
+/// The border is synced according to the [`Edge`] mode on every load, so
+/// every interior cell has eight neighbors in memory and counting them
+/// needs no bounds checks.
+pub struct PaddedMatrix {
+    rows: usize,
+    cols: usize,
+    cells: Vec<u8>,
+}
+
+impl PaddedMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        PaddedMatrix {
+            rows,
+            cols,
+            cells: vec![0; (rows + 2) * (cols + 2)],
+        }
+    }
+
+    /// Reallocates the cells if the padded matrix does not match a matrix
+    /// of `rows` by `cols` cells.
+    pub fn resize(&mut self, rows: usize, cols: usize) {
+        if (self.rows, self.cols) != (rows, cols) {
+            *self = PaddedMatrix::new(rows, cols);
+        }
+    }
+
+    fn stride(&self) -> usize {
+        self.cols + 2
+    }
+
+    /// Copies the interior from `matrix` and syncs the ghost border.
+    pub fn load(&mut self, matrix: &Matrix, edge: Edge) {
+        let stride = self.stride();
+
+        for row in 0..self.rows {
+            let cells = &matrix.as_slice()[row * self.cols..(row + 1) * self.cols];
+            let start = (row + 1) * stride + 1;
+            self.cells[start..start + self.cols].copy_from_slice(cells);
+        }
+
+        if self.rows == 0 || self.cols == 0 {
+            return;
+        }
+
+        match edge {
+            Edge::Dead => self.fill_border(0),
+            Edge::Alive => self.fill_border(1),
+            Edge::Wrap => self.sync_border(|cols| (cols, 1)),
+            Edge::Mirror => self.sync_border(|cols| (1, cols)),
+        }
+    }
+
+    /// Copies the interior from `matrix` and fills the ghost border with
+    /// the cells `map` says lie beyond the edges, dead where it says none.
+    pub fn load_mapped<F>(&mut self, matrix: &Matrix, map: F)
+    where
+        F: Fn(isize, isize) -> Option<(usize, usize)>,
+    {
+        self.load(matrix, Edge::Dead);
+        if self.rows == 0 || self.cols == 0 {
+            return;
+        }
+        let stride = self.stride();
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+        let mut fill = |row: isize, col: isize| {
+            if let Some((source_row, source_col)) = map(row, col) {
+                let border = (row + 1) as usize * stride + (col + 1) as usize;
+                self.cells[border] = matrix.get(source_row, source_col);
+            }
+        };
+
+        for col in -1..=cols {
+            fill(-1, col);
+            fill(rows, col);
+        }
+        for row in 0..rows {
+            fill(row, -1);
+            fill(row, cols);
+        }
+    }
+
+    fn fill_border(&mut self, value: u8) {
+        let stride = self.stride();
+        let last = (self.rows + 1) * stride;
+
+        self.cells[..stride].fill(value);
+        self.cells[last..].fill(value);
+        for row in 1..=self.rows {
+            self.cells[row * stride] = value;
+            self.cells[row * stride + self.cols + 1] = value;
+        }
+    }
+
+    /// Copies interior cells into the border. `sources` maps the number of
+    /// columns to the interior columns copied into the left and right ghost
+    /// columns, rows are copied the same way.
+    fn sync_border<F>(&mut self, sources: F)
+    where
+        F: Fn(usize) -> (usize, usize),
+    {
+        let stride = self.stride();
+        let (left, right) = sources(self.cols);
+        let (top, bottom) = sources(self.rows);
+
+        for row in 1..=self.rows {
+            let start = row * stride;
+            self.cells[start] = self.cells[start + left];
+            self.cells[start + self.cols + 1] = self.cells[start + right];
+        }
+
+        // whole rows, so the corners are synced as well
+        self.cells.copy_within(top * stride..(top + 1) * stride, 0);
+        self.cells.copy_within(
+            bottom * stride..(bottom + 1) * stride,
+            (self.rows + 1) * stride,
+        );
+    }
+
+    /// Row `row` of the padded cells, including the ghost cells at both
+    /// ends, so row 0 is the ghost row above the interior.
+    pub fn padded_row(&self, row: usize) -> &[u8] {
+        let stride = self.stride();
+        &self.cells[row * stride..(row + 1) * stride]
+    }
+
+    /// The interior cell at `(row, col)` followed by its neighbors clockwise
+    /// from the north, the order of transitions in Golly rule tables.
+    pub fn neighborhood(&self, row: usize, col: usize) -> [u8; 9] {
+        let stride = self.stride();
+        let center = (row + 1) * stride + col + 1;
+        let above = center - stride;
+        let below = center + stride;
+        let cells = &self.cells;
+
+        [
+            cells[center],
+            cells[above],
+            cells[above + 1],
+            cells[center + 1],
+            cells[below + 1],
+            cells[below],
+            cells[below - 1],
+            cells[center - 1],
+            cells[above - 1],
+        ]
+    }
+
+    /// Next state of the interior cell at `(row, col)` under `rule`.
+    #[inline]
+    pub fn next_state(&self, row: usize, col: usize, rule: &Rule) -> u8 {
+        let stride = self.stride();
+        let center = (row + 1) * stride + col + 1;
+        let above = center - stride;
+        let below = center + stride;
+        let cells = &self.cells;
+
+        let live_count = cells[above - 1]
+            + cells[above]
+            + cells[above + 1]
+            + cells[center - 1]
+            + cells[center + 1]
+            + cells[below - 1]
+            + cells[below]
+            + cells[below + 1];
+
+        rule.next_state(cells[center], live_count)
+    }
+}
@@ -0,0 +1,140 @@
+use crate::matrix::Matrix;
+use crate::plaintext;
+
+/// A well-known pattern in plaintext format, `O` for live and `.` for dead cells.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Pattern {
+    pub name: &'static str,
+    pub cells: &'static str,
+}
+
+impl Pattern {
+    pub fn matrix(&self) -> Matrix {
+        plaintext::parse(self.cells).expect("built-in patterns are valid plaintext")
+    }
+}
+
+pub const PATTERNS: &[Pattern] = &[
+    // still lifes
+    Pattern {
+        name: "block",
+        cells: "OO\nOO",
+    },
+    Pattern {
+        name: "beehive",
+        cells: ".OO.\nO..O\n.OO.",
+    },
+    Pattern {
+        name: "loaf",
+        cells: ".OO.\nO..O\n.O.O\n..O.",
+    },
+    Pattern {
+        name: "boat",
+        cells: "OO.\nO.O\n.O.",
+    },
+    Pattern {
+        name: "tub",
+        cells: ".O.\nO.O\n.O.",
+    },
+    Pattern {
+        name: "ship",
+        cells: "OO.\nO.O\n.OO",
+    },
+    Pattern {
+        name: "pond",
+        cells: ".OO.\nO..O\nO..O\n.OO.",
+    },
+    // oscillators
+    Pattern {
+        name: "blinker",
+        cells: "OOO",
+    },
+    Pattern {
+        name: "toad",
+        cells: ".OOO\nOOO.",
+    },
+    Pattern {
+        name: "beacon",
+        cells: "OO..\nOO..\n..OO\n..OO",
+    },
+    Pattern {
+        name: "pulsar",
+        cells: "\
+..OOO...OOO..
+.............
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO..
+.............
+..OOO...OOO..
+O....O.O....O
+O....O.O....O
+O....O.O....O
+.............
+..OOO...OOO..",
+    },
+    Pattern {
+        name: "pentadecathlon",
+        cells: "..O....O..\nOO.OOOO.OO\n..O....O..",
+    },
+    // spaceships
+    Pattern {
+        name: "glider",
+        cells: ".O.\n..O\nOOO",
+    },
+    Pattern {
+        name: "lwss",
+        cells: ".O..O\nO....\nO...O\nOOOO.",
+    },
+    Pattern {
+        name: "mwss",
+        cells: "...O..\n.O...O\nO.....\nO....O\nOOOOO.",
+    },
+    Pattern {
+        name: "hwss",
+        cells: "...OO..\n.O....O\nO......\nO.....O\nOOOOOO.",
+    },
+    // guns
+    Pattern {
+        name: "gosper-glider-gun",
+        cells: "\
+........................O...........
+......................O.O...........
+............OO......OO............OO
+...........O...O....OO............OO
+OO........O.....O...OO..............
+OO........O...O.OO....O.O...........
+..........O.....O.......O...........
+...........O...O....................
+............OO......................",
+    },
+    // methuselahs
+    Pattern {
+        name: "r-pentomino",
+        cells: ".OO\nOO.\n.O.",
+    },
+    Pattern {
+        name: "diehard",
+        cells: "......O.\nOO......\n.O...OOO",
+    },
+    Pattern {
+        name: "acorn",
+        cells: ".O.....\n...O...\nOO..OOO",
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Pattern> {
+    PATTERNS.iter().find(|pattern| pattern.name == name)
+}
+
+/// Parses a pattern name on the command line.
+pub fn parse(name: &str) -> Result<&'static Pattern, String> {
+    find(name).ok_or_else(|| {
+        let names: Vec<&str> = PATTERNS.iter().map(|pattern| pattern.name).collect();
+        format!(
+            "unknown pattern {name:?}, expected one of {}",
+            names.join(", ")
+        )
+    })
+}
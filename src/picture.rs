@@ -0,0 +1,111 @@
+//! Initial boards drawn from pictures, e.g. logos or text rendered to an
+//! image: the picture is scaled to the board and its dark pixels become
+//! live cells.
+
+use png::{ColorType, Decoder, Transformations};
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use crate::matrix::Matrix;
+
+/// Reads a PNG image as the luminance of its pixels, from 0 for black to 1
+/// for white. Transparent pixels are laid over white.
+pub fn load_luminance<P: AsRef<Path>>(path: P) -> io::Result<Matrix<f32>> {
+    let mut decoder = Decoder::new(File::open(path)?);
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    let (height, width) = (info.height as usize, info.width as usize);
+
+    let channels = match reader.output_color_type().0 {
+        ColorType::Grayscale => 1,
+        ColorType::GrayscaleAlpha => 2,
+        ColorType::Rgb => 3,
+        ColorType::Rgba => 4,
+        // palettes are expanded to RGB by the transformations
+        ColorType::Indexed => unreachable!("indexed colors exception"),
+    };
+    let mut luminance = Matrix::new(height, width);
+    for row in 0..height {
+        let pixels = &buf[row * info.line_size..][..width * channels];
+        for (col, pixel) in pixels.chunks_exact(channels).enumerate() {
+            let channel = |index: usize| f32::from(pixel[index]) / 255.0;
+            let (gray, alpha) = match channels {
+                1 => (channel(0), 1.0),
+                2 => (channel(0), channel(1)),
+                _ => (
+                    0.2126 * channel(0) + 0.7152 * channel(1) + 0.0722 * channel(2),
+                    if channels == 4 { channel(3) } else { 1.0 },
+                ),
+            };
+            luminance.set(row, col, gray * alpha + 1.0 - alpha);
+        }
+    }
+
+    Ok(luminance)
+}
+
+/// The luminance scaled to `rows` by `cols` cells, each the average of the
+/// pixels it covers, or of the nearest pixel when enlarging.
+pub fn scale(luminance: &Matrix<f32>, rows: usize, cols: usize) -> Matrix<f32> {
+    let (height, width) = (luminance.row_count(), luminance.col_count());
+    let mut scaled = Matrix::new(rows, cols);
+    if height == 0 || width == 0 {
+        return scaled;
+    }
+
+    let span = |cell: usize, cells: usize, pixels: usize| {
+        let start = cell * pixels / cells;
+        start..((cell + 1) * pixels / cells).max(start + 1)
+    };
+    for row in 0..rows {
+        let pixel_rows = span(row, rows, height);
+        for col in 0..cols {
+            let pixel_cols = span(col, cols, width);
+            let count = pixel_rows.len() * pixel_cols.len();
+            let sum: f32 = pixel_rows
+                .clone()
+                .flat_map(|pixel_row| pixel_cols.clone().map(move |col| (pixel_row, col)))
+                .map(|(pixel_row, pixel_col)| luminance.get(pixel_row, pixel_col))
+                .sum();
+            scaled.set(row, col, sum / count as f32);
+        }
+    }
+
+    scaled
+}
+
+/// A board with live cells where the luminance is below `threshold`. With
+/// `dither`, the error of every cell is spread over its unvisited neighbors
+/// Floyd-Steinberg style, so gray areas become patterns of matching density.
+pub fn threshold(luminance: &Matrix<f32>, threshold: f32, dither: bool) -> Matrix {
+    let (rows, cols) = (luminance.row_count(), luminance.col_count());
+    let mut values = luminance.clone();
+    let mut board = Matrix::new(rows, cols);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let value = values.get(row, col);
+            let alive = value < threshold;
+            board.set(row, col, u8::from(alive));
+            if !dither {
+                continue;
+            }
+
+            let error = value - if alive { 0.0 } else { 1.0 };
+            for (row_offset, col_offset, weight) in
+                [(0, 1, 7.0), (1, -1, 3.0), (1, 0, 5.0), (1, 1, 1.0)]
+            {
+                let (next_row, next_col) = (row + row_offset, col as isize + col_offset);
+                if next_row < rows && (0..cols as isize).contains(&next_col) {
+                    let next = values.get(next_row, next_col as usize);
+                    values.set(next_row, next_col as usize, next + error * weight / 16.0);
+                }
+            }
+        }
+    }
+
+    board
+}
@@ -0,0 +1,40 @@
+use crate::matrix::Matrix;
+
+/// Parses a pattern in plaintext format: `!` comment lines and one line per
+/// row, `O` for live and `.` for dead cells. Short rows are padded with dead cells.
+pub fn parse(text: &str) -> Result<Matrix, String> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.starts_with('!'))
+        .collect();
+    let cols = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let mut matrix = Matrix::try_new(lines.len(), cols)?;
+
+    for (row, line) in lines.iter().enumerate() {
+        for (col, cell) in line.chars().enumerate() {
+            match cell {
+                'O' | '*' => matrix.set(row, col, 1),
+                '.' => {}
+                cell => return Err(format!("unexpected character {cell:?} in row {row}")),
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Encodes a matrix in plaintext format, without trailing dead cells.
+pub fn encode(matrix: &Matrix) -> String {
+    let mut out = String::new();
+
+    for row in 0..matrix.row_count() {
+        let len = (0..matrix.col_count())
+            .rposition(|col| matrix.get(row, col) != 0)
+            .map_or(0, |col| col + 1);
+        out.extend((0..len).map(|col| if matrix.get(row, col) != 0 { 'O' } else { '.' }));
+        out.push('\n');
+    }
+
+    out
+}
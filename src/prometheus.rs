@@ -0,0 +1,160 @@
+//! Metrics of a long-running simulation or soup search served over HTTP in
+//! the Prometheus text format, e.g. for `--metrics-addr 0.0.0.0:9100`:
+//!
+//! ```text
+//! # HELP gameoflife_generation Number of the current generation
+//! # TYPE gameoflife_generation gauge
+//! gameoflife_generation 1200
+//! ```
+//!
+//! Any path but `/` and `/metrics` is not found.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Gauge,
+    Counter,
+}
+
+#[derive(Debug, Clone)]
+struct Metric {
+    help: &'static str,
+    kind: Kind,
+    /// Labels in the exposition format, e.g. `{engine="serial"}`, or empty.
+    labels: String,
+    value: f64,
+}
+
+/// The latest value of every metric, served by a thread of its own until
+/// the process exits.
+#[derive(Debug, Clone)]
+pub struct Exporter {
+    metrics: Arc<Mutex<BTreeMap<&'static str, Metric>>>,
+    addr: SocketAddr,
+}
+
+impl Exporter {
+    /// Listens on `addr`, port 0 picking a free port.
+    pub fn serve(addr: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let exporter = Exporter {
+            metrics: Arc::default(),
+            addr: listener.local_addr()?,
+        };
+
+        let serving = exporter.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // a scraper hanging up early is no reason to stop serving
+                let _ = serving.respond(stream);
+            }
+        });
+        Ok(exporter)
+    }
+
+    /// The address the metrics are served on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn gauge(&self, name: &'static str, help: &'static str, value: f64) {
+        self.set(name, help, Kind::Gauge, String::new(), value);
+    }
+
+    /// Sets a counter, which only ever goes up, to `value`.
+    pub fn counter(&self, name: &'static str, help: &'static str, value: f64) {
+        self.set(name, help, Kind::Counter, String::new(), value);
+    }
+
+    /// Adds `delta` to a counter.
+    pub fn increment(&self, name: &'static str, help: &'static str, delta: f64) {
+        let mut metrics = self.metrics.lock().expect("metrics lock poisoned");
+        metrics
+            .entry(name)
+            .or_insert(Metric {
+                help,
+                kind: Kind::Counter,
+                labels: String::new(),
+                value: 0.0,
+            })
+            .value += delta;
+    }
+
+    /// A gauge of value 1 describing the simulation by its labels, e.g. the engine.
+    pub fn info(&self, name: &'static str, help: &'static str, labels: &[(&str, &str)]) {
+        let labels = labels
+            .iter()
+            .map(|(label, value)| format!("{label}=\"{}\"", escape(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.set(name, help, Kind::Gauge, format!("{{{labels}}}"), 1.0);
+    }
+
+    fn set(&self, name: &'static str, help: &'static str, kind: Kind, labels: String, value: f64) {
+        let metric = Metric {
+            help,
+            kind,
+            labels,
+            value,
+        };
+        self.metrics
+            .lock()
+            .expect("metrics lock poisoned")
+            .insert(name, metric);
+    }
+
+    /// All metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let metrics = self.metrics.lock().expect("metrics lock poisoned");
+        let mut text = String::new();
+        for (name, metric) in metrics.iter() {
+            let kind = match metric.kind {
+                Kind::Gauge => "gauge",
+                Kind::Counter => "counter",
+            };
+            let _ = writeln!(text, "# HELP {name} {}", metric.help);
+            let _ = writeln!(text, "# TYPE {name} {kind}");
+            let _ = writeln!(text, "{name}{} {}", metric.labels, metric.value);
+        }
+        text
+    }
+
+    /// Answers a single request, ignoring its headers.
+    fn respond(&self, mut stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(&stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // closing with unread headers would reset the connection before the response arrives
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+            header.clear();
+        }
+
+        let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+        let (status, body) = match path {
+            "/" | "/metrics" => ("200 OK", self.render()),
+            _ => ("404 Not Found", String::from("not found\n")),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        stream.flush()
+    }
+}
+
+/// Escapes a label value of the exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
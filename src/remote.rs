@@ -0,0 +1,257 @@
+//! Generations streamed over WebSocket, so that a simulation running on one
+//! machine can be watched from others, with `view --connect` or a browser.
+//!
+//! Every generation is a binary message: the number of rows and columns as
+//! little-endian `u32`s, the tick as a little-endian `u64`, then the cells
+//! row after row, eight to a byte starting from the lowest bit. Cells of any
+//! state but 0 are sent as alive. Viewers connecting mid-run get the latest
+//! generation right away.
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tungstenite::{Message, WebSocket};
+
+use crate::matrix::Matrix;
+use crate::render::terminal::{Screen, TerminalRenderer};
+use crate::render::text::Renderer;
+use crate::render::theme::Theme;
+use crate::render::Frame;
+
+/// Length of the message header: rows, columns and tick.
+const HEADER_LEN: usize = 16;
+
+/// Viewers that take longer than this to shake hands or accept a generation
+/// are dropped, rather than holding up the simulation.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How often the viewer checks for key presses.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lines of the viewer besides the cells: the header, the population and
+/// the key help.
+const CHROME_LINES: usize = 4;
+
+/// A generation as a binary message.
+pub fn encode(matrix: &Matrix, tick: u64) -> Vec<u8> {
+    let cells = matrix.as_slice();
+    let mut message = Vec::with_capacity(HEADER_LEN + cells.len().div_ceil(8));
+    message.extend_from_slice(&(matrix.row_count() as u32).to_le_bytes());
+    message.extend_from_slice(&(matrix.col_count() as u32).to_le_bytes());
+    message.extend_from_slice(&tick.to_le_bytes());
+    message.extend(cells.chunks(8).map(|chunk| {
+        chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (bit, &cell)| byte | u8::from(cell != 0) << bit)
+    }));
+    message
+}
+
+/// The board and tick of a binary message.
+pub fn decode(message: &[u8]) -> Result<(Matrix, u64), String> {
+    let invalid = || format!("invalid generation message of {} bytes", message.len());
+    let header = message.get(..HEADER_LEN).ok_or_else(invalid)?;
+    let rows = u32::from_le_bytes(header[0..4].try_into().expect("4 bytes")) as usize;
+    let cols = u32::from_le_bytes(header[4..8].try_into().expect("4 bytes")) as usize;
+    let tick = u64::from_le_bytes(header[8..16].try_into().expect("8 bytes"));
+
+    let bits = &message[HEADER_LEN..];
+    let size = rows.checked_mul(cols).ok_or_else(invalid)?;
+    if bits.len() != size.div_ceil(8) {
+        return Err(invalid());
+    }
+    let cells = (0..size)
+        .map(|idx| (bits[idx / 8] >> (idx % 8)) & 1)
+        .collect();
+    let matrix = Matrix::from_vec(rows, cols, cells).ok_or_else(invalid)?;
+    Ok((matrix, tick))
+}
+
+/// Parses the address to serve on, e.g. `ws://0.0.0.0:9000` or `0.0.0.0:9000`.
+pub fn parse_serve_addr(s: &str) -> Result<SocketAddr, String> {
+    s.strip_prefix("ws://")
+        .unwrap_or(s)
+        .trim_end_matches('/')
+        .parse()
+        .map_err(|_| format!("invalid address {s:?}, expected ws://HOST:PORT"))
+}
+
+/// Sends every generation it is given to all connected viewers.
+#[derive(Clone)]
+pub struct Broadcaster {
+    viewers: Arc<Mutex<Vec<WebSocket<TcpStream>>>>,
+    /// The message of the latest generation, for viewers yet to connect.
+    latest: Arc<Mutex<Option<Vec<u8>>>>,
+    addr: SocketAddr,
+}
+
+impl Broadcaster {
+    /// Accepts viewers on `addr` on a thread of its own, port 0 picking a free port.
+    pub fn serve(addr: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let broadcaster = Broadcaster {
+            viewers: Arc::default(),
+            latest: Arc::default(),
+            addr: listener.local_addr()?,
+        };
+
+        let accepting = broadcaster.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // a client failing the handshake is no reason to stop serving others
+                let _ = accepting.accept(stream);
+            }
+        });
+        Ok(broadcaster)
+    }
+
+    /// The address viewers connect to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Number of viewers connected.
+    pub fn viewers(&self) -> usize {
+        self.viewers.lock().expect("viewers lock poisoned").len()
+    }
+
+    /// Sends `matrix` at `tick` to every viewer, dropping those that hung up.
+    pub fn send(&self, matrix: &Matrix, tick: u64) {
+        let message = encode(matrix, tick);
+        let mut viewers = self.viewers.lock().expect("viewers lock poisoned");
+        viewers.retain_mut(|viewer| viewer.send(Message::Binary(message.clone())).is_ok());
+        *self.latest.lock().expect("latest lock poisoned") = Some(message);
+    }
+
+    fn accept(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(WRITE_TIMEOUT))?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let mut viewer = tungstenite::accept(stream).map_err(io::Error::other)?;
+
+        // holding the viewers while catching up keeps the next generation from overtaking
+        let mut viewers = self.viewers.lock().expect("viewers lock poisoned");
+        let latest = self.latest.lock().expect("latest lock poisoned").clone();
+        if let Some(message) = latest {
+            viewer
+                .send(Message::Binary(message))
+                .map_err(io::Error::other)?;
+        }
+        viewers.push(viewer);
+        Ok(())
+    }
+}
+
+/// Receives the generations served at `url` on a thread of its own. The
+/// channel closes when the server goes away.
+pub fn connect(url: &str) -> Result<mpsc::Receiver<(Matrix, u64)>, String> {
+    let (mut socket, _) = tungstenite::connect(url).map_err(|err| format!("{url}: {err}"))?;
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        while let Ok(message) = socket.read() {
+            let generation = match message {
+                Message::Binary(bytes) => decode(&bytes),
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(generation) = generation else {
+                break;
+            };
+            if sender.send(generation).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(receiver)
+}
+
+/// Shows the generations served at `url` in the terminal, cropped to fit,
+/// until the server goes away or the user quits with `q`, `Esc` or `Ctrl-C`.
+pub fn view(url: &str, renderer: Renderer) -> io::Result<()> {
+    let receiver = connect(url).map_err(io::Error::other)?;
+    let _screen = Screen::enter(false)?;
+    show(url, &receiver, renderer)
+}
+
+fn show(url: &str, receiver: &mpsc::Receiver<(Matrix, u64)>, renderer: Renderer) -> io::Result<()> {
+    let mut terminal_renderer = TerminalRenderer::stdout();
+    let mut latest = None;
+    let mut connected = true;
+    let mut redraw = true;
+
+    loop {
+        if event::poll(POLL_INTERVAL)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(())
+                    }
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    _ => {}
+                },
+                Event::Resize(..) => {
+                    terminal_renderer.invalidate();
+                    redraw = true;
+                }
+                _ => {}
+            }
+        }
+
+        // only the latest of the generations received meanwhile is shown
+        loop {
+            match receiver.try_recv() {
+                Ok(generation) => latest = Some(generation),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    redraw |= connected;
+                    connected = false;
+                    break;
+                }
+            }
+            redraw = true;
+        }
+        if !redraw {
+            continue;
+        }
+        redraw = false;
+
+        let mut frame = Frame::default();
+        match &latest {
+            Some((matrix, tick)) => {
+                let (columns, lines) = terminal::size()?;
+                let (rows, cols) = renderer.fit(
+                    (lines as usize).saturating_sub(CHROME_LINES),
+                    columns as usize,
+                );
+                let view = matrix.crop(
+                    0,
+                    0,
+                    rows.min(matrix.row_count()),
+                    cols.min(matrix.col_count()),
+                );
+                frame.push_text(&format!(
+                    "remote: {url} \nsize: {} x {} ticks: {tick} ",
+                    matrix.row_count(),
+                    matrix.col_count()
+                ));
+                frame
+                    .lines
+                    .extend(renderer.render_glyphs(&view, None, None, Theme::Mono));
+                frame.push_text(&format!("population: {} ", matrix.count_live()));
+            }
+            None => frame.push_text(&format!("remote: {url} \nwaiting for a generation ")),
+        }
+        frame.push_text(if connected {
+            "q quits"
+        } else {
+            "disconnected, q quits"
+        });
+        terminal_renderer.draw(frame)?;
+    }
+}
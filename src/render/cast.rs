@@ -0,0 +1,66 @@
+//! Terminal output recorded in the asciinema v2 format: a JSON header line,
+//! then a `[seconds, "o", text]` line for every frame drawn, so that a run
+//! can be replayed with `asciinema play` or embedded in a web page with the
+//! asciinema player without running the simulation again.
+
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize)]
+struct Header {
+    version: u8,
+    width: u16,
+    height: u16,
+    /// Unix time the recording started at.
+    timestamp: u64,
+}
+
+/// A recording in progress, events are timed from its creation.
+pub struct Cast<W: Write = BufWriter<File>> {
+    out: W,
+    started: Instant,
+}
+
+impl Cast {
+    /// Starts recording a terminal of `width` columns by `height` lines to `path`.
+    pub fn create(path: &Path, width: u16, height: u16) -> io::Result<Self> {
+        Cast::new(BufWriter::new(File::create(path)?), width, height)
+    }
+}
+
+impl<W: Write> Cast<W> {
+    /// Starts recording to `out` by writing the header.
+    pub fn new(mut out: W, width: u16, height: u16) -> io::Result<Self> {
+        let header = Header {
+            version: 2,
+            width,
+            height,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+        };
+        serde_json::to_writer(&mut out, &header)?;
+        writeln!(out)?;
+        Ok(Cast {
+            out,
+            started: Instant::now(),
+        })
+    }
+
+    /// Records `output` written to the terminal now.
+    pub fn record(&mut self, output: &[u8]) -> io::Result<()> {
+        let seconds = self.started.elapsed().as_micros() as f64 / 1e6;
+        let event = (seconds, "o", String::from_utf8_lossy(output));
+        serde_json::to_writer(&mut self.out, &event)?;
+        writeln!(self.out)
+    }
+
+    /// Stops recording, returns where the recording was written to.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
@@ -0,0 +1,186 @@
+use gif::{Encoder, EncodingError, Frame, Repeat};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::Color;
+use crate::matrix::Matrix;
+
+/// Delay between two recorded generations, in hundredths of a second.
+const FRAME_DELAY: u16 = 10;
+
+/// How cells are drawn when a matrix is rasterized.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageStyle {
+    /// Width and height of a single cell in pixels.
+    pub cell_size: usize,
+    pub alive: Color,
+    pub dead: Color,
+    /// Color of the outline of live cells, only drawn in SVG images.
+    pub stroke: Option<Color>,
+    /// Width of the outline of live cells in SVG units, a cell being `cell_size` wide.
+    pub stroke_width: f64,
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        ImageStyle {
+            cell_size: 8,
+            alive: Color::WHITE,
+            dead: Color::BLACK,
+            stroke: None,
+            stroke_width: 1.0,
+        }
+    }
+}
+
+impl ImageStyle {
+    pub fn width(&self, matrix: &Matrix) -> usize {
+        matrix.col_count() * self.cell_size
+    }
+
+    pub fn height(&self, matrix: &Matrix) -> usize {
+        matrix.row_count() * self.cell_size
+    }
+
+    /// Rasterizes the matrix into one byte per pixel, 0 for dead and 1 for alive cells.
+    pub fn indexed_pixels(&self, matrix: &Matrix) -> Vec<u8> {
+        let width = self.width(matrix);
+        let mut pixels = Vec::with_capacity(width * self.height(matrix));
+
+        for row in 0..matrix.row_count() {
+            let start = pixels.len();
+            for col in 0..matrix.col_count() {
+                let index = u8::from(matrix.get(row, col) != 0);
+                pixels.extend(std::iter::repeat_n(index, self.cell_size));
+            }
+            for _ in 1..self.cell_size {
+                pixels.extend_from_within(start..start + width);
+            }
+        }
+
+        pixels
+    }
+
+    /// Rasterizes the matrix into packed 8-bit RGB pixels.
+    pub fn rgb_pixels(&self, matrix: &Matrix) -> Vec<u8> {
+        self.indexed_pixels(matrix)
+            .into_iter()
+            .flat_map(|index| {
+                let color = if index == 0 { self.dead } else { self.alive };
+                [color.r, color.g, color.b]
+            })
+            .collect()
+    }
+
+    fn palette(&self) -> [u8; 6] {
+        [
+            self.dead.r,
+            self.dead.g,
+            self.dead.b,
+            self.alive.r,
+            self.alive.g,
+            self.alive.b,
+        ]
+    }
+}
+
+/// Writes a single generation as an RGB PNG image.
+pub fn write_png<W: Write>(
+    writer: W,
+    matrix: &Matrix,
+    style: &ImageStyle,
+) -> Result<(), png::EncodingError> {
+    let mut encoder = png::Encoder::new(
+        writer,
+        style.width(matrix) as u32,
+        style.height(matrix) as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&style.rgb_pixels(matrix))?;
+    writer.finish()
+}
+
+pub fn save_png<P: AsRef<Path>>(
+    path: P,
+    matrix: &Matrix,
+    style: &ImageStyle,
+) -> Result<(), png::EncodingError> {
+    let writer = BufWriter::new(File::create(path)?);
+    write_png(writer, matrix, style)
+}
+
+/// Writes successive generations as frames of an endlessly looping GIF.
+pub struct GifRecorder<W: Write> {
+    encoder: Encoder<W>,
+    style: ImageStyle,
+    frames_left: usize,
+}
+
+impl GifRecorder<BufWriter<File>> {
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        matrix: &Matrix,
+        style: ImageStyle,
+        frames: usize,
+    ) -> Result<Self, EncodingError> {
+        let writer = BufWriter::new(File::create(path)?);
+        Self::new(writer, matrix, style, frames)
+    }
+}
+
+impl<W: Write> GifRecorder<W> {
+    pub fn new(
+        writer: W,
+        matrix: &Matrix,
+        style: ImageStyle,
+        frames: usize,
+    ) -> Result<Self, EncodingError> {
+        let width = dimension(style.width(matrix))?;
+        let height = dimension(style.height(matrix))?;
+
+        let mut encoder = Encoder::new(writer, width, height, &style.palette())?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        Ok(GifRecorder {
+            encoder,
+            style,
+            frames_left: frames,
+        })
+    }
+
+    /// Appends the matrix as the next frame, unless all frames were already recorded.
+    pub fn record(&mut self, matrix: &Matrix) -> Result<(), EncodingError> {
+        if self.is_done() {
+            return Ok(());
+        }
+
+        let width = dimension(self.style.width(matrix))?;
+        let height = dimension(self.style.height(matrix))?;
+        let pixels = self.style.indexed_pixels(matrix);
+
+        let mut frame = Frame::from_indexed_pixels(width, height, &pixels, None);
+        frame.delay = FRAME_DELAY;
+        self.encoder.write_frame(&frame)?;
+
+        self.frames_left -= 1;
+        Ok(())
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.frames_left == 0
+    }
+}
+
+fn dimension(pixels: usize) -> Result<u16, EncodingError> {
+    u16::try_from(pixels).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "image too large for GIF, reduce the cell size",
+        )
+        .into()
+    })
+}
@@ -0,0 +1,123 @@
+#[cfg(feature = "native")]
+pub mod cast;
+pub mod image;
+#[cfg(feature = "native")]
+pub mod pipeline;
+pub mod svg;
+#[cfg(feature = "native")]
+pub mod terminal;
+pub mod text;
+pub mod theme;
+pub mod video;
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A character drawn in the terminal, colors are indexes into the ANSI 256-color palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    pub ch: char,
+    pub fg: Option<u8>,
+    pub bg: Option<u8>,
+}
+
+impl Glyph {
+    pub const fn plain(ch: char) -> Self {
+        Glyph {
+            ch,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    pub const fn colored(ch: char, fg: Option<u8>, bg: Option<u8>) -> Self {
+        Glyph { ch, fg, bg }
+    }
+
+    /// Replaces the character by `ch` when the glyph has no colors, so that
+    /// glyphs relying on two colors still render without them.
+    pub fn filled_if_plain(self, ch: char) -> Self {
+        if self.fg.is_none() && self.bg.is_none() {
+            Glyph::plain(ch)
+        } else {
+            self
+        }
+    }
+}
+
+/// A screen of glyphs, one line per row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub lines: Vec<Vec<Glyph>>,
+}
+
+impl Frame {
+    /// Appends uncolored text, one line per line of `text`.
+    pub fn push_text(&mut self, text: &str) {
+        self.lines.extend(
+            text.lines()
+                .map(|line| line.chars().map(Glyph::plain).collect()),
+        );
+    }
+
+    /// Number of glyphs on the longest line.
+    pub fn width(&self) -> usize {
+        self.lines.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Puts `other` to the right of this frame, `gap` blank columns apart,
+    /// shorter lines padded so that `other` lines up.
+    pub fn beside(mut self, other: Frame, gap: usize) -> Frame {
+        let column = self.width() + gap;
+        let lines = self.lines.len().max(other.lines.len());
+        self.lines.resize(lines, Vec::new());
+        for (line, right) in self.lines.iter_mut().zip(other.lines) {
+            line.resize(column, Glyph::plain(' '));
+            line.extend(right);
+        }
+        self
+    }
+}
+
+/// An RGB color, parsed from a hex string such as `#ff8800` or `ff8800`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(format!(
+                "invalid color {s:?}, expected a hex value like #ff8800"
+            ));
+        }
+
+        let channel = |range| {
+            u8::from_str_radix(&hex[range], 16)
+                .map_err(|_| format!("invalid color {s:?}, expected a hex value like #ff8800"))
+        };
+
+        Ok(Color::new(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
@@ -0,0 +1,121 @@
+//! Frames drawn on a thread of their own, so that a slow terminal holds back
+//! the drawing but not the simulation. Frames are handed over through a
+//! channel of one, and the frames the simulation offers while the terminal
+//! is still busy with an earlier one are dropped without being built.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use super::terminal::TerminalRenderer;
+use super::Frame;
+
+/// A frame to draw, from scratch if set.
+type Message = (Frame, bool);
+
+/// A `TerminalRenderer` drawing on its own thread until finished or dropped.
+pub struct RenderThread<W: Write + Send + 'static> {
+    sender: Option<SyncSender<Message>>,
+    thread: Option<JoinHandle<io::Result<TerminalRenderer<W>>>>,
+    /// Number of frames waiting or being drawn.
+    pending: Arc<AtomicUsize>,
+    /// Whether the next frame is drawn from scratch.
+    invalidated: bool,
+    dropped: usize,
+}
+
+impl<W: Write + Send + 'static> RenderThread<W> {
+    pub fn spawn(mut renderer: TerminalRenderer<W>) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let pending = Arc::new(AtomicUsize::new(0));
+        let drawing = Arc::clone(&pending);
+        let thread = thread::spawn(move || {
+            for (frame, from_scratch) in receiver {
+                if from_scratch {
+                    renderer.invalidate();
+                }
+                let drawn = renderer.draw(frame);
+                drawing.fetch_sub(1, Ordering::Release);
+                drawn?;
+            }
+            Ok(renderer)
+        });
+
+        RenderThread {
+            sender: Some(sender),
+            thread: Some(thread),
+            pending,
+            invalidated: false,
+            dropped: 0,
+        }
+    }
+
+    /// Whether the terminal is done with the frames handed over, so that
+    /// the next one is drawn right away. If not, the frame that was up next
+    /// counts as dropped, to be left out rather than built and waited for.
+    pub fn ready(&mut self) -> bool {
+        let ready = self.pending.load(Ordering::Acquire) == 0;
+        if !ready {
+            self.dropped += 1;
+        }
+        ready
+    }
+
+    /// Hands `frame` over to be drawn, waiting for room in the channel.
+    pub fn draw(&mut self, frame: Frame) -> io::Result<()> {
+        self.pending.fetch_add(1, Ordering::Release);
+        let from_scratch = std::mem::take(&mut self.invalidated);
+        self.send((frame, from_scratch))
+    }
+
+    /// Forces the next frame to be drawn from scratch.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Number of frames dropped so far, as `ready` found the terminal busy.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Waits for the frames handed over to be drawn, then returns the
+    /// renderer, or the error that stopped the drawing.
+    pub fn finish(mut self) -> io::Result<TerminalRenderer<W>> {
+        self.join()
+    }
+
+    fn send(&mut self, message: Message) -> io::Result<()> {
+        let sent = match &self.sender {
+            Some(sender) => sender.send(message).is_ok(),
+            None => false,
+        };
+        match sent {
+            true => Ok(()),
+            // the thread only hangs up after failing to draw
+            false => Err(self
+                .join()
+                .err()
+                .unwrap_or_else(|| io::Error::other("the render thread is gone"))),
+        }
+    }
+
+    fn join(&mut self) -> io::Result<TerminalRenderer<W>> {
+        self.sender = None;
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => Err(io::Error::other("the render thread is gone")),
+        }
+    }
+}
+
+impl<W: Write + Send + 'static> Drop for RenderThread<W> {
+    /// Lets the frames handed over be drawn before the terminal is restored.
+    fn drop(&mut self) {
+        if self.thread.is_some() && !thread::panicking() {
+            let _ = self.join();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Boards drawn as SVG, which scale without pixelation, for papers and slides.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::image::ImageStyle;
+use crate::matrix::Matrix;
+
+/// Writes a single generation as an SVG image, one square of `cell_size`
+/// units per live cell on a background of the dead color. Without a stroke,
+/// runs of live cells in a row are drawn as a single rectangle.
+pub fn write_svg<W: Write>(mut writer: W, matrix: &Matrix, style: &ImageStyle) -> io::Result<()> {
+    let (width, height) = (style.width(matrix), style.height(matrix));
+    let size = style.cell_size;
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+        style.dead
+    )?;
+
+    match style.stroke {
+        Some(stroke) => writeln!(
+            writer,
+            r#"<g fill="{}" stroke="{stroke}" stroke-width="{}">"#,
+            style.alive, style.stroke_width
+        )?,
+        None => writeln!(writer, r#"<g fill="{}">"#, style.alive)?,
+    }
+    for row in 0..matrix.row_count() {
+        let mut col = 0;
+        while col < matrix.col_count() {
+            if matrix.get(row, col) == 0 {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            col += 1;
+            if style.stroke.is_none() {
+                while col < matrix.col_count() && matrix.get(row, col) != 0 {
+                    col += 1;
+                }
+            }
+            writeln!(
+                writer,
+                r#"<rect x="{}" y="{}" width="{}" height="{size}"/>"#,
+                start * size,
+                row * size,
+                (col - start) * size
+            )?;
+        }
+    }
+    writeln!(writer, "</g>")?;
+    writeln!(writer, "</svg>")?;
+    writer.flush()
+}
+
+pub fn save_svg<P: AsRef<Path>>(path: P, matrix: &Matrix, style: &ImageStyle) -> io::Result<()> {
+    let writer = BufWriter::new(File::create(path)?);
+    write_svg(writer, matrix, style)
+}
@@ -0,0 +1,268 @@
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::io::{self, Stdout, Write};
+use std::panic;
+use std::sync::Once;
+
+use super::cast::Cast;
+use super::{Frame, Glyph};
+
+/// The terminal taken over for drawing frames: in raw mode, on the alternate
+/// screen with the cursor hidden and, if asked for, with mouse capture. All of
+/// it is restored when dropped, also on errors and panics, leaving the
+/// scrollback as it was. Legacy Windows consoles without escape codes have no
+/// alternate screen, they are cleared instead.
+pub struct Screen {
+    mouse: bool,
+}
+
+/// Whether the terminal understands ANSI escape codes, which on Windows
+/// turns on their processing by the console first. Consoles of Windows
+/// before 10 don't, they are driven through the console API instead.
+pub fn supports_ansi() -> bool {
+    #[cfg(windows)]
+    return crossterm::ansi_support::supports_ansi();
+    #[cfg(not(windows))]
+    return true;
+}
+
+/// The closest of the 16 colors of legacy Windows consoles to `index` in the
+/// ANSI 256-color palette.
+pub fn console_color(index: u8) -> Color {
+    const COLORS: [Color; 16] = [
+        Color::Black,
+        Color::DarkRed,
+        Color::DarkGreen,
+        Color::DarkYellow,
+        Color::DarkBlue,
+        Color::DarkMagenta,
+        Color::DarkCyan,
+        Color::Grey,
+        Color::DarkGrey,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+    let basic = match index {
+        0..=15 => index,
+        // the 6x6x6 color cube: the components at least half as strong as the
+        // strongest make up the hue, bright if the strongest is
+        16..=231 => {
+            let (r, g, b) = ((index - 16) / 36, (index - 16) / 6 % 6, (index - 16) % 6);
+            let max = r.max(g).max(b);
+            match max {
+                0 => 0,
+                _ => {
+                    let hue = (r * 2 >= max) as u8
+                        | ((g * 2 >= max) as u8) << 1
+                        | ((b * 2 >= max) as u8) << 2;
+                    if max >= 4 {
+                        hue + 8
+                    } else {
+                        hue
+                    }
+                }
+            }
+        }
+        // the grayscale ramp from black to white
+        232..=255 => match index - 232 {
+            0..=5 => 0,
+            6..=11 => 8,
+            12..=19 => 7,
+            _ => 15,
+        },
+    };
+    COLORS[basic as usize]
+}
+
+impl Screen {
+    pub fn enter(mouse: bool) -> io::Result<Self> {
+        // a panic message printed on the alternate screen would vanish with it
+        static RESTORE_ON_PANIC: Once = Once::new();
+        RESTORE_ON_PANIC.call_once(|| {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                restore(true);
+                hook(info);
+            }));
+        });
+
+        terminal::enable_raw_mode()?;
+        match supports_ansi() {
+            true => execute!(io::stdout(), EnterAlternateScreen, Hide)?,
+            false => execute!(io::stdout(), Clear(ClearType::All), Hide)?,
+        }
+        if mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        Ok(Screen { mouse })
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        restore(self.mouse);
+    }
+}
+
+/// Gives the terminal back to the shell, ignoring errors as there is no one
+/// left to report them to.
+fn restore(mouse: bool) {
+    if mouse {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+    }
+    let _ = match supports_ansi() {
+        true => execute!(io::stdout(), Show, LeaveAlternateScreen),
+        false => execute!(io::stdout(), Clear(ClearType::All), MoveTo(0, 0), Show),
+    };
+    let _ = terminal::disable_raw_mode();
+}
+
+/// Draws frames in place, rewriting only the glyphs that changed since the
+/// previous frame instead of clearing the whole screen.
+pub struct TerminalRenderer<W: Write> {
+    out: W,
+    /// Whether the frames are drawn with escape codes, rather than through
+    /// the console API of a legacy Windows console.
+    ansi: bool,
+    /// The output of the frame being drawn, written out at once.
+    buffer: Vec<u8>,
+    /// The frame currently on screen, `None` before the first draw.
+    previous: Option<Frame>,
+    cast: Option<Cast>,
+}
+
+impl TerminalRenderer<Stdout> {
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> TerminalRenderer<W> {
+    pub fn new(out: W) -> Self {
+        TerminalRenderer {
+            out,
+            ansi: supports_ansi(),
+            buffer: Vec::new(),
+            previous: None,
+            cast: None,
+        }
+    }
+
+    /// Records every frame drawn from now on to `cast`. Legacy Windows
+    /// consoles draw without escape codes, so nothing is recorded there.
+    pub fn record(&mut self, cast: Cast) {
+        self.cast = Some(cast);
+    }
+
+    /// Draws `frame` and leaves the cursor on the line below it.
+    pub fn draw(&mut self, frame: Frame) -> io::Result<()> {
+        self.buffer.clear();
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => {
+                queue!(&mut self.target(), Clear(ClearType::All))?;
+                Frame::default()
+            }
+        };
+
+        for (row, line) in frame.lines.iter().enumerate() {
+            let old_line = previous
+                .lines
+                .get(row)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            self.draw_line(row, line, old_line)?;
+        }
+
+        // clear lines left over from a longer previous frame
+        for row in frame.lines.len()..previous.lines.len() {
+            queue!(
+                &mut self.target(),
+                MoveTo(0, row as u16),
+                Clear(ClearType::CurrentLine)
+            )?;
+        }
+
+        queue!(&mut self.target(), MoveTo(0, frame.lines.len() as u16))?;
+        self.out.write_all(&self.buffer)?;
+        self.out.flush()?;
+        if let Some(cast) = self.cast.as_mut().filter(|_| self.ansi) {
+            cast.record(&self.buffer)?;
+        }
+
+        self.previous = Some(frame);
+        Ok(())
+    }
+
+    /// Forces the next frame to be drawn from scratch.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Stops drawing, returns where the frames were drawn to.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Where the commands of a frame are queued: the buffer, unless the
+    /// console API carries them out as they are queued, which keeps them in
+    /// order only if they go straight to the console.
+    fn target(&mut self) -> &mut dyn Write {
+        match self.ansi {
+            true => &mut self.buffer,
+            false => &mut self.out,
+        }
+    }
+
+    fn draw_line(&mut self, row: usize, line: &[Glyph], old_line: &[Glyph]) -> io::Result<()> {
+        let mut col = 0;
+
+        while col < line.len() {
+            if old_line.get(col) == Some(&line[col]) {
+                col += 1;
+                continue;
+            }
+
+            // rewrite the whole run of changed glyphs at once
+            queue!(&mut self.target(), MoveTo(col as u16, row as u16))?;
+            while col < line.len() && old_line.get(col) != Some(&line[col]) {
+                self.draw_glyph(line[col])?;
+                col += 1;
+            }
+            queue!(&mut self.target(), ResetColor)?;
+        }
+
+        if old_line.len() > line.len() {
+            queue!(
+                &mut self.target(),
+                MoveTo(line.len() as u16, row as u16),
+                Clear(ClearType::UntilNewLine)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_glyph(&mut self, glyph: Glyph) -> io::Result<()> {
+        let ansi = self.ansi;
+        let color = |index: Option<u8>| match (index, ansi) {
+            (None, _) => Color::Reset,
+            (Some(index), true) => Color::AnsiValue(index),
+            (Some(index), false) => console_color(index),
+        };
+        queue!(
+            &mut self.target(),
+            SetForegroundColor(color(glyph.fg)),
+            SetBackgroundColor(color(glyph.bg)),
+            Print(glyph.ch)
+        )
+    }
+}
@@ -0,0 +1,215 @@
+use clap::ValueEnum;
+
+use super::theme::Theme;
+use super::Glyph;
+use crate::matrix::Matrix;
+
+/// How cells are drawn as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Renderer {
+    /// One character per cell, `x` for live and `.` for dead cells
+    #[default]
+    Ascii,
+    /// Two vertically stacked cells per character using half-block characters
+    #[value(name = "halfblock")]
+    HalfBlock,
+    /// Blocks of 4x2 cells per character using Braille patterns
+    Braille,
+    /// One character per cell on a background colored by how often the cell
+    /// has been alive, from dark red to white
+    Heatmap,
+}
+
+/// 256-color palette indices of the heatmap, from rarely to most often alive.
+const HEAT_COLORS: [u8; 12] = [52, 88, 124, 160, 196, 202, 208, 214, 220, 226, 228, 231];
+
+impl Renderer {
+    /// Number of rows and columns of cells drawn by a single character.
+    pub fn cells_per_char(self) -> (usize, usize) {
+        match self {
+            Renderer::Ascii | Renderer::Heatmap => (1, 1),
+            Renderer::HalfBlock => (2, 1),
+            Renderer::Braille => (4, 2),
+        }
+    }
+
+    /// Number of characters taken up on a line by the cells of one
+    /// character, ascii and heatmap cells are followed by a space.
+    fn chars_per_glyph(self) -> usize {
+        match self {
+            Renderer::Ascii | Renderer::Heatmap => 2,
+            _ => 1,
+        }
+    }
+
+    /// Number of rows and columns of cells that fit in a region of `lines` by
+    /// `columns` characters.
+    pub fn fit(self, lines: usize, columns: usize) -> (usize, usize) {
+        let (rows_per_char, cols_per_char) = self.cells_per_char();
+        (
+            lines * rows_per_char,
+            columns / self.chars_per_glyph() * cols_per_char,
+        )
+    }
+
+    /// Line and glyph index of the character drawing the cell at `row`, `col`.
+    pub fn glyph_position(self, row: usize, col: usize) -> (usize, usize) {
+        let (rows_per_char, cols_per_char) = self.cells_per_char();
+        (
+            row / rows_per_char,
+            col / cols_per_char * self.chars_per_glyph(),
+        )
+    }
+
+    /// Row and column of the top-left cell drawn by the character at `line`, `column`.
+    pub fn cell_position(self, line: usize, column: usize) -> (usize, usize) {
+        let (rows_per_char, cols_per_char) = self.cells_per_char();
+        (
+            line * rows_per_char,
+            column / self.chars_per_glyph() * cols_per_char,
+        )
+    }
+
+    /// Draws the matrix without colors, every line ends with a newline.
+    pub fn render(self, matrix: &Matrix) -> String {
+        let mut out = String::new();
+
+        for line in self.render_glyphs(matrix, None, None, Theme::Mono) {
+            out.extend(line.iter().map(|glyph| glyph.ch));
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Draws the matrix as lines of glyphs. With `ages`, live cells are
+    /// colored by the number of generations they have been alive. The
+    /// heatmap colors cells by their `activity` instead, the number of
+    /// generations they have ever been alive.
+    pub fn render_glyphs(
+        self,
+        matrix: &Matrix,
+        ages: Option<&Matrix>,
+        activity: Option<&Matrix<u32>>,
+        theme: Theme,
+    ) -> Vec<Vec<Glyph>> {
+        let color = |row: usize, col: usize| match ages {
+            Some(ages) if matrix.get(row, col) != 0 => theme.color(ages.get(row, col)),
+            _ => None,
+        };
+
+        match self {
+            Renderer::Ascii => render_ascii(matrix, color),
+            Renderer::HalfBlock => render_half_blocks(matrix, color),
+            Renderer::Braille => render_braille(matrix, color),
+            Renderer::Heatmap => match activity {
+                Some(activity) => render_heatmap(matrix, activity),
+                None => render_ascii(matrix, |_, _| None),
+            },
+        }
+    }
+}
+
+fn render_ascii<F>(matrix: &Matrix, color: F) -> Vec<Vec<Glyph>>
+where
+    F: Fn(usize, usize) -> Option<u8>,
+{
+    (0..matrix.row_count())
+        .map(|row| {
+            (0..matrix.col_count())
+                .flat_map(|col| {
+                    let ch = if matrix.get(row, col) == 0 { '.' } else { 'x' };
+                    [Glyph::colored(ch, color(row, col), None), Glyph::plain(' ')]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn render_heatmap(matrix: &Matrix, activity: &Matrix<u32>) -> Vec<Vec<Glyph>> {
+    // the colors are spread over the activity of the cells in view
+    let max = activity.as_slice().iter().copied().max().unwrap_or(0);
+
+    (0..matrix.row_count())
+        .map(|row| {
+            (0..matrix.col_count())
+                .flat_map(|col| {
+                    let count = activity.get(row, col);
+                    let bg = (count > 0).then(|| {
+                        let level = (count - 1) as u64 * (HEAT_COLORS.len() - 1) as u64
+                            / (max as u64 - 1).max(1);
+                        HEAT_COLORS[level as usize]
+                    });
+                    let ch = match (matrix.get(row, col) != 0, bg) {
+                        (true, _) => 'x',
+                        (false, Some(_)) => ' ',
+                        (false, None) => '.',
+                    };
+                    [Glyph::colored(ch, None, bg), Glyph::colored(' ', None, bg)]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn render_half_blocks<F>(matrix: &Matrix, color: F) -> Vec<Vec<Glyph>>
+where
+    F: Fn(usize, usize) -> Option<u8>,
+{
+    (0..matrix.row_count())
+        .step_by(2)
+        .map(|row| {
+            (0..matrix.col_count())
+                .map(|col| {
+                    let top = matrix.get(row, col) != 0;
+                    let bottom = row + 1 < matrix.row_count() && matrix.get(row + 1, col) != 0;
+                    match (top, bottom) {
+                        // the foreground colors the upper half, the background the lower half
+                        (true, true) => Glyph::colored('▀', color(row, col), color(row + 1, col))
+                            .filled_if_plain('█'),
+                        (true, false) => Glyph::colored('▀', color(row, col), None),
+                        (false, true) => Glyph::colored('▄', color(row + 1, col), None),
+                        (false, false) => Glyph::plain(' '),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Bit of each dot in a Braille pattern, indexed by row and column within the 4x2 block.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+fn render_braille<F>(matrix: &Matrix, color: F) -> Vec<Vec<Glyph>>
+where
+    F: Fn(usize, usize) -> Option<u8>,
+{
+    (0..matrix.row_count())
+        .step_by(4)
+        .map(|row| {
+            (0..matrix.col_count())
+                .step_by(2)
+                .map(|col| {
+                    let mut dots = 0;
+                    // a character has a single color, taken from its first live cell
+                    let mut fg = None;
+
+                    for (row_offset, bits) in BRAILLE_DOTS.iter().enumerate() {
+                        for (col_offset, bit) in bits.iter().enumerate() {
+                            let (row, col) = (row + row_offset, col + col_offset);
+                            if row < matrix.row_count()
+                                && col < matrix.col_count()
+                                && matrix.get(row, col) != 0
+                            {
+                                dots |= bit;
+                                fg = fg.or(color(row, col));
+                            }
+                        }
+                    }
+
+                    Glyph::colored(char::from_u32(0x2800 + dots).unwrap(), fg, None)
+                })
+                .collect()
+        })
+        .collect()
+}
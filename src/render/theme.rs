@@ -0,0 +1,47 @@
+use clap::ValueEnum;
+
+/// Colors of live cells by age, as indexes into the ANSI 256-color palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Theme {
+    /// No colors
+    #[default]
+    Mono,
+    /// Newborn cells are yellow and cool down to dark red
+    Fire,
+    /// Newborn cells are pale cyan and darken to deep blue
+    Ocean,
+    /// Newborn cells are light green and darken to deep green
+    Forest,
+    /// Cells are red, blue, green or yellow by species, see --species
+    Species,
+}
+
+impl Theme {
+    fn palette(self) -> &'static [u8] {
+        match self {
+            Theme::Mono => &[],
+            Theme::Fire => &[226, 220, 214, 208, 202, 196, 160, 124, 88],
+            Theme::Ocean => &[195, 159, 123, 87, 51, 45, 39, 33, 27, 21],
+            Theme::Forest => &[193, 156, 120, 84, 48, 46, 40, 34, 28, 22],
+            Theme::Species => &[196, 33, 46, 226],
+        }
+    }
+
+    pub fn is_colored(self) -> bool {
+        self != Theme::Mono
+    }
+
+    /// Whether cells are colored by age rather than by their state.
+    pub fn colors_by_age(self) -> bool {
+        self.is_colored() && self != Theme::Species
+    }
+
+    /// Color of a cell that has been alive for `age` generations, or of
+    /// species `age` for the species theme, cells older than the palette
+    /// share its last color.
+    pub fn color(self, age: u8) -> Option<u8> {
+        let palette = self.palette();
+        let last = palette.len().checked_sub(1)?;
+        Some(palette[usize::from(age.saturating_sub(1)).min(last)])
+    }
+}
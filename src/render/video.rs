@@ -0,0 +1,112 @@
+//! Generations recorded as a video by piping raw RGB frames into `ffmpeg`,
+//! which keeps long runs of large boards far smaller than a GIF.
+
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+use super::image::ImageStyle;
+use crate::matrix::Matrix;
+
+/// Writes successive generations as frames of a video encoded by a child
+/// process reading raw RGB frames on its standard input.
+pub struct VideoRecorder {
+    child: Child,
+    /// Closed when the recording is finished, which ends the encoding.
+    stdin: Option<BufWriter<ChildStdin>>,
+    style: ImageStyle,
+    width: usize,
+    height: usize,
+}
+
+impl VideoRecorder {
+    /// Starts `ffmpeg` encoding a video of boards the size of `matrix` to
+    /// `path`, in the format its extension names, e.g. `.mp4` or `.webm`.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        matrix: &Matrix,
+        style: ImageStyle,
+        fps: u32,
+    ) -> io::Result<Self> {
+        let (width, height) = (style.width(matrix), style.height(matrix));
+        let mut command = Command::new("ffmpeg");
+        command
+            .args(["-y", "-loglevel", "error"])
+            .args(["-f", "rawvideo", "-pixel_format", "rgb24"])
+            .args(["-video_size", &format!("{width}x{height}")])
+            .args(["-framerate", &fps.to_string(), "-i", "-"])
+            // most players only take even dimensions in the usual pixel format
+            .args([
+                "-vf",
+                "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+                "-pix_fmt",
+                "yuv420p",
+            ])
+            .arg(path.as_ref());
+
+        Self::spawn(command, matrix, style).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => io::Error::new(
+                io::ErrorKind::NotFound,
+                "ffmpeg is needed to record videos, install it or put it on the PATH",
+            ),
+            _ => err,
+        })
+    }
+
+    /// Starts `command` and pipes it frames of boards the size of `matrix`.
+    pub fn spawn(mut command: Command, matrix: &Matrix, style: ImageStyle) -> io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().map(BufWriter::new);
+
+        Ok(VideoRecorder {
+            child,
+            stdin,
+            style,
+            width: style.width(matrix),
+            height: style.height(matrix),
+        })
+    }
+
+    /// Appends the matrix as the next frame.
+    pub fn record(&mut self, matrix: &Matrix) -> io::Result<()> {
+        if (self.style.width(matrix), self.style.height(matrix)) != (self.width, self.height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames of a video must all be the same size",
+            ));
+        }
+        match &mut self.stdin {
+            Some(stdin) => stdin.write_all(&self.style.rgb_pixels(matrix)),
+            None => Ok(()),
+        }
+    }
+
+    /// Closes the stream of frames and waits for the encoding to complete.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.close()
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        let Some(mut stdin) = self.stdin.take() else {
+            return Ok(());
+        };
+        stdin.flush()?;
+        drop(stdin);
+
+        let status = self.child.wait()?;
+        match status.success() {
+            true => Ok(()),
+            false => Err(io::Error::other(format!("video encoder failed: {status}"))),
+        }
+    }
+}
+
+/// A recording still in progress is finished when the game goes away.
+impl Drop for VideoRecorder {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
@@ -0,0 +1,159 @@
+//! Sessions recorded as the state they started from and every change made
+//! to the board between generations, the cells edited and the rules
+//! switched to, so that they can be played back exactly, e.g. to share what
+//! happened at generation 5000. A replay file holds the starting state as a
+//! checkpoint on a single line of JSON, followed by the changes as events in
+//! the syntax of scenario files, the generation followed by what happens:
+//!
+//! ```text
+//! # replay of a session, play it back with --replay
+//! {"version":2,"ticks":0,"seed":42,...}
+//! 120 toggle 5,5
+//! 120 toggle 5,6
+//! 300 rule B36/S23
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::checkpoint::Checkpoint;
+use crate::rule::Rule;
+use crate::scenario::Scenario;
+
+/// Comment line at the top of replay files.
+const REPLAY_HEADER: &str = "# replay of a session, play it back with --replay";
+
+/// A change made to the board between two generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// Flipped the cell at `(row, col)` relative to the top-left cell of the
+    /// initial board.
+    Toggle { row: isize, col: isize },
+    /// Switched to another rule, keeping the edge mode.
+    Rule(Rule),
+}
+
+/// A recorded session: the state it started from and the changes to play
+/// at their generations from there.
+pub struct Replay {
+    pub start: Checkpoint,
+    pub scenario: Scenario,
+}
+
+impl Replay {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        let start = lines
+            .by_ref()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .ok_or("missing starting state")?;
+        let start =
+            Checkpoint::parse(start).map_err(|err| format!("invalid starting state: {err}"))?;
+        let scenario = Scenario::parse(&lines.collect::<Vec<_>>().join("\n"))?;
+        Ok(Replay { start, scenario })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Records the changes made to a game, following it back and forth through
+/// its history, and writes the replay when dropped.
+pub struct ReplayRecorder {
+    out: BufWriter<File>,
+    start: Checkpoint,
+    /// Changes of the generations up to the current one, in order.
+    edits: Vec<(usize, Edit)>,
+    /// Changes of rewound generations, the latest first, which are replayed
+    /// when stepping forward through them again.
+    undone: Vec<(usize, Edit)>,
+}
+
+impl ReplayRecorder {
+    /// Starts recording a session starting from `start` to `path`.
+    pub fn create<P: AsRef<Path>>(path: P, start: Checkpoint) -> io::Result<Self> {
+        Ok(ReplayRecorder {
+            out: BufWriter::new(File::create(path)?),
+            start,
+            edits: Vec::new(),
+            undone: Vec::new(),
+        })
+    }
+
+    /// Records a change made at generation `tick`.
+    pub fn record(&mut self, tick: usize, edit: Edit) {
+        self.edits.push((tick, edit));
+    }
+
+    /// Starts over from `start`, e.g. after loading a checkpoint, which the
+    /// earlier changes do not lead to.
+    pub fn restart(&mut self, start: Checkpoint) {
+        self.start = start;
+        self.edits.clear();
+        self.undone.clear();
+    }
+
+    /// Puts aside the changes of the generations after `tick`, which the
+    /// game stepped back to.
+    pub fn rewind(&mut self, tick: usize) {
+        while let Some(&(edit_tick, _)) = self.edits.last() {
+            if edit_tick <= tick {
+                break;
+            }
+            self.undone.extend(self.edits.pop());
+        }
+    }
+
+    /// Takes back the changes put aside up to generation `tick`, which the
+    /// game stepped forward to again.
+    pub fn forward(&mut self, tick: usize) {
+        while let Some(&(edit_tick, _)) = self.undone.last() {
+            if edit_tick > tick {
+                break;
+            }
+            self.edits.extend(self.undone.pop());
+        }
+    }
+
+    /// Drops the changes put aside, once the game moved on differently from
+    /// generation `tick` it was rewound to. Stepping back keeps the rule, so
+    /// the latest rule put aside is recorded again at `tick`.
+    pub fn discard_undone(&mut self, tick: usize) {
+        let rule = self.undone.iter().find_map(|(_, edit)| match edit {
+            Edit::Rule(rule) => Some(*rule),
+            Edit::Toggle { .. } => None,
+        });
+        self.undone.clear();
+        if let Some(rule) = rule {
+            self.record(tick, Edit::Rule(rule));
+        }
+    }
+
+    /// Writes the replay of the generations up to the current one.
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{REPLAY_HEADER}")?;
+        serde_json::to_writer(&mut out, &self.start)?;
+        writeln!(out)?;
+        for (tick, edit) in &self.edits {
+            match edit {
+                Edit::Toggle { row, col } => writeln!(out, "{tick} toggle {row},{col}")?,
+                Edit::Rule(rule) => writeln!(out, "{tick} rule {rule}")?,
+            }
+        }
+        out.flush()
+    }
+}
+
+/// The replay is complete when the game goes away.
+impl Drop for ReplayRecorder {
+    fn drop(&mut self) {
+        let mut out = Vec::new();
+        if self.write(&mut out).is_ok() {
+            let _ = self.out.write_all(&out).and_then(|()| self.out.flush());
+        }
+    }
+}
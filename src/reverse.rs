@@ -0,0 +1,257 @@
+//! Predecessors of small patterns, found by backtracking over the cells of
+//! the previous generation the way a SAT solver would, without learning:
+//! cells are set one at a time, and the search backs up as soon as a cell
+//! of the pattern can no longer come out right whatever the cells left
+//! unset turn out to be. A pattern that has no predecessor is a Garden of
+//! Eden, or orphan, that only appears in an initial state.
+
+use clap::Args;
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::edge::Edge;
+use crate::matrix::Matrix;
+use crate::padded::PaddedMatrix;
+use crate::patterns::{self, Pattern};
+use crate::plaintext;
+use crate::rule::Rule;
+
+/// Mask of the neighbor counts 0 to 8.
+const ALL_COUNTS: u16 = (1 << 9) - 1;
+
+#[derive(Args, Debug)]
+pub struct ReverseArgs {
+    /// Board file of the pattern to find a predecessor of, - for stdin, its dead cells included
+    #[arg(required_unless_present = "pattern")]
+    input: Option<PathBuf>,
+    /// Built-in pattern to find a predecessor of instead of a board file
+    #[arg(long, value_parser = patterns::parse, conflicts_with = "input")]
+    pattern: Option<&'static Pattern>,
+    /// Life-like rule in B/S notation the predecessor evolves by
+    #[arg(long, default_value_t = Rule::CONWAY)]
+    rule: Rule,
+    /// Number of cells to set at most before giving up
+    #[arg(long, default_value_t = 100_000_000)]
+    max_steps: u64,
+    /// Board file to write the predecessor to, in the format its name suggests, - for stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// What became of a predecessor search.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// A predecessor, one cell larger than the pattern on every side.
+    Found(Matrix),
+    /// The pattern has no predecessor, it is a Garden of Eden.
+    GardenOfEden,
+    /// The search ran out of steps before deciding either way.
+    GaveUp,
+}
+
+/// The outcome of a search and the number of cells it set.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Search {
+    pub outcome: Outcome,
+    pub steps: u64,
+}
+
+impl Display for Search {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            Outcome::Found(_) => write!(f, "predecessor found after {} steps", self.steps),
+            Outcome::GardenOfEden => write!(
+                f,
+                "no predecessor: a Garden of Eden, proved in {} steps",
+                self.steps
+            ),
+            Outcome::GaveUp => write!(f, "gave up after {} steps", self.steps),
+        }
+    }
+}
+
+/// Searches a board one cell larger than `target` on every side whose
+/// next generation under `rule` is `target`, setting at most `max_steps`
+/// cells. Only the cells of `target` are matched, the cells around it are
+/// free, so a pattern without predecessor has none whatever surrounds it.
+pub fn predecessor(target: &Matrix, rule: &Rule, max_steps: u64) -> Search {
+    let mut search = Searcher::new(target, rule);
+    let outcome = search.run(max_steps);
+    Search {
+        outcome,
+        steps: search.steps,
+    }
+}
+
+/// The cells of `target` in the next generation of `predecessor`, a board
+/// one cell larger than `target` on every side, under `rule`.
+pub fn successor(predecessor: &Matrix, rule: &Rule) -> Matrix {
+    let (rows, cols) = (predecessor.row_count(), predecessor.col_count());
+    let mut padded = PaddedMatrix::new(rows, cols);
+    padded.load(predecessor, Edge::Dead);
+
+    let mut next = Matrix::new(rows.saturating_sub(2), cols.saturating_sub(2));
+    for row in 0..next.row_count() {
+        for col in 0..next.col_count() {
+            next.set(row, col, padded.next_state(row + 1, col + 1, rule));
+        }
+    }
+    next
+}
+
+/// Looks for a predecessor of the pattern in `args` and prints it, failing
+/// if there is none or the search gave up.
+pub fn run(args: &ReverseArgs) -> ExitCode {
+    let target = match (&args.input, &args.pattern) {
+        (Some(path), _) => Matrix::load_for_rule(path, &args.rule).expect("board exception"),
+        (None, Some(pattern)) => pattern.matrix(),
+        (None, None) => unreachable!("clap requires an input or pattern"),
+    };
+
+    let search = predecessor(&target, &args.rule, args.max_steps);
+    match &search.outcome {
+        Outcome::Found(predecessor) => {
+            match &args.out {
+                Some(path) => predecessor
+                    .save_with_rule(path, &args.rule)
+                    .expect("io exception"),
+                None => print!("{}", plaintext::encode(predecessor)),
+            }
+            eprintln!("{search}");
+            ExitCode::SUCCESS
+        }
+        _ => {
+            println!("{search}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The state of a search: the cells of the predecessor row after row, the
+/// first `set` of them decided.
+struct Searcher<'a> {
+    target: &'a Matrix,
+    rule: &'a Rule,
+    rows: usize,
+    cols: usize,
+    cells: Vec<u8>,
+    set: usize,
+    steps: u64,
+}
+
+impl<'a> Searcher<'a> {
+    fn new(target: &'a Matrix, rule: &'a Rule) -> Self {
+        let (rows, cols) = (target.row_count() + 2, target.col_count() + 2);
+        Searcher {
+            target,
+            rule,
+            rows,
+            cols,
+            cells: vec![0; rows * cols],
+            set: 0,
+            steps: 0,
+        }
+    }
+
+    fn run(&mut self, max_steps: u64) -> Outcome {
+        // whether the value the cell was set to first has been swapped for
+        // the other, for every cell set
+        let mut swapped = Vec::with_capacity(self.cells.len());
+        loop {
+            if self.set == self.cells.len() {
+                let predecessor = Matrix::from_vec(self.rows, self.cols, self.cells.clone())
+                    .expect("the predecessor has its size");
+                return Outcome::Found(predecessor);
+            }
+            if self.steps >= max_steps {
+                return Outcome::GaveUp;
+            }
+
+            // cells settling into the pattern are the likeliest, so every
+            // cell first takes the value of the cell of the pattern it is on
+            let (row, col) = (self.set / self.cols, self.set % self.cols);
+            self.cells[self.set] = self.target_cell(row, col).unwrap_or(0);
+            swapped.push(false);
+            self.set += 1;
+            self.steps += 1;
+
+            while !self.consistent(row_col(self.set - 1, self.cols)) {
+                // back up to the latest cell with a value left to try
+                while swapped.last() == Some(&true) {
+                    swapped.pop();
+                    self.set -= 1;
+                }
+                let Some(last) = swapped.last_mut() else {
+                    return Outcome::GardenOfEden;
+                };
+                *last = true;
+                self.cells[self.set - 1] ^= 1;
+                self.steps += 1;
+            }
+        }
+    }
+
+    /// The cell of the pattern under cell `(row, col)` of the predecessor,
+    /// `None` on the border around it.
+    fn target_cell(&self, row: usize, col: usize) -> Option<u8> {
+        let (row, col) = (row.checked_sub(1)?, col.checked_sub(1)?);
+        (row < self.target.row_count() && col < self.target.col_count())
+            .then(|| u8::from(self.target.get(row, col) != 0))
+    }
+
+    /// Whether every cell of the pattern next to the latest cell set can
+    /// still come out right.
+    fn consistent(&self, (row, col): (usize, usize)) -> bool {
+        let rows = row.saturating_sub(1)..(row + 2).min(self.rows);
+        rows.flat_map(|row| {
+            let cols = col.saturating_sub(1)..(col + 2).min(self.cols);
+            cols.map(move |col| (row, col))
+        })
+        .all(|(row, col)| match self.target_cell(row, col) {
+            Some(wanted) => self.possible(row, col, wanted),
+            None => true,
+        })
+    }
+
+    /// Whether cell `(row, col)` can become `wanted` given the cells set in
+    /// its neighborhood, whatever the others turn out to be.
+    fn possible(&self, row: usize, col: usize, wanted: u8) -> bool {
+        let (mut live, mut unset) = (0, 0);
+        for near_row in row - 1..=row + 1 {
+            for near_col in col - 1..=col + 1 {
+                if (near_row, near_col) == (row, col) {
+                    continue;
+                }
+                let idx = near_row * self.cols + near_col;
+                match idx < self.set {
+                    true => live += self.cells[idx],
+                    false => unset += 1,
+                }
+            }
+        }
+        // the neighbor counts still reachable
+        let counts = (ALL_COUNTS >> (8 - unset)) << live;
+
+        let idx = row * self.cols + col;
+        let centers: &[u8] = match idx < self.set {
+            true => &self.cells[idx..=idx],
+            false => &[0, 1],
+        };
+        centers.iter().any(|&alive| {
+            let becomes_alive = match alive {
+                0 => self.rule.birth_mask(),
+                _ => self.rule.survival_mask(),
+            };
+            let wanted_counts = match wanted {
+                0 => !becomes_alive & ALL_COUNTS,
+                _ => becomes_alive,
+            };
+            wanted_counts & counts != 0
+        })
+    }
+}
+
+fn row_col(idx: usize, cols: usize) -> (usize, usize) {
+    (idx / cols, idx % cols)
+}
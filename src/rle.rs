@@ -0,0 +1,198 @@
+use std::fmt::Write;
+
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// Longest line written in the body of an RLE file.
+const LINE_WIDTH: usize = 70;
+
+/// Parses a pattern in run length encoded format: `#` comment lines, an
+/// `x = <cols>, y = <rows>` header and runs of `b` (dead) and `o` (alive)
+/// cells, with `$` ending a row and `!` ending the pattern. Callers running
+/// the pattern under a rule should check it against `parse_with_rule`.
+pub fn parse(text: &str) -> Result<Matrix, String> {
+    parse_with_rule(text).map(|(matrix, _)| matrix)
+}
+
+/// Parses a pattern like `parse`, along with the rule its header names, if
+/// any. Rules are in B/S notation such as `B36/S23` or in S/B notation such
+/// as `23/36`, other rules are rejected.
+pub fn parse_with_rule(text: &str) -> Result<(Matrix, Option<Rule>), String> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let header = lines.next().ok_or("missing RLE header")?;
+    let (rows, cols, rule) = parse_header(header)?;
+    let mut matrix = Matrix::try_new(rows, cols)?;
+
+    let (mut row, mut col): (usize, usize) = (0, 0);
+    let mut count = String::new();
+
+    'body: for line in lines {
+        for ch in line.chars() {
+            if ch.is_ascii_digit() {
+                count.push(ch);
+                continue;
+            }
+
+            let run = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .map_err(|_| format!("invalid run length {count:?}"))?
+            };
+            count.clear();
+            // runs too long to count can only go beyond the pattern
+            let beyond = || format!("cells beyond the {cols}x{rows} pattern");
+
+            match ch {
+                'b' | '.' => col = col.checked_add(run).ok_or_else(beyond)?,
+                '$' => {
+                    row = row.checked_add(run).ok_or_else(beyond)?;
+                    col = 0;
+                }
+                '!' => break 'body,
+                ch if ch.is_whitespace() => {}
+                // any other letter is a live cell of some state
+                ch if ch.is_ascii_alphabetic() => {
+                    let end = col.checked_add(run).filter(|&end| end <= cols);
+                    let end = match end {
+                        Some(end) if row < rows => end,
+                        _ => return Err(beyond()),
+                    };
+                    for col in col..end {
+                        matrix.set(row, col, 1);
+                    }
+                    col = end;
+                }
+                ch => return Err(format!("unexpected character {ch:?}")),
+            }
+        }
+    }
+
+    Ok((matrix, rule))
+}
+
+/// Reads the width, height and rule from a header such as
+/// `x = 3, y = 3, rule = B3/S23`.
+fn parse_header(header: &str) -> Result<(usize, usize, Option<Rule>), String> {
+    let mut rows = None;
+    let mut cols = None;
+    let mut rule = None;
+
+    // the rule comes last, followed by the bounded grid after a colon in Golly
+    let header = header.split_once(':').map_or(header, |(header, _)| header);
+    for field in header.split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| format!("invalid RLE header {header:?}"))?;
+        let parse = || {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("invalid RLE header {header:?}"))
+        };
+
+        match key.trim() {
+            "x" => cols = Some(parse()?),
+            "y" => rows = Some(parse()?),
+            "rule" => rule = Some(parse_rule(value.trim())?),
+            _ => {}
+        }
+    }
+
+    match (rows, cols) {
+        (Some(rows), Some(cols)) => Ok((rows, cols, rule)),
+        _ => Err(format!("RLE header {header:?} lacks x or y")),
+    }
+}
+
+/// Parses the rule of an RLE header in B/S or S/B notation.
+fn parse_rule(rule: &str) -> Result<Rule, String> {
+    let digits = |part: &str| part.chars().all(|ch| ch.is_ascii_digit());
+    match rule.split_once('/') {
+        // S/B notation names the survivals first, without letters
+        Some((survival, birth)) if digits(survival) && digits(birth) => {
+            format!("B{birth}/S{survival}").parse()
+        }
+        _ => rule.parse(),
+    }
+    .map_err(|_| format!("unsupported rule {rule:?} in RLE header, expected B/S notation"))
+}
+
+/// Encodes a matrix in run length encoded format, naming Conway's rule in
+/// the header.
+pub fn encode(matrix: &Matrix) -> String {
+    encode_with_rule(matrix, &Rule::CONWAY)
+}
+
+/// Encodes a matrix in run length encoded format, naming `rule` in the
+/// header.
+pub fn encode_with_rule(matrix: &Matrix, rule: &Rule) -> String {
+    let mut tokens = Vec::new();
+    let mut pending_rows = 0;
+
+    for row in 0..matrix.row_count() {
+        let mut runs = Vec::new();
+        let mut col = 0;
+        while col < matrix.col_count() {
+            let alive = matrix.get(row, col) != 0;
+            let start = col;
+            while col < matrix.col_count() && (matrix.get(row, col) != 0) == alive {
+                col += 1;
+            }
+            runs.push((col - start, alive));
+        }
+
+        // trailing dead cells are implied by the end of the row
+        if let Some((_, false)) = runs.last() {
+            runs.pop();
+        }
+
+        if !runs.is_empty() {
+            if pending_rows > 0 {
+                tokens.push(run_token(pending_rows, '$'));
+            }
+            pending_rows = 0;
+            tokens.extend(
+                runs.into_iter()
+                    .map(|(len, alive)| run_token(len, if alive { 'o' } else { 'b' })),
+            );
+        }
+        pending_rows += 1;
+    }
+    tokens.push("!".to_string());
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "x = {}, y = {}, rule = {rule}",
+        matrix.col_count(),
+        matrix.row_count()
+    )
+    .unwrap();
+
+    let mut width = 0;
+    for token in tokens {
+        if width + token.len() > LINE_WIDTH {
+            out.push('\n');
+            width = 0;
+        }
+        width += token.len();
+        out.push_str(&token);
+    }
+    out.push('\n');
+
+    out
+}
+
+fn run_token(len: usize, tag: char) -> String {
+    if len == 1 {
+        tag.to_string()
+    } else {
+        format!("{len}{tag}")
+    }
+}
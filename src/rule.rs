@@ -0,0 +1,137 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::str::FromStr;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use crate::edge::Edge;
+
+/// A Life-like rule: the neighbor counts at which dead cells are born and
+/// live cells survive, along with how cells beyond the board boundaries are
+/// treated, like the bounded grid suffix of a Golly rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    /// Bit `n` of the first mask is set if dead cells with `n` live
+    /// neighbors are born, bit `n` of the second if live cells survive.
+    masks: [u16; 2],
+    pub edge: Edge,
+}
+
+impl Rule {
+    /// Conway's Game of Life, B3/S23, with dead cells beyond the boundaries.
+    pub const CONWAY: Rule = Rule {
+        masks: [1 << 3, 1 << 2 | 1 << 3],
+        edge: Edge::Dead,
+    };
+
+    /// A rule from the neighbor counts at which cells are born and survive.
+    /// Counts above 8 are ignored.
+    pub fn new(birth: &[u8], survival: &[u8], edge: Edge) -> Self {
+        let mask = |counts: &[u8]| {
+            counts
+                .iter()
+                .filter(|&&count| count <= 8)
+                .fold(0, |mask, &count| mask | 1 << count)
+        };
+
+        Rule {
+            masks: [mask(birth), mask(survival)],
+            edge,
+        }
+    }
+
+    pub fn with_edge(self, edge: Edge) -> Self {
+        Rule { edge, ..self }
+    }
+
+    /// Whether births and survivals follow B3/S23, whatever the edge mode.
+    pub fn is_conway(&self) -> bool {
+        self.masks == Rule::CONWAY.masks
+    }
+
+    /// Bit `n` is set if dead cells with `n` live neighbors are born.
+    pub fn birth_mask(&self) -> u16 {
+        self.masks[0]
+    }
+
+    /// Bit `n` is set if live cells with `n` live neighbors survive.
+    pub fn survival_mask(&self) -> u16 {
+        self.masks[1]
+    }
+
+    /// Next state of a cell that is `alive` (0 or 1) with `live_count` live neighbors.
+    #[inline]
+    pub fn next_state(&self, alive: u8, live_count: u8) -> u8 {
+        ((self.masks[usize::from(alive & 1)] >> live_count) & 1) as u8
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::CONWAY
+    }
+}
+
+/// Parses a rule in B/S notation such as `B3/S23` or `b36/s23`, with dead edges.
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid rule {s:?}, expected B/S notation such as B3/S23");
+        let (birth, survival) = s.split_once('/').ok_or_else(invalid)?;
+        let counts = |part: &str, prefix: char| -> Result<Vec<u8>, String> {
+            let digits = part
+                .strip_prefix(prefix)
+                .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(invalid)?;
+            digits
+                .chars()
+                .map(|digit| match digit.to_digit(10) {
+                    Some(count) if count <= 8 => Ok(count as u8),
+                    _ => Err(invalid()),
+                })
+                .collect()
+        };
+
+        Ok(Rule::new(
+            &counts(birth.trim(), 'B')?,
+            &counts(survival.trim(), 'S')?,
+            Edge::Dead,
+        ))
+    }
+}
+
+/// Formats the births and survivals in B/S notation, without the edge mode.
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = |mask: u16| -> String {
+            (0..=8)
+                .filter(|count| mask & 1 << count != 0)
+                .map(|count| char::from(b'0' + count as u8))
+                .collect()
+        };
+
+        write!(f, "B{}/S{}", counts(self.masks[0]), counts(self.masks[1]))
+    }
+}
+
+impl Serialize for Rule {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,571 @@
+//! Cellular automata defined by Golly `.rule` files, beyond Life-like
+//! rules: any number of states and transitions that may depend on where
+//! neighbors are rather than how many there are. A rule file holds an
+//! `@TABLE` of transitions or an `@TREE` deciding the next state one
+//! neighbor at a time, other sections such as `@COLORS` are ignored:
+//!
+//! ```text
+//! @RULE Southward
+//! @TABLE
+//! n_states:2
+//! neighborhood:vonNeumann
+//! symmetries:none
+//! var a={0,1}
+//! var b={a}
+//! var c={a}
+//! var d={a}
+//! # every cell takes the state of its northern neighbor
+//! a,0,b,c,d,0
+//! a,1,b,c,d,1
+//! ```
+//!
+//! Cells hold their state, 0 being the background state, and cells not
+//! matched by any transition keep their state.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::padded::PaddedMatrix;
+use crate::rule::Rule;
+
+/// Positions of the von Neumann neighbors in a Moore neighborhood, the
+/// cell followed by its neighbors clockwise from the north.
+const VON_NEUMANN: [usize; 5] = [0, 1, 3, 5, 7];
+
+/// Order in which a tree looks at the neighborhood, by position in a Moore
+/// neighborhood: north-west, north-east, south-west, south-east, north,
+/// west, east, south and the cell itself.
+const TREE_ORDER_MOORE: [usize; 9] = [8, 2, 6, 4, 1, 7, 3, 5, 0];
+/// The same for von Neumann neighborhoods: north, west, east, south and the cell.
+const TREE_ORDER_VON_NEUMANN: [usize; 5] = [1, 7, 3, 5, 0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The eight surrounding cells.
+    Moore,
+    /// The four orthogonally adjacent cells.
+    VonNeumann,
+}
+
+impl Neighborhood {
+    fn neighbors(self) -> usize {
+        match self {
+            Neighborhood::Moore => 8,
+            Neighborhood::VonNeumann => 4,
+        }
+    }
+}
+
+/// The next state of a cell from its neighborhood.
+#[derive(Debug, Clone)]
+enum Lookup {
+    Table(Table),
+    Tree(Tree),
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleTable {
+    name: String,
+    states: u8,
+    neighborhood: Neighborhood,
+    lookup: Lookup,
+}
+
+impl RuleTable {
+    /// Parses a rule file with an `@TABLE` or `@TREE` section.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut name = String::new();
+        let mut section = None;
+        let mut table = Vec::new();
+        let mut tree = Vec::new();
+
+        for (line, text) in text.lines().enumerate() {
+            let text = text.split('#').next().unwrap_or_default().trim();
+            if let Some(header) = text.strip_prefix('@') {
+                let (keyword, rest) = header.split_once(' ').unwrap_or((header, ""));
+                if keyword == "RULE" {
+                    name = rest.trim().to_string();
+                }
+                section = Some(keyword.to_string());
+            } else if !text.is_empty() {
+                match section.as_deref() {
+                    Some("TABLE") => table.push((line + 1, text)),
+                    Some("TREE") => tree.push((line + 1, text)),
+                    _ => {}
+                }
+            }
+        }
+
+        let (states, neighborhood, lookup) = match (table.is_empty(), tree.is_empty()) {
+            (false, _) => {
+                let table = Table::parse(&table)?;
+                (table.states, table.neighborhood, Lookup::Table(table))
+            }
+            (true, false) => {
+                let tree = Tree::parse(&tree)?;
+                (tree.states, tree.neighborhood, Lookup::Tree(tree))
+            }
+            (true, true) => return Err("no @TABLE or @TREE section".to_string()),
+        };
+
+        Ok(RuleTable {
+            name,
+            states,
+            neighborhood,
+            lookup,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {err}", path.display()),
+            )
+        })
+    }
+
+    /// The name given by `@RULE`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of states, cells hold states below it.
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    /// The next state of the first cell of `neighborhood`, which is followed
+    /// by its eight neighbors clockwise from the north. Von Neumann rules
+    /// only look at the orthogonal neighbors.
+    pub fn next_state(&self, neighborhood: &[u8; 9]) -> u8 {
+        match &self.lookup {
+            Lookup::Table(table) => table.next_state(neighborhood),
+            Lookup::Tree(tree) => tree.next_state(neighborhood),
+        }
+    }
+}
+
+/// The cells of a neighborhood the rule looks at, the cell first.
+fn cells_of(neighborhood: Neighborhood, cells: &[u8; 9]) -> Vec<u8> {
+    match neighborhood {
+        Neighborhood::Moore => cells.to_vec(),
+        Neighborhood::VonNeumann => VON_NEUMANN.iter().map(|&idx| cells[idx]).collect(),
+    }
+}
+
+/// A state or a variable in a transition. Every occurrence of a variable
+/// in a transition stands for the same state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    State(u8),
+    Var(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Transition {
+    /// The cell and its neighbors.
+    inputs: Vec<Slot>,
+    output: Slot,
+}
+
+#[derive(Debug, Clone)]
+struct Table {
+    states: u8,
+    neighborhood: Neighborhood,
+    /// Orders of the neighbors a transition also matches in, as indices
+    /// into its neighbors, or none if it matches them in any order.
+    symmetries: Option<Vec<Vec<usize>>>,
+    /// The states of every variable.
+    vars: Vec<Vec<u8>>,
+    transitions: Vec<Transition>,
+}
+
+impl Table {
+    fn parse(lines: &[(usize, &str)]) -> Result<Self, String> {
+        let mut states = None;
+        let mut neighborhood = Neighborhood::Moore;
+        let mut symmetries = "none".to_string();
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let mut vars: Vec<Vec<u8>> = Vec::new();
+        let mut transitions = Vec::new();
+
+        for &(line, text) in lines {
+            let at_line = |err: String| format!("line {line}: {err}");
+
+            if let Some((key, value)) = text.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "n_states" => {
+                        let count: u8 = value
+                            .parse()
+                            .ok()
+                            .filter(|count| (2..=255).contains(count))
+                            .ok_or_else(|| {
+                                at_line(format!("invalid number of states {value:?}"))
+                            })?;
+                        states = Some(count);
+                    }
+                    "neighborhood" => {
+                        neighborhood = match value {
+                            "Moore" => Neighborhood::Moore,
+                            "vonNeumann" => Neighborhood::VonNeumann,
+                            _ => {
+                                return Err(at_line(format!("unsupported neighborhood {value:?}")))
+                            }
+                        }
+                    }
+                    "symmetries" => symmetries = value.to_string(),
+                    key => return Err(at_line(format!("unknown setting {key:?}"))),
+                }
+                continue;
+            }
+
+            let states = states.ok_or_else(|| at_line("n_states must come first".to_string()))?;
+            let state = |token: &str| -> Result<Slot, String> {
+                match token.parse::<u8>() {
+                    Ok(state) if state < states => Ok(Slot::State(state)),
+                    Ok(state) => Err(format!("state {state} out of range")),
+                    Err(_) => names
+                        .get(token)
+                        .map(|&var| Slot::Var(var))
+                        .ok_or_else(|| format!("unknown variable {token:?}")),
+                }
+            };
+
+            if let Some(declaration) = text.strip_prefix("var ") {
+                let (name, values) = declaration
+                    .split_once('=')
+                    .ok_or_else(|| at_line("expected var name={states}".to_string()))?;
+                let values = values
+                    .trim()
+                    .strip_prefix('{')
+                    .and_then(|values| values.strip_suffix('}'))
+                    .ok_or_else(|| at_line("expected var name={states}".to_string()))?;
+
+                let mut members = Vec::new();
+                for token in values.split(',').map(str::trim) {
+                    match state(token).map_err(at_line)? {
+                        Slot::State(state) => members.push(state),
+                        Slot::Var(var) => members.extend(&vars[var]),
+                    }
+                }
+                names.insert(name.trim().to_string(), vars.len());
+                vars.push(members);
+                continue;
+            }
+
+            let tokens: Vec<&str> = if text.contains(',') {
+                text.split(',').map(str::trim).collect()
+            } else {
+                // without commas every character is a state or a variable
+                text.char_indices()
+                    .filter(|(_, ch)| !ch.is_whitespace())
+                    .map(|(idx, ch)| &text[idx..idx + ch.len_utf8()])
+                    .collect()
+            };
+            if tokens.len() != neighborhood.neighbors() + 2 {
+                return Err(at_line(format!(
+                    "expected {} states in a transition, got {}",
+                    neighborhood.neighbors() + 2,
+                    tokens.len()
+                )));
+            }
+
+            let slots = tokens
+                .iter()
+                .map(|token| state(token))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(at_line)?;
+            let (output, inputs) = slots.split_last().expect("transitions are not empty");
+            if let Slot::Var(var) = output {
+                if !inputs.contains(output) && vars[*var].len() > 1 {
+                    return Err(at_line("the new state is an unbound variable".to_string()));
+                }
+            }
+            transitions.push(Transition {
+                inputs: inputs.to_vec(),
+                output: *output,
+            });
+        }
+
+        let states = states.ok_or_else(|| "missing n_states".to_string())?;
+        let symmetries = symmetry_orders(neighborhood, &symmetries)?;
+        Ok(Table {
+            states,
+            neighborhood,
+            symmetries,
+            vars,
+            transitions,
+        })
+    }
+
+    /// The state of the first transition matching the neighborhood in any
+    /// of the symmetric orders.
+    fn next_state(&self, cells: &[u8; 9]) -> u8 {
+        let cells = cells_of(self.neighborhood, cells);
+        let (center, neighbors) = (cells[0], &cells[1..]);
+
+        for transition in &self.transitions {
+            let mut bound = vec![None; self.vars.len()];
+            if !self.bind(transition.inputs[0], center, &mut bound) {
+                continue;
+            }
+
+            let matched = match &self.symmetries {
+                Some(orders) => orders.iter().find_map(|order| {
+                    let mut bound = bound.clone();
+                    order
+                        .iter()
+                        .zip(neighbors)
+                        .all(|(&slot, &state)| {
+                            self.bind(transition.inputs[1 + slot], state, &mut bound)
+                        })
+                        .then_some(bound)
+                }),
+                None => {
+                    let mut used = vec![false; neighbors.len()];
+                    self.permuted(&transition.inputs[1..], neighbors, &mut used, bound)
+                }
+            };
+
+            if let Some(bound) = matched {
+                return match transition.output {
+                    Slot::State(state) => state,
+                    Slot::Var(var) => bound[var].unwrap_or(self.vars[var][0]),
+                };
+            }
+        }
+
+        center
+    }
+
+    /// Whether `slot` matches `state`, binding the variable it may be.
+    fn bind(&self, slot: Slot, state: u8, bound: &mut [Option<u8>]) -> bool {
+        match slot {
+            Slot::State(expected) => expected == state,
+            Slot::Var(var) => match bound[var] {
+                Some(value) => value == state,
+                None if self.vars[var].contains(&state) => {
+                    bound[var] = Some(state);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Matches the neighbors with the slots in any order, trying every
+    /// unused slot for the first neighbor and the rest recursively.
+    fn permuted(
+        &self,
+        slots: &[Slot],
+        neighbors: &[u8],
+        used: &mut [bool],
+        bound: Vec<Option<u8>>,
+    ) -> Option<Vec<Option<u8>>> {
+        let Some((&state, rest)) = neighbors.split_first() else {
+            return Some(bound);
+        };
+
+        for idx in 0..slots.len() {
+            if used[idx] {
+                continue;
+            }
+            let mut bound = bound.clone();
+            if self.bind(slots[idx], state, &mut bound) {
+                used[idx] = true;
+                let matched = self.permuted(slots, rest, used, bound);
+                used[idx] = false;
+                if matched.is_some() {
+                    return matched;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The orders of the neighbors equivalent under `symmetries`, none for
+/// `permute`. Neighbors are numbered clockwise from the north.
+fn symmetry_orders(
+    neighborhood: Neighborhood,
+    symmetries: &str,
+) -> Result<Option<Vec<Vec<usize>>>, String> {
+    let count = neighborhood.neighbors();
+    // a quarter turn moves every neighbor this many places round the cell
+    let quarter = count / 4;
+    let rotated =
+        |turns: usize| -> Vec<usize> { (0..count).map(|idx| (idx + turns) % count).collect() };
+    let reflected = |order: Vec<usize>| -> Vec<usize> {
+        order.into_iter().map(|idx| (count - idx) % count).collect()
+    };
+
+    let rotations = |step: usize| (0..count).step_by(step).map(rotated).collect::<Vec<_>>();
+    let orders = match (symmetries, neighborhood) {
+        ("permute", _) => return Ok(None),
+        ("none", _) => vec![rotated(0)],
+        ("rotate4", _) => rotations(quarter),
+        ("rotate8", Neighborhood::Moore) => rotations(1),
+        ("reflect_horizontal", _) => vec![rotated(0), reflected(rotated(0))],
+        ("rotate4reflect", _) => {
+            let rotations = rotations(quarter);
+            let reflections = rotations.iter().cloned().map(reflected).collect::<Vec<_>>();
+            [rotations, reflections].concat()
+        }
+        ("rotate8reflect", Neighborhood::Moore) => {
+            let rotations = rotations(1);
+            let reflections = rotations.iter().cloned().map(reflected).collect::<Vec<_>>();
+            [rotations, reflections].concat()
+        }
+        _ => return Err(format!("unsupported symmetries {symmetries:?}")),
+    };
+    Ok(Some(orders))
+}
+
+/// A decision tree: every node looks at one cell of the neighborhood and
+/// leads to the node looking at the next one, the nodes of the lowest level
+/// hold the next state.
+#[derive(Debug, Clone)]
+struct Tree {
+    states: u8,
+    neighborhood: Neighborhood,
+    /// Children, or next states for the lowest level, by state.
+    nodes: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    fn parse(lines: &[(usize, &str)]) -> Result<Self, String> {
+        let mut states = None;
+        let mut neighbors = None;
+        let mut nodes: Vec<(usize, Vec<usize>)> = Vec::new();
+
+        for &(line, text) in lines {
+            let at_line = |err: String| format!("line {line}: {err}");
+            let number = |value: &str| {
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| at_line(format!("invalid number {value:?}")))
+            };
+
+            if let Some((key, value)) = text.split_once('=') {
+                match key.trim() {
+                    "num_states" => states = Some(number(value)?),
+                    "num_neighbors" => neighbors = Some(number(value)?),
+                    "num_nodes" => {}
+                    key => return Err(at_line(format!("unknown setting {key:?}"))),
+                }
+                continue;
+            }
+
+            let mut numbers = text.split_whitespace().map(number);
+            let level = numbers.next().expect("lines are not empty")?;
+            let children = numbers.collect::<Result<Vec<_>, _>>()?;
+            nodes.push((level, children));
+        }
+
+        let states = states
+            .filter(|states| (2..=255).contains(states))
+            .ok_or_else(|| "missing or invalid num_states".to_string())?;
+        let neighborhood = match neighbors {
+            Some(8) => Neighborhood::Moore,
+            Some(4) => Neighborhood::VonNeumann,
+            _ => return Err("num_neighbors must be 4 or 8".to_string()),
+        };
+
+        for (idx, (level, children)) in nodes.iter().enumerate() {
+            let valid = children.len() == states
+                && children.iter().all(|&child| match level {
+                    1 => child < states,
+                    level => nodes
+                        .get(child)
+                        .is_some_and(|(child_level, _)| child < idx && child_level + 1 == *level),
+                });
+            if !valid {
+                return Err(format!("invalid node {idx}"));
+            }
+        }
+        match nodes.last() {
+            Some((level, _)) if *level == neighborhood.neighbors() + 1 => {}
+            _ => return Err("the last node must be the root".to_string()),
+        }
+
+        Ok(Tree {
+            states: states as u8,
+            neighborhood,
+            nodes: nodes.into_iter().map(|(_, children)| children).collect(),
+        })
+    }
+
+    fn next_state(&self, cells: &[u8; 9]) -> u8 {
+        let order: &[usize] = match self.neighborhood {
+            Neighborhood::Moore => &TREE_ORDER_MOORE,
+            Neighborhood::VonNeumann => &TREE_ORDER_VON_NEUMANN,
+        };
+
+        let mut node = self.nodes.len() - 1;
+        for &idx in order {
+            // states beyond the rule behave like the background state
+            let state = cells[idx] as usize;
+            node = self.nodes[node]
+                .get(state)
+                .copied()
+                .unwrap_or(self.nodes[node][0]);
+        }
+        node as u8
+    }
+}
+
+/// Ticks a board with a rule table, one cell after another, remembering the
+/// next state of every neighborhood seen. The edge mode of the rule passed
+/// in is kept, its births and survivals are ignored.
+pub struct RuleTableEngine {
+    table: RuleTable,
+    padded: PaddedMatrix,
+    cache: HashMap<[u8; 9], u8>,
+}
+
+impl RuleTableEngine {
+    pub fn new(table: RuleTable) -> Self {
+        RuleTableEngine {
+            table,
+            padded: PaddedMatrix::new(0, 0),
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn table(&self) -> &RuleTable {
+        &self.table
+    }
+}
+
+impl TickEngine for RuleTableEngine {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        self.padded.resize(src.row_count(), src.col_count());
+        self.padded.load(src, rule.edge);
+
+        let cols = src.col_count();
+        for row in 0..src.row_count() {
+            let cells = &mut dst.as_mut_slice()[row * cols..(row + 1) * cols];
+            for (col, value) in cells.iter_mut().enumerate() {
+                let neighborhood = self.padded.neighborhood(row, col);
+                let table = &self.table;
+                *value = *self
+                    .cache
+                    .entry(neighborhood)
+                    .or_insert_with(|| table.next_state(&neighborhood));
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Events scripted to happen at given generations, for reproducible demos
+//! and for setting up interactions between patterns. A scenario file holds
+//! one event per line, the generation it happens at followed by what
+//! happens, with positions relative to the top-left cell of the initial board:
+//!
+//! ```text
+//! # two gliders colliding
+//! 0 place glider 2,2
+//! 0 place glider 2,20 flip-h
+//! 100 clear 0,0 10x10
+//! 150 toggle 5,5
+//! 200 rule B36/S23
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::matrix::Matrix;
+use crate::patterns;
+use crate::rule::Rule;
+
+#[derive(Clone)]
+pub enum Action {
+    /// Copies a pattern onto the board with its top-left corner at `(row, col)`.
+    Place {
+        pattern: Matrix,
+        row: isize,
+        col: isize,
+    },
+    /// Kills the `rows` by `cols` cells with their top-left corner at `(row, col)`.
+    Clear {
+        row: isize,
+        col: isize,
+        rows: usize,
+        cols: usize,
+    },
+    /// Flips the cell at `(row, col)`.
+    Toggle { row: isize, col: isize },
+    /// Switches to another rule, keeping the edge mode.
+    Rule(Rule),
+}
+
+#[derive(Clone)]
+pub struct Event {
+    pub tick: usize,
+    pub action: Action,
+}
+
+/// Events in the order they happen, those of the same generation in the
+/// order they were given.
+#[derive(Clone, Default)]
+pub struct Scenario {
+    events: Vec<Event>,
+}
+
+impl Scenario {
+    /// Parses a scenario: one event per line and `#` comment lines. Events
+    /// are `place <pattern> <row>,<col> [<transform>]` with a built-in
+    /// pattern and one of rotate90, rotate180, rotate270, flip-h or flip-v,
+    /// `clear <row>,<col> <rows>x<cols>`, `toggle <row>,<col>` and `rule <rule>`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut events = text
+            .lines()
+            .enumerate()
+            .map(|(line, text)| (line + 1, text.trim()))
+            .filter(|(_, text)| !text.is_empty() && !text.starts_with('#'))
+            .map(|(line, text)| parse_event(text).map_err(|err| format!("line {line}: {err}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        events.sort_by_key(|event| event.tick);
+
+        Ok(Scenario { events })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// The events happening at generation `tick`.
+    pub fn events_at(&self, tick: usize) -> &[Event] {
+        let start = self.events.partition_point(|event| event.tick < tick);
+        let end = self.events.partition_point(|event| event.tick <= tick);
+        &self.events[start..end]
+    }
+}
+
+fn parse_event(text: &str) -> Result<Event, String> {
+    let mut words = text.split_whitespace();
+    let tick = words.next().unwrap_or_default();
+    let tick = tick
+        .parse()
+        .map_err(|_| format!("invalid generation {tick:?}"))?;
+    let mut arg = |what: &str| words.next().ok_or_else(|| format!("missing {what}"));
+
+    let action = match arg("event")? {
+        "place" => {
+            let name = arg("pattern")?;
+            let pattern = patterns::parse(name)?.matrix();
+            let (row, col) = parse_position(arg("position")?)?;
+            let pattern = match words.next() {
+                None => pattern,
+                Some("rotate90") => pattern.rotate90(),
+                Some("rotate180") => pattern.rotate180(),
+                Some("rotate270") => pattern.rotate270(),
+                Some("flip-h") => pattern.flip_h(),
+                Some("flip-v") => pattern.flip_v(),
+                Some(transform) => return Err(format!("unknown transform {transform:?}")),
+            };
+            Action::Place { pattern, row, col }
+        }
+        "clear" => {
+            let (row, col) = parse_position(arg("position")?)?;
+            let size = arg("size")?;
+            let invalid = || format!("invalid size {size:?}, expected ROWSxCOLS");
+            let (rows, cols) = size.split_once('x').ok_or_else(invalid)?;
+            let rows = rows.parse().map_err(|_| invalid())?;
+            let cols = cols.parse().map_err(|_| invalid())?;
+            Action::Clear {
+                row,
+                col,
+                rows,
+                cols,
+            }
+        }
+        "toggle" => {
+            let (row, col) = parse_position(arg("position")?)?;
+            Action::Toggle { row, col }
+        }
+        "rule" => Action::Rule(arg("rule")?.parse()?),
+        event => return Err(format!("unknown event {event:?}")),
+    };
+
+    match words.next() {
+        Some(extra) => Err(format!("unexpected {extra:?}")),
+        None => Ok(Event { tick, action }),
+    }
+}
+
+/// Parses a `row,col` position, which may lie above or left of the initial board.
+fn parse_position(s: &str) -> Result<(isize, isize), String> {
+    let invalid = || format!("invalid position {s:?}, expected ROW,COL");
+    let (row, col) = s.split_once(',').ok_or_else(invalid)?;
+    let row = row.trim().parse().map_err(|_| invalid())?;
+    let col = col.trim().parse().map_err(|_| invalid())?;
+    Ok((row, col))
+}
@@ -0,0 +1,424 @@
+use clap::Args;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs::{self, File};
+use std::io::BufWriter;
+#[cfg(feature = "metrics")]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::analyze::{self, Census, Haul, GLIDER_APGCODE};
+use crate::game_of_life::{self, GameOfLife};
+use crate::matrix::{Matrix, Overflow};
+use crate::padded::PaddedMatrix;
+#[cfg(feature = "metrics")]
+use crate::prometheus::Exporter;
+use crate::rule::Rule;
+use crate::simulator::Simulator;
+use crate::symmetry::Symmetry;
+
+/// Gliders whose window is at most this many cells away from the boundary are escaping.
+const ESCAPE_DISTANCE: usize = 2;
+
+/// Soups run at once by the search, enough to keep every thread busy.
+const SOUPS_IN_FLIGHT: usize = 256;
+
+#[derive(Args, Debug)]
+pub struct SoupSearchArgs {
+    /// Number of random soups to run
+    #[arg(long, default_value_t = 1000)]
+    soups: u64,
+    /// Seed of the first soup, the following soups use consecutive seeds
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Width and height of each soup
+    #[arg(long, default_value_t = 16)]
+    size: usize,
+    /// Probability of each soup cell being alive
+    #[arg(long, value_parser = game_of_life::parse_probability, default_value_t = 0.5)]
+    fill_prob: f64,
+    /// Symmetry of the soups, which makes rare patterns far more likely
+    #[arg(long, value_enum)]
+    symmetry: Option<Symmetry>,
+    /// Number of generations each soup is run for at most
+    #[arg(long, default_value_t = 2000)]
+    ticks: usize,
+    /// Report soups that stabilize no earlier than this generation
+    #[arg(long, default_value_t = 1000)]
+    min_longevity: usize,
+    /// Report soups whose final population is at least this large
+    #[arg(long)]
+    min_population: Option<usize>,
+    /// Report soups that emit at least this many escaping gliders
+    #[arg(long)]
+    min_gliders: Option<usize>,
+    /// Directory the initial state of every reported soup is written to
+    #[arg(long, default_value = "soups")]
+    out_dir: PathBuf,
+    /// Stop after this many seconds, fractions allowed, dropping the soups still running and reporting how far the search got
+    #[arg(long, value_parser = game_of_life::parse_seconds)]
+    max_seconds: Option<Duration>,
+    /// Take a census of the objects every soup ends with, the gliders it emitted included, and print it in the layout of a Catagolue haul
+    #[arg(long)]
+    catagolue: bool,
+    /// Address to serve the number of soups run and reported on in the Prometheus format, e.g. 0.0.0.0:9100
+    #[cfg(feature = "metrics")]
+    #[arg(long)]
+    metrics_addr: Option<SocketAddr>,
+}
+
+/// Outcome of running a single soup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoupResult {
+    pub seed: u64,
+    /// The generation the soup stabilized at, or the tick budget if it never did.
+    pub longevity: usize,
+    pub population: usize,
+    pub gliders: usize,
+    /// State hash of the live cells of the final board, cropped to their
+    /// bounding box so that soups ending up alike wherever on the board hash
+    /// the same, see `Matrix::state_hash`.
+    pub hash: u64,
+}
+
+impl Display for SoupResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "seed {}: longevity {}, population {}, gliders {}, hash {:016x}",
+            self.seed, self.longevity, self.population, self.gliders, self.hash
+        )
+    }
+}
+
+impl SoupSearchArgs {
+    fn soup(&self, seed: u64) -> Matrix {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut soup = Matrix::random(self.size, self.size, self.fill_prob, &mut rng);
+        if let Some(symmetry) = self.symmetry {
+            symmetry.apply(&mut soup);
+        }
+        soup
+    }
+
+    fn is_interesting(&self, result: &SoupResult) -> bool {
+        result.longevity >= self.min_longevity
+            || self
+                .min_population
+                .is_some_and(|min| result.population >= min)
+            || self.min_gliders.is_some_and(|min| result.gliders >= min)
+    }
+}
+
+/// Runs a soup in an unbounded universe until it stabilizes or the tick budget runs out.
+///
+/// Gliders escaping the soup are counted and removed, otherwise the universe
+/// would keep growing and never repeat.
+pub fn run_soup(soup: Matrix, seed: u64, ticks: usize) -> SoupResult {
+    let (game, gliders) = play_soup(soup, ticks);
+    soup_result(&game, seed, gliders)
+}
+
+/// Runs a soup like `run_soup` and counts the objects it ends with by
+/// apgcode, the gliders it emitted among them.
+pub fn soup_census(soup: Matrix, seed: u64, ticks: usize) -> (SoupResult, Census) {
+    let (game, gliders) = play_soup(soup, ticks);
+    (soup_result(&game, seed, gliders), census(&game, gliders))
+}
+
+/// Runs the soups of `seeds` like `run_soup`, many at once in one
+/// simulator. Every soup that finishes is summed up by `summarize` from its
+/// seed, final game and the number of gliders it emitted, in parallel, and
+/// the summary handed to `finished`, the soups finishing first first. Once
+/// `deadline` passes, the soups still running are dropped and no more are
+/// started. Returns the number of generations run, by all soups.
+pub fn run_soups<S, M, R, F>(
+    seeds: impl IntoIterator<Item = u64>,
+    soup: S,
+    ticks: usize,
+    deadline: Option<Instant>,
+    summarize: M,
+    mut finished: F,
+) -> usize
+where
+    S: Fn(u64) -> Matrix,
+    M: Fn(u64, &GameOfLife, usize) -> R + Sync,
+    R: Send,
+    F: FnMut(R),
+{
+    let mut seeds = seeds.into_iter();
+    let mut simulator = Simulator::new();
+    // the seed and gliders so far of every soup running
+    let mut soups = HashMap::new();
+    let mut generations = 0;
+
+    loop {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return generations;
+        }
+        while simulator.len() < SOUPS_IN_FLIGHT {
+            let Some(seed) = seeds.next() else {
+                break;
+            };
+            let id = simulator.add_universe(soup_game(soup(seed), ticks));
+            soups.insert(id, (seed, 0));
+        }
+        if simulator.is_empty() {
+            return generations;
+        }
+
+        let ticked = simulator.tick_all_then(remove_escaping_gliders);
+        generations += ticked.len();
+        for (id, escaped) in ticked {
+            soups.get_mut(&id).expect("every soup is tracked").1 += escaped;
+        }
+        let done: Vec<_> = simulator
+            .take_finished()
+            .into_iter()
+            .map(|(id, game)| (soups.remove(&id).expect("every soup is tracked"), game))
+            .collect();
+        let summaries: Vec<R> = done
+            .into_par_iter()
+            .map(|((seed, gliders), game)| summarize(seed, &game, gliders))
+            .collect();
+        summaries.into_iter().for_each(&mut finished);
+    }
+}
+
+/// The game a soup ended in and the number of gliders it emitted.
+fn play_soup(soup: Matrix, ticks: usize) -> (GameOfLife, usize) {
+    let mut game = soup_game(soup, ticks);
+    let mut gliders = 0;
+    while !game.is_finished() {
+        game.tick();
+        gliders += remove_escaping_gliders(&mut game);
+    }
+    (game, gliders)
+}
+
+/// The objects `game` ended with by apgcode, and the `gliders` it emitted.
+fn census(game: &GameOfLife, gliders: usize) -> Census {
+    let mut census = Census::apgcodes(&analyze::objects(game.matrix(), game.rule()));
+    census.add(GLIDER_APGCODE, gliders);
+    census
+}
+
+/// A soup in an unbounded universe, run until it stabilizes or for `ticks`
+/// generations.
+fn soup_game(soup: Matrix, ticks: usize) -> GameOfLife {
+    let mut game = GameOfLife::new(soup);
+    game.set_unbounded(true);
+    game.set_stop_on_cycle(true);
+    game.set_max_ticks(Some(ticks));
+    game
+}
+
+/// Removes the gliders escaping the soup of `game`, returns how many.
+fn remove_escaping_gliders(game: &mut GameOfLife) -> usize {
+    let empty = Matrix::new(5, 5);
+    let gliders = escaping_gliders(game.matrix());
+    for &(row, col) in &gliders {
+        game.place_pattern(&empty, row, col, Overflow::Clip);
+    }
+    gliders.len()
+}
+
+fn soup_result(game: &GameOfLife, seed: u64, gliders: usize) -> SoupResult {
+    SoupResult {
+        seed,
+        longevity: game.cycle().map_or(game.ticks(), |cycle| cycle.start),
+        population: game.stats().latest().population,
+        gliders,
+        hash: game.matrix().crop_to_bounding_box().state_hash(),
+    }
+}
+
+/// Runs all soups, many at once, reports the interesting ones in the order
+/// of their seeds and writes their initial states to the output directory,
+/// followed by the census of all soups with `--catagolue`.
+pub fn run(args: &SoupSearchArgs) {
+    fs::create_dir_all(&args.out_dir).expect("io exception");
+    #[cfg(feature = "metrics")]
+    let exporter = args
+        .metrics_addr
+        .map(|addr| Exporter::serve(addr).expect("metrics endpoint exception"));
+
+    // a limit beyond the clock's range never passes
+    let deadline = args
+        .max_seconds
+        .and_then(|limit| Instant::now().checked_add(limit));
+    let mut census = Census::default();
+    let mut results = Vec::new();
+    let mut finished = 0;
+    let summarize = |seed, game: &GameOfLife, gliders| {
+        let soup_census = args.catagolue.then(|| self::census(game, gliders));
+        (soup_result(game, seed, gliders), soup_census)
+    };
+    // the seeds stop at the largest one rather than wrapping around
+    let seeds = args.seed..args.seed.saturating_add(args.soups);
+    let soups = seeds.end - seeds.start;
+    let generations = run_soups(
+        seeds,
+        |seed| args.soup(seed),
+        args.ticks,
+        deadline,
+        summarize,
+        |(result, soup_census)| {
+            finished += 1;
+            if let Some(soup_census) = soup_census {
+                census.merge(&soup_census);
+            }
+            let interesting = args.is_interesting(&result);
+            #[cfg(feature = "metrics")]
+            if let Some(exporter) = &exporter {
+                exporter.increment("gameoflife_soups_total", "Soups run to the end", 1.0);
+                exporter.increment(
+                    "gameoflife_soups_reported_total",
+                    "Soups found interesting",
+                    f64::from(u8::from(interesting)),
+                );
+            }
+            if interesting {
+                results.push(result);
+            }
+        },
+    );
+    results.sort_by_key(|result| result.seed);
+
+    for result in &results {
+        println!("{result}");
+
+        let path = args.out_dir.join(format!("soup-{}.json", result.seed));
+        let file = File::create(path).expect("io exception");
+        serde_json::to_writer(BufWriter::new(file), &args.soup(result.seed))
+            .expect("json encode exception");
+    }
+
+    println!("{} of {} soups reported", results.len(), finished);
+    // only the time limit cuts a search short
+    if let Some(limit) = args.max_seconds.filter(|_| finished < soups) {
+        println!(
+            "stopped: reached max seconds ({}) after {finished} of {soups} soups and {generations} generations",
+            limit.as_secs_f64(),
+        );
+    }
+
+    if args.catagolue {
+        let symmetry = catagolue_symmetry(args.symmetry, args.size);
+        let haul = Haul {
+            rule: &Rule::CONWAY,
+            symmetry: Some(&symmetry),
+            soups: Some(finished),
+            census: &census,
+        };
+        println!();
+        print!("{haul}");
+    }
+}
+
+/// The name Catagolue gives soups of `symmetry` and `size` cells square,
+/// its numbers telling whether the center of the symmetry is a cell, 1, or
+/// the corner between four cells, 4.
+fn catagolue_symmetry(symmetry: Option<Symmetry>, size: usize) -> String {
+    let center = match size % 2 {
+        1 => 1,
+        _ => 4,
+    };
+    match symmetry {
+        None => "C1".to_string(),
+        Some(Symmetry::C2) => format!("C2_{center}"),
+        Some(Symmetry::C4) => format!("C4_{center}"),
+        Some(Symmetry::D4) => format!("D4_+{center}"),
+        Some(Symmetry::D8) => format!("D8_{center}"),
+    }
+}
+
+/// Top-left corners of the 5x5 windows holding an isolated glider, in any
+/// phase and orientation, close to the boundary of an unbounded universe.
+///
+/// The boundary of an unbounded universe follows its outermost cells, so a
+/// glider there has nothing left to collide with.
+pub fn escaping_gliders(matrix: &Matrix) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    if matrix.row_count() < 5 || matrix.col_count() < 5 {
+        return found;
+    }
+
+    let gliders = glider_shapes();
+    let (last_row, last_col) = (matrix.row_count() - 5, matrix.col_count() - 5);
+    let near_boundary = |row: usize, col: usize| {
+        row <= ESCAPE_DISTANCE
+            || col <= ESCAPE_DISTANCE
+            || row + ESCAPE_DISTANCE >= last_row
+            || col + ESCAPE_DISTANCE >= last_col
+    };
+
+    for row in 0..=last_row {
+        for col in 0..=last_col {
+            if near_boundary(row, col)
+                && gliders
+                    .iter()
+                    .any(|glider| matches_at(matrix, glider, row, col))
+            {
+                found.push((row, col));
+            }
+        }
+    }
+
+    found
+}
+
+/// Whether the 5x5 window at `(row, col)` holds exactly `glider` in its center.
+fn matches_at(matrix: &Matrix, glider: &Matrix, row: usize, col: usize) -> bool {
+    (0..5).all(|r| {
+        (0..5).all(|c| {
+            let expected = if (1..4).contains(&r) && (1..4).contains(&c) {
+                glider.get(r - 1, c - 1)
+            } else {
+                0
+            };
+            matrix.get(row + r, col + c) == expected
+        })
+    })
+}
+
+/// The four phases of the glider in each of the four directions.
+fn glider_shapes() -> Vec<Matrix> {
+    let mut glider = Matrix::new(3, 3);
+    for (row, col) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+        glider.set(row, col, 1);
+    }
+
+    let mut shapes = Vec::new();
+    for _ in 0..4 {
+        let mut shape = glider.clone();
+        for _ in 0..4 {
+            shape = shape.rotate90();
+            if !shapes.contains(&shape) {
+                shapes.push(shape.clone());
+            }
+        }
+        glider = next_phase(&glider);
+    }
+    shapes
+}
+
+/// Advances a glider by one generation and crops it back to its 3x3 bounding box.
+fn next_phase(glider: &Matrix) -> Matrix {
+    let expanded = glider.expand(1, 1, 1, 1);
+    let mut padded = PaddedMatrix::new(expanded.row_count(), expanded.col_count());
+    padded.load(&expanded, Rule::CONWAY.edge);
+
+    let mut next = Matrix::new(expanded.row_count(), expanded.col_count());
+    for row in 0..next.row_count() {
+        for col in 0..next.col_count() {
+            next.set(row, col, padded.next_state(row, col, &Rule::CONWAY));
+        }
+    }
+
+    next.crop_to_bounding_box()
+}
@@ -0,0 +1,38 @@
+//! Ctrl-C and SIGTERM turned into a request to stop, which running
+//! simulations check between generations to shut down cleanly: with the
+//! terminal restored, a summary printed and the final state saved, rather
+//! than killed halfway through drawing a frame. Without the `native`
+//! feature signals are not caught and keep their default behavior.
+
+#[cfg(feature = "native")]
+use signal_hook::consts::{SIGINT, SIGTERM};
+#[cfg(feature = "native")]
+use signal_hook::flag;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Set once a signal arrived, after the signals are caught.
+static REQUESTED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+/// Catches Ctrl-C and SIGTERM from now on, which then only request the
+/// shutdown.
+pub fn catch_signals() -> io::Result<()> {
+    if REQUESTED.get().is_some() {
+        return Ok(());
+    }
+    let requested = Arc::new(AtomicBool::new(false));
+    #[cfg(feature = "native")]
+    for signal in [SIGINT, SIGTERM] {
+        flag::register(signal, Arc::clone(&requested))?;
+    }
+    let _ = REQUESTED.set(requested);
+    Ok(())
+}
+
+/// Whether a caught signal asked to shut down.
+pub fn requested() -> bool {
+    REQUESTED
+        .get()
+        .is_some_and(|requested| requested.load(Ordering::Relaxed))
+}
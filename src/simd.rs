@@ -0,0 +1,95 @@
+//! Row-at-a-time tick whose inner loops are branch free, so the compiler can
+//! vectorize them. On x86 the same code is additionally compiled with AVX2
+//! enabled and picked at runtime when the CPU supports it, which takes `std`,
+//! aarch64 always has NEON.
+//! Conway's rule has a dedicated inner loop, other rules look up each cell's
+//! next state, which vectorizes less well.
+
+use alloc::vec;
+
+use crate::edge::Edge;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+pub fn tick(rule: &Rule, matrix: &Matrix, next: &mut Matrix) {
+    #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2, checked above
+        unsafe { tick_avx2(rule, matrix, next) };
+        return;
+    }
+
+    tick_rows(rule, matrix, next);
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+#[target_feature(enable = "avx2")]
+unsafe fn tick_avx2(rule: &Rule, matrix: &Matrix, next: &mut Matrix) {
+    tick_rows(rule, matrix, next);
+}
+
+#[inline(always)]
+fn tick_rows(rule: &Rule, matrix: &Matrix, next: &mut Matrix) {
+    let edge = rule.edge;
+    let conway = rule.is_conway();
+    let rows = matrix.row_count();
+    let cols = matrix.col_count();
+    // the row beyond the top and bottom boundaries in dead and alive modes
+    let outside_row = vec![u8::from(edge == Edge::Alive); cols];
+    // vertical sums of each column, padded by one column on each side
+    let mut sums = vec![0; cols + 2];
+
+    for row in 0..rows {
+        let above = match (row, edge) {
+            (0, Edge::Dead | Edge::Alive) => &outside_row,
+            (0, Edge::Wrap) => row_slice(matrix, rows - 1),
+            (0, Edge::Mirror) => row_slice(matrix, 0),
+            _ => row_slice(matrix, row - 1),
+        };
+        let below = match edge {
+            _ if row < rows - 1 => row_slice(matrix, row + 1),
+            Edge::Dead | Edge::Alive => &outside_row,
+            Edge::Wrap => row_slice(matrix, 0),
+            Edge::Mirror => row_slice(matrix, rows - 1),
+        };
+        let current = row_slice(matrix, row);
+
+        for (((sum, &up), &mid), &down) in
+            sums[1..=cols].iter_mut().zip(above).zip(current).zip(below)
+        {
+            *sum = up + mid + down;
+        }
+
+        let (left, right) = match edge {
+            Edge::Dead => (0, 0),
+            Edge::Alive => (3, 3),
+            Edge::Wrap => (sums[cols], sums[1]),
+            Edge::Mirror => (sums[1], sums[cols]),
+        };
+        sums[0] = left;
+        sums[cols + 1] = right;
+
+        let out = &mut next.as_mut_slice()[row * cols..(row + 1) * cols];
+        let cells = out
+            .iter_mut()
+            .zip(current)
+            .zip(&sums[..cols])
+            .zip(&sums[1..=cols])
+            .zip(&sums[2..]);
+
+        if conway {
+            for ((((cell, &alive), &left), &center), &right) in cells {
+                let live_count = left + center + right - alive;
+                *cell = u8::from(live_count == 3) | (alive & u8::from(live_count == 2));
+            }
+        } else {
+            for ((((cell, &alive), &left), &center), &right) in cells {
+                *cell = rule.next_state(alive, left + center + right - alive);
+            }
+        }
+    }
+}
+
+fn row_slice(matrix: &Matrix, row: usize) -> &[u8] {
+    &matrix.as_slice()[row * matrix.col_count()..(row + 1) * matrix.col_count()]
+}
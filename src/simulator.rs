@@ -0,0 +1,129 @@
+//! Many independent universes driven together: every call ticks all of
+//! them, spread over threads one universe per task rather than splitting
+//! a single board, which keeps every core busy with boards too small to
+//! be worth splitting, such as soups, and keeps universes compared side by
+//! side in lockstep.
+
+use rayon::prelude::*;
+
+use crate::game_of_life::GameOfLife;
+
+/// Identifies a universe of a simulator, never reused for another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UniverseId(usize);
+
+/// Independent universes ticked together, in the order they were added.
+#[derive(Default)]
+pub struct Simulator {
+    universes: Vec<(UniverseId, GameOfLife)>,
+    next_id: usize,
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `game` to the universes ticked from now on.
+    pub fn add_universe(&mut self, game: GameOfLife) -> UniverseId {
+        let id = UniverseId(self.next_id);
+        self.next_id += 1;
+        self.universes.push((id, game));
+        id
+    }
+
+    /// Takes a universe out of the simulator, `None` if it is not in it.
+    pub fn remove_universe(&mut self, id: UniverseId) -> Option<GameOfLife> {
+        let idx = self.universes.iter().position(|(other, _)| *other == id)?;
+        Some(self.universes.remove(idx).1)
+    }
+
+    /// Takes the universes that have finished out of the simulator.
+    pub fn take_finished(&mut self) -> Vec<(UniverseId, GameOfLife)> {
+        let (finished, running) = std::mem::take(&mut self.universes)
+            .into_iter()
+            .partition(|(_, game)| game.is_finished());
+        self.universes = running;
+        finished
+    }
+
+    pub fn universe(&self, id: UniverseId) -> Option<&GameOfLife> {
+        self.universes
+            .iter()
+            .find(|(other, _)| *other == id)
+            .map(|(_, game)| game)
+    }
+
+    pub fn universe_mut(&mut self, id: UniverseId) -> Option<&mut GameOfLife> {
+        self.universes
+            .iter_mut()
+            .find(|(other, _)| *other == id)
+            .map(|(_, game)| game)
+    }
+
+    pub fn universes(&self) -> impl Iterator<Item = (UniverseId, &GameOfLife)> {
+        self.universes.iter().map(|(id, game)| (*id, game))
+    }
+
+    pub fn universes_mut(&mut self) -> impl Iterator<Item = (UniverseId, &mut GameOfLife)> {
+        self.universes.iter_mut().map(|(id, game)| (*id, game))
+    }
+
+    pub fn len(&self) -> usize {
+        self.universes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.universes.is_empty()
+    }
+
+    /// Whether every universe has finished, so ticking would change nothing.
+    pub fn is_finished(&self) -> bool {
+        self.universes.iter().all(|(_, game)| game.is_finished())
+    }
+
+    /// Ticks every universe that hasn't finished once, in parallel.
+    pub fn tick_all(&mut self) {
+        self.tick_all_then(|_| ());
+    }
+
+    /// Ticks like `tick_all` and runs `then` on every universe ticked right
+    /// after its tick, on the same thread, e.g. to edit its board. Returns
+    /// what `then` returned for every universe ticked.
+    pub fn tick_all_then<R, F>(&mut self, then: F) -> Vec<(UniverseId, R)>
+    where
+        R: Send,
+        F: Fn(&mut GameOfLife) -> R + Sync,
+    {
+        self.universes
+            .par_iter_mut()
+            .filter(|(_, game)| !game.is_finished())
+            .map(|(id, game)| {
+                game.tick();
+                (*id, then(game))
+            })
+            .collect()
+    }
+
+    /// Runs the generations of a frame in every universe that hasn't
+    /// finished, `step_size` of each, in parallel.
+    pub fn step_all(&mut self) {
+        self.universes
+            .par_iter_mut()
+            .filter(|(_, game)| !game.is_finished())
+            .for_each(|(_, game)| game.step());
+    }
+
+    /// Ticks all universes until every one has finished. Universes without
+    /// stop conditions never finish.
+    pub fn run_all(&mut self) {
+        while !self.is_finished() {
+            self.tick_all();
+        }
+    }
+
+    /// The universes and their ids, in the order they were added.
+    pub fn into_universes(self) -> Vec<(UniverseId, GameOfLife)> {
+        self.universes
+    }
+}
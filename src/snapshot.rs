@@ -0,0 +1,137 @@
+//! Checkpoints as compressed binary snapshots: a small header with the
+//! dimensions, rule and the other fields of the checkpoint, followed by the
+//! cells row after row, one byte each, compressed with zstd. Big boards take
+//! a fraction of the space and time of a JSON checkpoint, where every cell
+//! is a digit and a comma.
+//!
+//! The header holds, integers little-endian:
+//! - the magic bytes `GOLZ` and the snapshot version as a `u32`,
+//! - the rows and columns as `u64`s,
+//! - the generation and the seed as `u64`s,
+//! - the length of the rule in B/S notation as a `u8`, then the rule,
+//! - the edge mode as a `u8`, 0 for dead, 1 alive, 2 wrap, 3 mirror,
+//! - whether the universe is unbounded as a `u8`,
+//! - the row and column of the origin as `u64`s.
+
+use std::io::{self, Read, Write};
+
+use crate::checkpoint::{Checkpoint, CHECKPOINT_VERSION, SNAPSHOT_MAGIC};
+use crate::edge::Edge;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// Version written into new snapshots. Snapshots of any version up to this
+/// one can be read.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Extension of the files checkpoints are saved to as snapshots.
+pub const EXTENSION: &str = "snap";
+
+/// Compression level, the default of zstd, which compresses boards about as
+/// well as the higher levels at several times their speed.
+const LEVEL: i32 = 3;
+
+/// Writes `checkpoint` to `out` as a snapshot.
+pub fn write<W: Write>(checkpoint: &Checkpoint, mut out: W) -> io::Result<()> {
+    let matrix = &checkpoint.matrix;
+    let rule = checkpoint.rule.to_string();
+    let edge: u8 = match checkpoint.edge {
+        Edge::Dead => 0,
+        Edge::Alive => 1,
+        Edge::Wrap => 2,
+        Edge::Mirror => 3,
+    };
+
+    out.write_all(&SNAPSHOT_MAGIC)?;
+    out.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+    for value in [matrix.row_count(), matrix.col_count()] {
+        out.write_all(&(value as u64).to_le_bytes())?;
+    }
+    out.write_all(&(checkpoint.ticks as u64).to_le_bytes())?;
+    out.write_all(&checkpoint.seed.to_le_bytes())?;
+    out.write_all(&[rule.len() as u8])?;
+    out.write_all(rule.as_bytes())?;
+    out.write_all(&[edge, u8::from(checkpoint.unbounded)])?;
+    for value in [checkpoint.origin.0, checkpoint.origin.1] {
+        out.write_all(&(value as u64).to_le_bytes())?;
+    }
+
+    let mut encoder = zstd::Encoder::new(out, LEVEL)?;
+    encoder.write_all(matrix.as_slice())?;
+    encoder.finish()?.flush()
+}
+
+/// Reads a snapshot written by `write`.
+pub fn read<R: Read>(mut input: R) -> io::Result<Checkpoint> {
+    let mut magic = [0; 4];
+    input.read_exact(&mut magic)?;
+    if magic != SNAPSHOT_MAGIC {
+        return Err(invalid("not a snapshot".to_string()));
+    }
+    let version = u32::from_le_bytes(read_array(&mut input)?);
+    if version > SNAPSHOT_VERSION {
+        return Err(invalid(format!(
+            "snapshot version {version} is newer than the supported version {SNAPSHOT_VERSION}"
+        )));
+    }
+
+    let rows = read_usize(&mut input)?;
+    let cols = read_usize(&mut input)?;
+    let ticks = read_usize(&mut input)?;
+    let seed = u64::from_le_bytes(read_array(&mut input)?);
+    let [rule_len] = read_array(&mut input)?;
+    let mut rule = vec![0; rule_len as usize];
+    input.read_exact(&mut rule)?;
+    let rule = String::from_utf8(rule)
+        .map_err(|err| invalid(err.to_string()))?
+        .parse::<Rule>()
+        .map_err(invalid)?;
+    let [edge, unbounded] = read_array(&mut input)?;
+    let edge = match edge {
+        0 => Edge::Dead,
+        1 => Edge::Alive,
+        2 => Edge::Wrap,
+        3 => Edge::Mirror,
+        _ => return Err(invalid(format!("unknown edge mode {edge}"))),
+    };
+    let origin = (read_usize(&mut input)?, read_usize(&mut input)?);
+
+    let size = rows
+        .checked_mul(cols)
+        .ok_or_else(|| invalid(format!("board of {rows}x{cols} cells is too large")))?;
+    let mut cells = Vec::new();
+    zstd::Decoder::new(input)?
+        .take(size as u64 + 1)
+        .read_to_end(&mut cells)?;
+    let matrix = Matrix::from_vec(rows, cols, cells).ok_or_else(|| {
+        invalid(format!(
+            "snapshot of a {rows}x{cols} board holds another number of cells"
+        ))
+    })?;
+
+    Ok(Checkpoint {
+        version: CHECKPOINT_VERSION,
+        ticks,
+        seed,
+        edge,
+        rule: rule.with_edge(edge),
+        unbounded: unbounded != 0,
+        origin,
+        matrix,
+    })
+}
+
+fn read_array<R: Read, const N: usize>(input: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    input.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_usize<R: Read>(input: &mut R) -> io::Result<usize> {
+    let value = u64::from_le_bytes(read_array(input)?);
+    usize::try_from(value).map_err(|err| invalid(err.to_string()))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,108 @@
+//! Several species sharing a Life-like rule, such as Immigration with two
+//! species and QuadLife with four. Live cells hold their species, numbered
+//! from 1, and survive as it, and newborn cells take on the species most of
+//! their live neighbors have.
+
+use rand::Rng;
+
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::padded::PaddedMatrix;
+use crate::rule::Rule;
+
+/// The largest number of species, one per color of the species theme.
+pub const MAX_SPECIES: u8 = 4;
+
+/// Ticks a board of several species one cell after another. Births and
+/// survivals follow the rule whatever the species of the neighbors.
+pub struct SpeciesEngine {
+    species: u8,
+    padded: PaddedMatrix,
+}
+
+impl SpeciesEngine {
+    pub fn new(species: u8) -> Self {
+        SpeciesEngine {
+            species,
+            padded: PaddedMatrix::new(0, 0),
+        }
+    }
+
+    pub fn species(&self) -> u8 {
+        self.species
+    }
+}
+
+impl TickEngine for SpeciesEngine {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        self.padded.resize(src.row_count(), src.col_count());
+        self.padded.load(src, rule.edge);
+
+        let cols = src.col_count();
+        for row in 0..src.row_count() {
+            let cells = &mut dst.as_mut_slice()[row * cols..(row + 1) * cols];
+            for (col, value) in cells.iter_mut().enumerate() {
+                *value = next_state(&self.padded.neighborhood(row, col), self.species, rule);
+            }
+        }
+    }
+}
+
+/// The next state of the first cell of `neighborhood`, which is followed by
+/// its eight neighbors.
+pub fn next_state(neighborhood: &[u8; 9], species: u8, rule: &Rule) -> u8 {
+    let (cell, neighbors) = (neighborhood[0], &neighborhood[1..]);
+    let live = neighbors.iter().filter(|&&state| state != 0).count() as u8;
+
+    match (rule.next_state(u8::from(cell != 0), live), cell) {
+        (0, _) => 0,
+        (_, 0) => parent_species(neighbors, species),
+        (_, cell) => cell,
+    }
+}
+
+/// The species most live neighbors have. When the neighbors are all of
+/// different species and exactly one species is missing, the child is of
+/// that one, as in QuadLife where three parents of different species have
+/// a child of the fourth. Other ties go to the lowest tied species.
+fn parent_species(neighbors: &[u8], species: u8) -> u8 {
+    let mut counts = [0; MAX_SPECIES as usize + 1];
+    for &state in neighbors {
+        // states beyond the species, e.g. of a board from another rule, count as the first
+        let state = if state > species { 1 } else { state };
+        counts[usize::from(state)] += 1;
+    }
+
+    let counts = &counts[1..=usize::from(species)];
+    let most = counts.iter().copied().max().unwrap_or(0);
+    let tied: Vec<usize> = (0..counts.len())
+        .filter(|&idx| counts[idx] == most)
+        .collect();
+    let absent: Vec<usize> = (0..counts.len()).filter(|&idx| counts[idx] == 0).collect();
+
+    let winner = match (tied.as_slice(), absent.as_slice()) {
+        ([winner], _) => *winner,
+        (_, [absent]) if most == 1 => *absent,
+        (tied, _) => tied[0],
+    };
+    winner as u8 + 1
+}
+
+/// Gives every live cell of `matrix` a random species.
+pub fn scatter<R: Rng>(matrix: &mut Matrix, species: u8, rng: &mut R) {
+    for cell in matrix.as_mut_slice() {
+        if *cell != 0 {
+            *cell = rng.gen_range(1..=species);
+        }
+    }
+}
+
+/// Parses a number of species on the command line.
+pub fn parse_species(s: &str) -> Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(species) if (2..=MAX_SPECIES).contains(&species) => Ok(species),
+        _ => Err(format!(
+            "invalid number of species {s:?}, expected 2 to {MAX_SPECIES}"
+        )),
+    }
+}
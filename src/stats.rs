@@ -0,0 +1,154 @@
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+
+use crate::matrix::Matrix;
+
+/// Population figures of a single generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Generation {
+    pub tick: usize,
+    pub population: usize,
+    pub births: usize,
+    pub deaths: usize,
+}
+
+impl Generation {
+    pub const CSV_HEADER: &'static str = "tick,population,births,deaths";
+
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            self.tick, self.population, self.births, self.deaths
+        )
+    }
+}
+
+/// Population history of a simulation, one entry per generation.
+pub struct Stats {
+    history: Vec<Generation>,
+    peak: Generation,
+}
+
+impl Stats {
+    pub fn new(matrix: &Matrix) -> Self {
+        Self::starting_at(0, matrix)
+    }
+
+    /// Statistics of a simulation resumed at generation `tick`.
+    pub fn starting_at(tick: usize, matrix: &Matrix) -> Self {
+        let initial = Generation {
+            tick,
+            population: matrix.count_live(),
+            births: 0,
+            deaths: 0,
+        };
+
+        Stats {
+            history: vec![initial],
+            peak: initial,
+        }
+    }
+
+    /// Records the transition from `previous` to `current` as generation `tick`.
+    pub fn record(&mut self, tick: usize, previous: &Matrix, current: &Matrix) -> &Generation {
+        let mut generation = Generation {
+            tick,
+            population: 0,
+            births: 0,
+            deaths: 0,
+        };
+
+        for (&before, &after) in previous.as_slice().iter().zip(current.as_slice()) {
+            match (before != 0, after != 0) {
+                (false, true) => generation.births += 1,
+                (true, false) => generation.deaths += 1,
+                _ => {}
+            }
+            if after != 0 {
+                generation.population += 1;
+            }
+        }
+
+        self.push(generation);
+        self.latest()
+    }
+
+    /// Appends a generation recorded earlier, for example one that was undone.
+    pub fn push(&mut self, generation: Generation) {
+        if generation.population > self.peak.population {
+            self.peak = generation;
+        }
+        self.history.push(generation);
+    }
+
+    /// Forgets the generations after `tick`, the initial generation is always kept.
+    pub fn truncate(&mut self, tick: usize) {
+        let len = self
+            .history
+            .iter()
+            .position(|generation| generation.tick > tick)
+            .unwrap_or(self.history.len())
+            .max(1);
+        self.history.truncate(len);
+
+        self.peak = self.history[0];
+        for generation in &self.history[1..] {
+            if generation.population > self.peak.population {
+                self.peak = *generation;
+            }
+        }
+    }
+
+    pub fn latest(&self) -> &Generation {
+        self.history.last().unwrap()
+    }
+
+    /// The first generation that reached the highest population so far.
+    pub fn peak(&self) -> &Generation {
+        &self.peak
+    }
+
+    /// The population of the last `width` generations as a line of block
+    /// characters, scaled from the lowest to the highest population among them.
+    pub fn sparkline(&self, width: usize) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let start = self.history.len().saturating_sub(width);
+        let recent = &self.history[start..];
+        let min = recent.iter().map(|generation| generation.population).min();
+        let max = recent.iter().map(|generation| generation.population).max();
+        let (min, max) = (min.unwrap_or(0), max.unwrap_or(0));
+
+        recent
+            .iter()
+            .map(|generation| {
+                let level = (generation.population - min) * (BARS.len() - 1) / (max - min).max(1);
+                BARS[level]
+            })
+            .collect()
+    }
+
+    pub fn history(&self) -> &[Generation] {
+        &self.history
+    }
+
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", Generation::CSV_HEADER)?;
+        for generation in &self.history {
+            generation.write_csv(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let latest = self.latest();
+        write!(
+            f,
+            "population: {} (peak {} at tick {}) \nbirths: {} deaths: {} ",
+            latest.population, self.peak.population, self.peak.tick, latest.births, latest.deaths
+        )
+    }
+}
@@ -0,0 +1,219 @@
+//! Brute force search for still lifes and oscillators: every board of a
+//! small bounding box is tried in turn, or random soups in a box are run
+//! until they settle and the objects they leave behind are kept. Every
+//! object is reported once, under its apgcode.
+
+use clap::Args;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use std::collections::HashSet;
+
+use crate::analyze::{self, Kind};
+use crate::game_of_life::{self, GameOfLife};
+use crate::matrix::Matrix;
+use crate::rle;
+use crate::rule::Rule;
+
+/// Largest width and height of the boxes enumerated, 2^25 boards for the
+/// largest box alone.
+pub const MAX_ENUMERATED_SIZE: usize = 5;
+
+#[derive(Args, Debug)]
+pub struct StillSearchArgs {
+    /// Largest width and height of the bounding boxes searched, at most 5 unless --soups is set
+    #[arg(long, default_value_t = 4)]
+    size: usize,
+    /// Life-like rule in B/S notation to search under
+    #[arg(long, default_value_t = Rule::CONWAY)]
+    rule: Rule,
+    /// Largest period of the oscillators reported, 1 for still lifes only
+    #[arg(long, default_value_t = 2)]
+    max_period: usize,
+    /// Run this many random soups of the largest box until they settle instead of trying every board
+    #[arg(long)]
+    soups: Option<u64>,
+    /// Seed of the first soup, the following soups use consecutive seeds
+    #[arg(long, default_value_t = 0, requires = "soups")]
+    seed: u64,
+    /// Probability of each soup cell being alive
+    #[arg(long, value_parser = game_of_life::parse_probability, default_value_t = 0.5, requires = "soups")]
+    fill_prob: f64,
+    /// Number of generations each soup is run for at most
+    #[arg(long, default_value_t = 1000, requires = "soups")]
+    ticks: usize,
+}
+
+/// A still life or oscillator found.
+#[derive(Clone)]
+pub struct Discovery {
+    pub apgcode: String,
+    pub kind: Kind,
+    /// Its live cells, cropped to their bounding box, in the phase and
+    /// orientation it was found in.
+    pub cells: Matrix,
+}
+
+/// Tries every board of every box up to `size` cells wide and high whose
+/// live cells reach all four sides, and returns the still lifes and
+/// oscillators of a period up to `max_period` among them, smaller boxes
+/// first. Turned boxes are left out, objects are found in any orientation.
+pub fn enumerate(size: usize, rule: &Rule, max_period: usize) -> Vec<Discovery> {
+    let mut found = Found::default();
+    for cols in 1..=size {
+        for rows in 1..=cols {
+            let boards: Vec<Matrix> = (0..1u64 << (rows * cols))
+                .into_par_iter()
+                .filter(|&bits| reaches_sides(bits, rows, cols))
+                .map(|bits| board(bits, rows, cols))
+                .filter(|board| period(board, rule, max_period).is_some())
+                .collect();
+            for board in boards {
+                found.add(&board, rule, max_period);
+            }
+        }
+    }
+    found.discoveries
+}
+
+/// Runs `soups` random soups of `size` by `size` cells from `seed` on,
+/// each until it settles into a cycle or for `ticks` generations, and
+/// returns the still lifes and oscillators of a period up to `max_period`
+/// the settled soups hold, in the order of the soups.
+pub fn search_soups(
+    size: usize,
+    rule: &Rule,
+    max_period: usize,
+    (seed, soups): (u64, u64),
+    fill_prob: f64,
+    ticks: usize,
+) -> Vec<Discovery> {
+    // the seeds stop at the largest one rather than wrapping around
+    let ashes: Vec<Matrix> = (seed..seed.saturating_add(soups))
+        .into_par_iter()
+        .filter_map(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = GameOfLife::new(Matrix::random(size, size, fill_prob, &mut rng));
+            game.set_rule(*rule);
+            game.set_history(0);
+            game.set_stop_on_cycle(true);
+            game.set_max_ticks(Some(ticks));
+            while !game.is_finished() {
+                game.tick();
+            }
+            game.cycle().map(|_| game.matrix().clone())
+        })
+        .collect();
+
+    let mut found = Found::default();
+    for ash in ashes {
+        for object in analyze::objects(&ash, rule) {
+            found.add(&object.cells, rule, max_period);
+        }
+    }
+    found.discoveries
+}
+
+/// Searches as `args` ask and prints every discovery as RLE, named by its
+/// apgcode, followed by the number found on stderr.
+pub fn run(args: &StillSearchArgs) {
+    let discoveries = match args.soups {
+        Some(soups) => search_soups(
+            args.size,
+            &args.rule,
+            args.max_period,
+            (args.seed, soups),
+            args.fill_prob,
+            args.ticks,
+        ),
+        None if args.size > MAX_ENUMERATED_SIZE => clap::Error::raw(
+            clap::error::ErrorKind::ValueValidation,
+            format!(
+                "boxes larger than {MAX_ENUMERATED_SIZE}x{MAX_ENUMERATED_SIZE} take too long to enumerate, search random soups with --soups instead\n"
+            ),
+        )
+        .exit(),
+        None => enumerate(args.size, &args.rule, args.max_period),
+    };
+
+    for discovery in &discoveries {
+        println!("#N {}", discovery.apgcode);
+        println!("#C {}", discovery.kind);
+        print!("{}", rle::encode_with_rule(&discovery.cells, &args.rule));
+    }
+    let still_lifes = discoveries
+        .iter()
+        .filter(|discovery| discovery.kind == Kind::StillLife)
+        .count();
+    eprintln!(
+        "{still_lifes} still lifes and {} oscillators found",
+        discoveries.len() - still_lifes
+    );
+}
+
+/// The discoveries so far and their apgcodes.
+#[derive(Default)]
+struct Found {
+    discoveries: Vec<Discovery>,
+    apgcodes: HashSet<String>,
+}
+
+impl Found {
+    /// Adds `cells` if they are a single still life or an oscillator of a
+    /// period up to `max_period` not found before.
+    fn add(&mut self, cells: &Matrix, rule: &Rule, max_period: usize) {
+        let objects = analyze::objects(cells, rule);
+        let [object] = objects.as_slice() else {
+            return;
+        };
+        let wanted = match object.kind {
+            Kind::StillLife => true,
+            Kind::Oscillator { period } => period <= max_period,
+            _ => false,
+        };
+        if wanted && self.apgcodes.insert(object.apgcode.clone()) {
+            self.discoveries.push(Discovery {
+                apgcode: object.apgcode.clone(),
+                kind: object.kind,
+                cells: object.cells.clone(),
+            });
+        }
+    }
+}
+
+/// Period after which `cells` take on their shape again in place, if at
+/// most `max_period`, a quick check before the objects are told apart.
+fn period(cells: &Matrix, rule: &Rule, max_period: usize) -> Option<usize> {
+    let mut phase = cells.clone();
+    let mut shift = (0, 0);
+    for period in 1..=max_period {
+        let (next, moved) = analyze::step(&phase, rule);
+        if next.row_count() == 0 {
+            return None;
+        }
+        shift = (shift.0 + moved.0, shift.1 + moved.1);
+        phase = next;
+        if phase == *cells {
+            return (shift == (0, 0)).then_some(period);
+        }
+    }
+    None
+}
+
+/// Whether the live cells of the board numbered `bits`, bit `row * cols +
+/// col` for each cell, reach all four sides of its box.
+fn reaches_sides(bits: u64, rows: usize, cols: usize) -> bool {
+    let row_mask = (1 << cols) - 1;
+    let col_mask = (0..rows).fold(0, |mask, row| mask | 1 << (row * cols));
+    bits & row_mask != 0
+        && bits >> ((rows - 1) * cols) & row_mask != 0
+        && bits & col_mask != 0
+        && bits >> (cols - 1) & col_mask != 0
+}
+
+fn board(bits: u64, rows: usize, cols: usize) -> Matrix {
+    let cells = (0..rows * cols)
+        .map(|idx| (bits >> idx & 1) as u8)
+        .collect();
+    Matrix::from_vec(rows, cols, cells).expect("the board has its size")
+}
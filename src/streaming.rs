@@ -0,0 +1,114 @@
+//! Tick that writes the next generation over the current one, for boards
+//! too large to keep two copies of. Rows are computed one after another into
+//! a scratch row, and the row above is saved before it is overwritten, so
+//! two rows of scratch space are needed, three with wrapping edges, where
+//! the last row also depends on the original first one.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edge::Edge;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// How many cells are alive after a tick, and how many were born and died.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Transition {
+    pub population: usize,
+    pub births: usize,
+    pub deaths: usize,
+}
+
+/// A row of neighbors, either cells of the board or cells beyond its
+/// boundaries that are all dead or all alive.
+#[derive(Clone, Copy)]
+enum Row<'a> {
+    Cells(&'a [u8]),
+    Constant(u8),
+}
+
+impl Row<'_> {
+    /// State of the cell at `col`, which may lie beyond the left or right boundary.
+    #[inline]
+    fn cell(self, col: isize, edge: Edge) -> u8 {
+        let cells = match self {
+            Row::Cells(cells) => cells,
+            Row::Constant(state) => return state,
+        };
+        let cols = cells.len() as isize;
+
+        if (0..cols).contains(&col) {
+            return cells[col as usize];
+        }
+
+        match edge {
+            Edge::Dead => 0,
+            Edge::Alive => 1,
+            Edge::Wrap => cells[col.rem_euclid(cols) as usize],
+            Edge::Mirror => cells[col.clamp(0, cols - 1) as usize],
+        }
+    }
+}
+
+/// Replaces `matrix` by its next generation under `rule`.
+pub fn tick_in_place(matrix: &mut Matrix, rule: &Rule) -> Transition {
+    let (rows, cols) = (matrix.row_count(), matrix.col_count());
+    let mut transition = Transition::default();
+    if rows == 0 || cols == 0 {
+        return transition;
+    }
+
+    let edge = rule.edge;
+    let first: Vec<u8> = match edge {
+        Edge::Wrap => matrix.as_slice()[..cols].to_vec(),
+        _ => Vec::new(),
+    };
+    // the original row above the current one, which was already overwritten
+    let mut above = vec![0; cols];
+    let mut next = vec![0; cols];
+
+    for row in 0..rows {
+        let cells = matrix.as_slice();
+        let original = |row: usize| Row::Cells(&cells[row * cols..(row + 1) * cols]);
+        let center = original(row);
+        let up = match edge {
+            _ if row > 0 => Row::Cells(&above),
+            Edge::Dead => Row::Constant(0),
+            Edge::Alive => Row::Constant(1),
+            Edge::Wrap => original(rows - 1),
+            Edge::Mirror => center,
+        };
+        let down = match edge {
+            _ if row + 1 < rows => original(row + 1),
+            Edge::Dead => Row::Constant(0),
+            Edge::Alive => Row::Constant(1),
+            Edge::Wrap => Row::Cells(&first),
+            Edge::Mirror => center,
+        };
+
+        for (col, value) in next.iter_mut().enumerate() {
+            let col = col as isize;
+            let mut live_count = 0;
+            for neighbors in [up, center, down] {
+                live_count += neighbors.cell(col - 1, edge)
+                    + neighbors.cell(col, edge)
+                    + neighbors.cell(col + 1, edge);
+            }
+            let alive = center.cell(col, edge);
+            *value = rule.next_state(alive, live_count - alive);
+
+            match (alive != 0, *value != 0) {
+                (false, true) => transition.births += 1,
+                (true, false) => transition.deaths += 1,
+                _ => {}
+            }
+            transition.population += usize::from(*value != 0);
+        }
+
+        let cells = &mut matrix.as_mut_slice()[row * cols..(row + 1) * cols];
+        above.copy_from_slice(cells);
+        cells.copy_from_slice(&next);
+    }
+
+    transition
+}
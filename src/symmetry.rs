@@ -0,0 +1,77 @@
+//! Symmetric random soups, mirrored or rotated onto themselves, which are
+//! far more likely to produce rare patterns than asymmetric ones.
+
+#[cfg(feature = "std")]
+use clap::ValueEnum;
+
+use crate::matrix::Matrix;
+
+/// A symmetry of a board, named as in Catagolue's soup symmetries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum Symmetry {
+    /// The same after a half turn
+    C2,
+    /// The same after a quarter turn, square boards only
+    C4,
+    /// Mirrored left to right and top to bottom
+    D4,
+    /// Mirrored along both axes and both diagonals, square boards only
+    D8,
+}
+
+impl Symmetry {
+    /// Whether the symmetry turns rows into columns, which only square
+    /// boards allow.
+    pub fn needs_square(self) -> bool {
+        matches!(self, Symmetry::C4 | Symmetry::D8)
+    }
+
+    /// The cell of the orbit of `(row, col)` the whole orbit copies, the
+    /// first of its cells row after row.
+    fn representative(self, rows: usize, cols: usize, row: usize, col: usize) -> (usize, usize) {
+        let (last_row, last_col) = (rows - 1, cols - 1);
+        let mut images = [(row, col); 8];
+        let count = match self {
+            Symmetry::C2 => {
+                images[1] = (last_row - row, last_col - col);
+                2
+            }
+            Symmetry::C4 => {
+                images[1] = (col, last_col - row);
+                images[2] = (last_row - row, last_col - col);
+                images[3] = (last_row - col, row);
+                4
+            }
+            Symmetry::D4 | Symmetry::D8 => {
+                images[1] = (row, last_col - col);
+                images[2] = (last_row - row, col);
+                images[3] = (last_row - row, last_col - col);
+                if self == Symmetry::D4 {
+                    4
+                } else {
+                    for index in 0..4 {
+                        let (row, col) = images[index];
+                        images[index + 4] = (col, row);
+                    }
+                    8
+                }
+            }
+        };
+        images[..count].iter().copied().min().unwrap()
+    }
+
+    /// Makes the board symmetric, every cell copying the first cell of its
+    /// orbit, so that a random board keeps its density.
+    pub fn apply(self, matrix: &mut Matrix) {
+        let (rows, cols) = (matrix.row_count(), matrix.col_count());
+        assert!(!self.needs_square() || rows == cols);
+
+        for row in 0..rows {
+            for col in 0..cols {
+                let (source_row, source_col) = self.representative(rows, cols, row, col);
+                matrix.set(row, col, matrix.get(source_row, source_col));
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Boards stored tile after tile instead of row after row, so that the
+//! neighbors of a cell, which lie in the rows above and below it, are close
+//! by in memory on big boards too.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edge::Edge;
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+/// Width and height of a tile.
+pub const TILE_SIZE: usize = 64;
+
+/// Number of cells of a tile.
+const TILE_CELLS: usize = TILE_SIZE * TILE_SIZE;
+
+/// A board stored as tiles of [`TILE_SIZE`] by [`TILE_SIZE`] cells, tile
+/// row after tile row, each tile row after row. Tiles along the right and
+/// bottom edges hold dead cells beyond the board, which are never read.
+/// Cells are reached by `(row, col)` exactly like in a [`Matrix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TiledMatrix {
+    rows: usize,
+    cols: usize,
+    tile_cols: usize,
+    cells: Vec<u8>,
+}
+
+impl TiledMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let tile_cols = cols.div_ceil(TILE_SIZE);
+        TiledMatrix {
+            rows,
+            cols,
+            tile_cols,
+            cells: vec![0; rows.div_ceil(TILE_SIZE) * tile_cols * TILE_CELLS],
+        }
+    }
+
+    pub fn from_matrix(matrix: &Matrix) -> Self {
+        let mut tiled = TiledMatrix::new(matrix.row_count(), matrix.col_count());
+        tiled.load(matrix);
+        tiled
+    }
+
+    pub fn to_matrix(&self) -> Matrix {
+        let mut matrix = Matrix::new(self.rows, self.cols);
+        self.store(&mut matrix);
+        matrix
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.cols
+    }
+
+    fn idx(&self, row: usize, col: usize) -> usize {
+        let tile = row / TILE_SIZE * self.tile_cols + col / TILE_SIZE;
+        tile * TILE_CELLS + row % TILE_SIZE * TILE_SIZE + col % TILE_SIZE
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        debug_assert!(row < self.rows && col < self.cols);
+        self.cells[self.idx(row, col)]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, val: u8) {
+        debug_assert!(row < self.rows && col < self.cols);
+        let idx = self.idx(row, col);
+        self.cells[idx] = val;
+    }
+
+    /// State of the cell at `(row, col)`, which may lie outside the board,
+    /// like [`Edge::cell`].
+    pub fn cell(&self, edge: Edge, row: isize, col: isize) -> u8 {
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+        if (0..rows).contains(&row) && (0..cols).contains(&col) {
+            return self.get(row as usize, col as usize);
+        }
+
+        match edge {
+            Edge::Dead => 0,
+            Edge::Alive => 1,
+            Edge::Wrap => self.get(row.rem_euclid(rows) as usize, col.rem_euclid(cols) as usize),
+            Edge::Mirror => self.get(
+                row.clamp(0, rows - 1) as usize,
+                col.clamp(0, cols - 1) as usize,
+            ),
+        }
+    }
+
+    /// Copies the cells of `matrix`, which must have the same size.
+    pub fn load(&mut self, matrix: &Matrix) {
+        assert_eq!(
+            (matrix.row_count(), matrix.col_count()),
+            (self.rows, self.cols)
+        );
+        for (row, cells) in matrix.rows().enumerate() {
+            for (chunk, cells) in cells.chunks(TILE_SIZE).enumerate() {
+                let start = self.idx(row, chunk * TILE_SIZE);
+                self.cells[start..start + cells.len()].copy_from_slice(cells);
+            }
+        }
+    }
+
+    /// Copies the cells into `matrix`, which must have the same size.
+    pub fn store(&self, matrix: &mut Matrix) {
+        assert_eq!(
+            (matrix.row_count(), matrix.col_count()),
+            (self.rows, self.cols)
+        );
+        let cols = self.cols.max(1);
+        for (row, cells) in matrix.as_mut_slice().chunks_mut(cols).enumerate() {
+            for (chunk, cells) in cells.chunks_mut(TILE_SIZE).enumerate() {
+                let start = self.idx(row, chunk * TILE_SIZE);
+                cells.copy_from_slice(&self.cells[start..start + cells.len()]);
+            }
+        }
+    }
+}
+
+/// Tile after tile on copies of the board stored as a [`TiledMatrix`].
+pub struct Tiled {
+    current: TiledMatrix,
+    next: TiledMatrix,
+    /// A tile with a one cell wide border of its neighbors.
+    halo: Vec<u8>,
+}
+
+impl Tiled {
+    pub fn new() -> Self {
+        Tiled {
+            current: TiledMatrix::new(0, 0),
+            next: TiledMatrix::new(0, 0),
+            halo: vec![0; (TILE_SIZE + 2) * (TILE_SIZE + 2)],
+        }
+    }
+
+    /// Computes the next generation of the tile with its top-left cell at
+    /// `(first_row, first_col)`.
+    fn step_tile(&mut self, first_row: usize, first_col: usize, rule: &Rule) {
+        let current = &self.current;
+        let height = TILE_SIZE.min(current.rows - first_row);
+        let width = TILE_SIZE.min(current.cols - first_col);
+        let stride = TILE_SIZE + 2;
+        let tile = current.idx(first_row, first_col);
+
+        // the cells of the tile are copied whole rows at a time, only the
+        // border needs the neighboring tiles and the edge mode
+        let (top, left) = (first_row as isize - 1, first_col as isize - 1);
+        for row in 0..height + 2 {
+            let halo_row = &mut self.halo[row * stride..(row + 1) * stride];
+            if row == 0 || row == height + 1 {
+                for (col, cell) in halo_row[..width + 2].iter_mut().enumerate() {
+                    *cell = current.cell(rule.edge, top + row as isize, left + col as isize);
+                }
+                continue;
+            }
+            let start = tile + (row - 1) * TILE_SIZE;
+            halo_row[1..=width].copy_from_slice(&current.cells[start..start + width]);
+            halo_row[0] = current.cell(rule.edge, top + row as isize, left);
+            halo_row[width + 1] =
+                current.cell(rule.edge, top + row as isize, left + width as isize + 1);
+        }
+
+        let halo = &self.halo;
+        for row in 0..height {
+            let (above, center, below) = (row * stride, (row + 1) * stride, (row + 2) * stride);
+            let cells = &mut self.next.cells[tile + row * TILE_SIZE..][..width];
+            for (col, value) in cells.iter_mut().enumerate() {
+                let live_count = halo[above + col]
+                    + halo[above + col + 1]
+                    + halo[above + col + 2]
+                    + halo[center + col]
+                    + halo[center + col + 2]
+                    + halo[below + col]
+                    + halo[below + col + 1]
+                    + halo[below + col + 2];
+                *value = rule.next_state(halo[center + col + 1], live_count);
+            }
+        }
+    }
+}
+
+impl Default for Tiled {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickEngine for Tiled {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        let (rows, cols) = (src.row_count(), src.col_count());
+        if (self.current.rows, self.current.cols) != (rows, cols) {
+            self.current = TiledMatrix::new(rows, cols);
+            self.next = TiledMatrix::new(rows, cols);
+        }
+        self.current.load(src);
+
+        for first_row in (0..rows).step_by(TILE_SIZE) {
+            for first_col in (0..cols).step_by(TILE_SIZE) {
+                self.step_tile(first_row, first_col, rule);
+            }
+        }
+        self.next.store(dst);
+    }
+}
@@ -0,0 +1,240 @@
+//! Bounded grids whose edges are joined in other ways than a plain torus,
+//! described as in Golly: a letter for the kind of surface, then the width
+//! and height.
+//!
+//! - `P100,50` is a plane of 100 columns by 50 rows, dead beyond its edges.
+//! - `T100,50` is a torus, `T100+20,50` joins its top and bottom edges 20
+//!   columns apart and `T100,50+5` its left and right edges 5 rows apart.
+//! - `K100*,50` is a Klein bottle whose top and bottom edges are joined
+//!   reversed, `K100,50*` one whose left and right edges are.
+//! - `C100,50` is a cross-surface, both pairs of edges joined reversed.
+//! - `S50` is a sphere of 50 by 50 cells, its top edge joined to its left
+//!   edge and its bottom edge to its right edge.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::padded::PaddedMatrix;
+use crate::rule::Rule;
+
+/// How a pair of opposite edges is joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Join {
+    /// Not at all, cells beyond the edges are dead
+    Open,
+    /// Straight across, the cells reappearing shifted along the edge
+    Shifted(isize),
+    /// Reversed, a cell leaving near one end reappears near the other end
+    Twisted,
+}
+
+/// The bounded grid a board lives on and how its edges are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Topology {
+    pub cols: usize,
+    pub rows: usize,
+    /// How the top and bottom edges are joined.
+    pub horizontal: Join,
+    /// How the left and right edges are joined.
+    pub vertical: Join,
+    /// Whether the top edge is joined to the left one and the bottom edge to
+    /// the right one instead.
+    pub sphere: bool,
+}
+
+impl Topology {
+    /// The cell at `(row, col)`, which may lie beyond the edges, is the same
+    /// as this cell of the board, if any.
+    pub fn map(&self, row: isize, col: isize) -> Option<(usize, usize)> {
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+        let inside = |row: isize, col: isize| (0..rows).contains(&row) && (0..cols).contains(&col);
+        if inside(row, col) {
+            return Some((row as usize, col as usize));
+        }
+
+        // cells diagonally beyond the corners of a sphere touch no cell
+        if self.sphere {
+            let (row, col) = match (row, col) {
+                (-1, col) => (col, 0),
+                (row, -1) => (0, row),
+                (row, col) if row == rows => (col, cols - 1),
+                (row, _) => (rows - 1, row),
+            };
+            return inside(row, col).then_some((row as usize, col as usize));
+        }
+
+        let (mut row, mut col) = (row, col);
+        if !(0..rows).contains(&row) {
+            match self.horizontal {
+                Join::Open => return None,
+                Join::Shifted(shift) => col += if row < 0 { -shift } else { shift },
+                Join::Twisted => col = cols - 1 - col,
+            }
+            row = row.rem_euclid(rows);
+        }
+        if !(0..cols).contains(&col) {
+            match self.vertical {
+                Join::Open => return None,
+                Join::Shifted(shift) => row += if col < 0 { -shift } else { shift },
+                Join::Twisted => row = rows - 1 - row,
+            }
+            col = col.rem_euclid(cols);
+            // only a shift on the left and right edges moves the row beyond
+            // the top or bottom edges, which are then joined straight across
+            row = row.rem_euclid(rows);
+        }
+        Some((row as usize, col as usize))
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| format!("invalid topology {s:?}: {reason}");
+        let mut chars = s.trim().chars();
+        let kind = chars.next().ok_or_else(|| invalid("empty"))?;
+        let size = chars.as_str();
+
+        if kind.eq_ignore_ascii_case(&'S') {
+            let side: usize = size
+                .parse()
+                .map_err(|_| invalid("a sphere takes a single size, e.g. S50"))?;
+            if side == 0 {
+                return Err(invalid("the size must be positive"));
+            }
+            return Ok(Topology {
+                cols: side,
+                rows: side,
+                horizontal: Join::Open,
+                vertical: Join::Open,
+                sphere: true,
+            });
+        }
+
+        let (width, height) = size
+            .split_once(',')
+            .ok_or_else(|| invalid("expected WIDTH,HEIGHT after the kind, e.g. T100,50"))?;
+        let (cols, width_twisted, width_shift) =
+            parse_dimension(width).ok_or_else(|| invalid("bad width"))?;
+        let (rows, height_twisted, height_shift) =
+            parse_dimension(height).ok_or_else(|| invalid("bad height"))?;
+        if cols == 0 || rows == 0 {
+            return Err(invalid("the size must be positive"));
+        }
+
+        let shifted = width_shift != 0 || height_shift != 0;
+        let twisted = width_twisted || height_twisted;
+        let (horizontal, vertical) = match kind.to_ascii_uppercase() {
+            'P' if !shifted && !twisted => (Join::Open, Join::Open),
+            'T' if !twisted && (width_shift == 0 || height_shift == 0) => {
+                (Join::Shifted(width_shift), Join::Shifted(height_shift))
+            }
+            'K' if !shifted && width_twisted != height_twisted => match width_twisted {
+                true => (Join::Twisted, Join::Shifted(0)),
+                false => (Join::Shifted(0), Join::Twisted),
+            },
+            'C' if !shifted && !twisted => (Join::Twisted, Join::Twisted),
+            'P' | 'T' | 'K' | 'C' => {
+                return Err(invalid(
+                    "only tori take a shift, on one pair of edges, and Klein bottles a twist, on one pair of edges",
+                ))
+            }
+            _ => return Err(invalid("expected one of the kinds P, T, K, C or S")),
+        };
+        Ok(Topology {
+            cols,
+            rows,
+            horizontal,
+            vertical,
+            sphere: false,
+        })
+    }
+}
+
+/// Parses a dimension of a descriptor, the size followed by a twist `*`
+/// and a shift such as `+20` or `-5`.
+fn parse_dimension(s: &str) -> Option<(usize, bool, isize)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let size = s[..end].parse().ok()?;
+    let rest = &s[end..];
+    let (twisted, shift) = match rest.strip_prefix('*') {
+        Some(shift) => (true, shift),
+        None => (false, rest),
+    };
+    let shift = match shift {
+        "" => 0,
+        shift if shift.starts_with(['+', '-']) => shift.parse().ok()?,
+        _ => return None,
+    };
+    Some((size, twisted, shift))
+}
+
+/// Formats the descriptor the topology was parsed from, e.g. `T100+20,50`.
+impl Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sphere {
+            return write!(f, "S{}", self.cols);
+        }
+        let kind = match (self.horizontal, self.vertical) {
+            (Join::Open, _) | (_, Join::Open) => 'P',
+            (Join::Twisted, Join::Twisted) => 'C',
+            (Join::Twisted, _) | (_, Join::Twisted) => 'K',
+            _ => 'T',
+        };
+        let modifier = |join: Join| match join {
+            Join::Twisted if kind == 'K' => String::from("*"),
+            Join::Shifted(shift) if shift != 0 => format!("{shift:+}"),
+            _ => String::new(),
+        };
+        write!(
+            f,
+            "{kind}{}{},{}{}",
+            self.cols,
+            modifier(self.horizontal),
+            self.rows,
+            modifier(self.vertical)
+        )
+    }
+}
+
+/// Life-like rules on a board joined at its edges as the topology says,
+/// which takes the place of the edge mode of the rule.
+pub struct TopologyEngine {
+    topology: Topology,
+    padded: PaddedMatrix,
+}
+
+impl TopologyEngine {
+    pub fn new(topology: Topology) -> Self {
+        TopologyEngine {
+            topology,
+            padded: PaddedMatrix::new(0, 0),
+        }
+    }
+}
+
+impl TickEngine for TopologyEngine {
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        assert_eq!(
+            (src.row_count(), src.col_count()),
+            (self.topology.rows, self.topology.cols),
+            "the board does not have the size of topology {}",
+            self.topology
+        );
+        self.padded.resize(src.row_count(), src.col_count());
+        self.padded
+            .load_mapped(src, |row, col| self.topology.map(row, col));
+        let padded = &self.padded;
+
+        let cols = src.col_count();
+        for row in 0..src.row_count() {
+            let cells = &mut dst.as_mut_slice()[row * cols..(row + 1) * cols];
+            for (col, value) in cells.iter_mut().enumerate() {
+                *value = padded.next_state(row, col, rule);
+            }
+        }
+    }
+}
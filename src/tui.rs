@@ -0,0 +1,823 @@
+use crate::batch;
+use crate::edge::Edge;
+use crate::game_of_life::{GameOfLife, GameOfLifeArgs, FRAME_CHROME_LINES};
+use crate::matrix::{Matrix, Overflow};
+use crate::pacing::FramePacer;
+#[cfg(feature = "remote")]
+use crate::remote;
+use crate::render::cast::Cast;
+use crate::render::pipeline::RenderThread;
+use crate::render::terminal::{Screen, TerminalRenderer};
+use crate::render::text::Renderer;
+use crate::render::theme::Theme;
+use crate::render::Frame;
+use crate::rule::Rule;
+use crate::shutdown;
+use crate::simulator::Simulator;
+use clap::{Args, ValueEnum};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use crossterm::terminal;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io::{self, Stdout};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Longest time between two looks out for Ctrl-C and SIGTERM.
+const SIGNAL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time between two generations of the screensaver.
+const SCREENSAVER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Themes the screensaver takes turns with, a new one for every soup, unless
+/// a colored theme is chosen.
+const SCREENSAVER_THEMES: [Theme; 3] = [Theme::Fire, Theme::Ocean, Theme::Forest];
+
+/// Background color of the cell under the edit cursor.
+const CURSOR_COLOR: u8 = 244;
+
+/// Blank columns between games compared side by side.
+const COMPARE_GAP: usize = 3;
+
+#[derive(Args, Debug)]
+pub struct ViewArgs {
+    #[command(flatten)]
+    game: GameOfLifeArgs,
+    /// Size the random board to exactly fill the terminal, overrides rows and cols
+    #[arg(long, default_value_t = false)]
+    fit_terminal: bool,
+    /// How cells are drawn in the terminal
+    #[arg(long, value_enum, default_value_t = Renderer::Ascii)]
+    renderer: Renderer,
+    /// Color live cells in the terminal by how many generations they have been alive
+    #[arg(long, value_enum, default_value_t = Theme::Mono)]
+    theme: Theme,
+    /// Frames shown per second, fractions allowed, e.g. 0.5 for one every two seconds; the status line shows the rate achieved
+    #[arg(long, value_parser = parse_fps, default_value_t = 1.0, conflicts_with = "render_every")]
+    fps: f64,
+    /// Number of generations run per frame drawn, so that slow terminals don't hold back fast engines, also set with the 1 to 9 keys
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), conflicts_with = "render_every")]
+    step_size: u32,
+    /// File checkpoints are saved to and loaded from with the s and l keys
+    #[arg(long, default_value = "checkpoint.json")]
+    checkpoint: PathBuf,
+    /// Save a checkpoint of the final state to --checkpoint when quitting or interrupted, to pick up later with --resume
+    #[arg(long, default_value_t = false)]
+    save_on_exit: bool,
+    /// Fill the terminal with random soups, a new one in another color whenever the last stabilizes or cycles, until any key is pressed
+    #[arg(long, default_value_t = false)]
+    screensaver: bool,
+    /// Record the frames drawn and their timings to an asciinema v2 file, e.g. out.cast, to replay with asciinema play
+    #[arg(long)]
+    record_cast: Option<PathBuf>,
+    /// Run the same initial board under this rule too, side by side and in lockstep, e.g. B36/S23 to see where HighLife departs from Life
+    #[arg(long, conflicts_with_all = ["screensaver", "save_on_exit", "rule_file", "species", "rule_1d", "turmite", "topology"])]
+    compare_rule: Option<Rule>,
+    /// Run the same initial board with this edge mode too, side by side and in lockstep, under --compare-rule if also given
+    #[arg(long, value_enum, conflicts_with_all = ["screensaver", "save_on_exit", "rule_file", "species", "rule_1d", "turmite", "topology"])]
+    compare_edge: Option<Edge>,
+    /// Watch the generations broadcast by a simulation started with --serve, e.g. ws://host:9000, instead of running one
+    #[cfg(feature = "remote")]
+    #[arg(long)]
+    connect: Option<String>,
+}
+
+impl ViewArgs {
+    fn game(&self) -> GameOfLife {
+        let mut game = if self.fit_terminal {
+            let (columns, lines) = terminal::size().expect("io exception");
+            let (lines, columns) = match self.compared_rule() {
+                // half the terminal, below the label of the rule
+                Some(_) => (
+                    (lines as usize).saturating_sub(1),
+                    (columns as usize).saturating_sub(COMPARE_GAP) / 2,
+                ),
+                None => (lines as usize, columns as usize),
+            };
+            let (rows, cols) = self
+                .renderer
+                .fit(lines.saturating_sub(FRAME_CHROME_LINES), columns);
+            GameOfLife::from_args(&self.game.with_size(rows.max(1), cols.max(1)))
+        } else {
+            GameOfLife::from_args(&self.game)
+        };
+
+        game.set_renderer(self.renderer);
+        game.set_theme(self.theme);
+        game.set_step_size(self.step_size as usize);
+        game
+    }
+
+    /// The rule, edge mode included, the game is compared with when
+    /// `--compare-rule` or `--compare-edge` is given.
+    fn compared_rule(&self) -> Option<Rule> {
+        if self.compare_rule.is_none() && self.compare_edge.is_none() {
+            return None;
+        }
+        let rule = self.game.rule();
+        let edge = self.compare_edge.unwrap_or(rule.edge);
+        Some(self.compare_rule.unwrap_or(rule).with_edge(edge))
+    }
+
+    /// A game as large as the whole terminal, for the screensaver.
+    fn screensaver_game(&self) -> io::Result<GameOfLife> {
+        let (columns, lines) = terminal::size()?;
+        let (rows, cols) = self.renderer.fit(lines as usize, columns as usize);
+        let mut game = GameOfLife::from_args(&self.game.with_size(rows.max(1), cols.max(1)));
+        game.set_renderer(self.renderer);
+        game.set_step_size(self.step_size as usize);
+        Ok(game)
+    }
+
+    /// A recording of a terminal of the current size to `--record-cast`, if given.
+    fn cast(&self) -> io::Result<Option<Cast>> {
+        let Some(path) = &self.record_cast else {
+            return Ok(None);
+        };
+        let (columns, lines) = terminal::size()?;
+        Cast::create(path, columns, lines).map(Some)
+    }
+
+    /// The theme of the `soup`th soup of the screensaver.
+    fn screensaver_theme(&self, soup: usize) -> Theme {
+        match self.theme {
+            Theme::Mono => SCREENSAVER_THEMES[soup % SCREENSAVER_THEMES.len()],
+            theme => theme,
+        }
+    }
+}
+
+/// Shows the game described by `args` until it finishes, the user quits or
+/// a signal interrupts it, then prints a summary of the run.
+pub fn run(args: &ViewArgs) {
+    shutdown::catch_signals().expect("signal exception");
+    if args.screensaver {
+        screensaver(args).expect("io exception");
+        return;
+    }
+    #[cfg(feature = "remote")]
+    if let Some(url) = &args.connect {
+        remote::view(url, args.renderer).expect("websocket exception");
+        return;
+    }
+
+    let mut game = args.game();
+    let cast = args.cast().expect("io exception");
+    let (first_tick, started) = (game.ticks(), Instant::now());
+    if let Some(rule) = args.compared_rule() {
+        let other = game.fork(rule, args.game.build_engine(args.game.engine()));
+        let mut simulator = Simulator::new();
+        simulator.add_universe(game);
+        simulator.add_universe(other);
+        compare(&mut simulator, args.fps, cast).expect("io exception");
+        let summaries: Vec<String> = simulator
+            .universes()
+            .map(|(_, game)| {
+                let ticks = game.ticks().saturating_sub(first_tick);
+                let summary = batch::summary(game, ticks, started.elapsed());
+                format!("{}\n{summary}", label(game))
+            })
+            .collect();
+        print!("{}", summaries.join("\n"));
+        return;
+    }
+    interact(&mut game, args.fps, &args.checkpoint, cast).expect("io exception");
+
+    if args.save_on_exit {
+        game.save_checkpoint(&args.checkpoint)
+            .expect("checkpoint exception");
+        println!("saved checkpoint to {}", args.checkpoint.display());
+    }
+    let ticks = game.ticks().saturating_sub(first_tick);
+    print!("{}", batch::summary(&game, ticks, started.elapsed()));
+}
+
+/// Runs the game interactively until it finishes or the user quits.
+///
+/// Arrow keys pan the viewport, `z` zooms in, `x` zooms out and `q`, `Esc`
+/// or `Ctrl-C` quit. `e` pauses the simulation to edit cells: the arrow keys
+/// move a cursor, space or enter toggle the cell under it and `e` resumes.
+/// While editing, clicking a cell toggles it and dragging paints live cells.
+/// `b` and `f` pause the simulation and step one generation backwards or
+/// forwards, `p` pauses or resumes it and `1` to `9` set the number of
+/// generations run per frame, except in a time-lapse. `s` saves a
+/// checkpoint to `checkpoint` and `l` loads it back. Frames are shown `fps`
+/// times per second, or as fast as the generations of a time-lapse come,
+/// and recorded to `cast`, if given.
+pub fn interact(
+    game: &mut GameOfLife,
+    fps: f64,
+    checkpoint: &Path,
+    cast: Option<Cast>,
+) -> io::Result<()> {
+    let _screen = Screen::enter(true)?;
+    let mut renderer = TerminalRenderer::stdout();
+    if let Some(cast) = cast {
+        renderer.record(cast);
+    }
+    let renderer = RenderThread::spawn(renderer);
+    let pacer = pacer(game, fps);
+    let mut tui = Tui::new(game, renderer, pacer, checkpoint)?;
+    tui.event_loop()?;
+    tui.renderer.finish().map(drop)
+}
+
+/// Runs the universes of `simulator` side by side, in the order they were
+/// added, until all finish or the user quits, ticking them in lockstep; a
+/// universe that finishes first stays on its last generation. Arrow keys
+/// pan all views, `z` zooms in, `x` zooms out and `q`, `Esc` or `Ctrl-C`
+/// quit. `p` pauses or resumes all universes, `f` pauses them and steps
+/// each one generation forwards and `1` to `9` set the number of
+/// generations run per frame, except in a time-lapse. Frames are shown
+/// `fps` times per second, or as fast as the generations of a time-lapse
+/// come, and recorded to `cast`, if given.
+pub fn compare(simulator: &mut Simulator, fps: f64, cast: Option<Cast>) -> io::Result<()> {
+    let _screen = Screen::enter(false)?;
+    let mut renderer = TerminalRenderer::stdout();
+    if let Some(cast) = cast {
+        renderer.record(cast);
+    }
+    let renderer = RenderThread::spawn(renderer);
+    let pacer = match simulator.universes().next() {
+        Some((_, game)) => pacer(game, fps),
+        None => FramePacer::new(fps, Instant::now()),
+    };
+    let mut comparison = Comparison::new(simulator, renderer, pacer)?;
+    comparison.event_loop()?;
+    comparison.renderer.finish().map(drop)
+}
+
+/// Fills the terminal with random soups, sowing a new one whenever the last
+/// stabilizes or starts repeating, until any key is pressed.
+pub fn screensaver(args: &ViewArgs) -> io::Result<()> {
+    let _screen = Screen::enter(false)?;
+    run_screensaver(args)
+}
+
+fn run_screensaver(args: &ViewArgs) -> io::Result<()> {
+    let mut renderer = TerminalRenderer::stdout();
+    if let Some(cast) = args.cast()? {
+        renderer.record(cast);
+    }
+    let mut soup = 0;
+    let mut game = args.screensaver_game()?;
+    game.set_theme(args.screensaver_theme(soup));
+    let mut rng = StdRng::seed_from_u64(game.seed());
+
+    loop {
+        renderer.draw(game.cells_frame())?;
+
+        if shutdown::requested() {
+            return Ok(());
+        }
+        if event::poll(SCREENSAVER_INTERVAL)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => return Ok(()),
+                Event::Resize(..) => {
+                    game = args.screensaver_game()?;
+                    game.set_theme(args.screensaver_theme(soup));
+                    renderer.invalidate();
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if game.cycle().is_some() {
+            soup += 1;
+            let (rows, cols) = (game.matrix().row_count(), game.matrix().col_count());
+            let matrix = Matrix::random(rows, cols, args.game.fill_prob(), &mut rng);
+            game.place_pattern(&matrix, 0, 0, Overflow::Clip);
+            game.set_theme(args.screensaver_theme(soup));
+        } else {
+            game.step();
+        }
+    }
+}
+
+struct Tui<'a> {
+    game: &'a mut GameOfLife,
+    /// Draws the frames while the simulation goes on, dropping frames while
+    /// the terminal can't keep up.
+    renderer: RenderThread<Stdout>,
+    pacer: FramePacer,
+    /// Terminal size in lines and columns.
+    size: (usize, usize),
+    /// Position of the edit cursor relative to the top-left cell of the
+    /// initial board, the simulation is paused while editing.
+    cursor: Option<(isize, isize)>,
+    paused: bool,
+    checkpoint: &'a Path,
+    /// Shown in place of the key help until the next key press.
+    message: Option<String>,
+}
+
+impl<'a> Tui<'a> {
+    fn new(
+        game: &'a mut GameOfLife,
+        renderer: RenderThread<Stdout>,
+        pacer: FramePacer,
+        checkpoint: &'a Path,
+    ) -> io::Result<Self> {
+        let (columns, lines) = terminal::size()?;
+        let mut tui = Tui {
+            game,
+            renderer,
+            pacer,
+            size: (lines as usize, columns as usize),
+            cursor: None,
+            paused: false,
+            checkpoint,
+            message: None,
+        };
+        tui.fit();
+        Ok(tui)
+    }
+
+    fn event_loop(&mut self) -> io::Result<()> {
+        self.pacer.restart(Instant::now());
+        let mut ticked = false;
+
+        loop {
+            let finished = !self.is_paused() && self.game.is_finished();
+            // the frames of generations may be dropped, not the last one or
+            // the ones showing what the user did
+            match ticked && !finished {
+                true => self.offer()?,
+                false => self.draw()?,
+            }
+            if finished {
+                return Ok(());
+            }
+
+            // waits for the next generation or an event, looking out for
+            // signals in between
+            let event = loop {
+                if shutdown::requested() {
+                    return Ok(());
+                }
+                let timeout = match self.is_paused() {
+                    true => SIGNAL_INTERVAL,
+                    false => self.pacer.remaining(Instant::now()).min(SIGNAL_INTERVAL),
+                };
+                if event::poll(timeout)? {
+                    break Some(event::read()?);
+                }
+                if !self.is_paused() && self.pacer.is_due(Instant::now()) {
+                    break None;
+                }
+            };
+            let Some(event) = event else {
+                self.pacer.frame_shown(Instant::now());
+                self.game.step();
+                ticked = true;
+                continue;
+            };
+            ticked = false;
+
+            let was_paused = self.is_paused();
+            match event {
+                Event::Key(key) if key.kind == KeyEventKind::Press && !self.handle_key(key) => {
+                    return Ok(());
+                }
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                Event::Resize(columns, lines) => {
+                    self.size = (lines as usize, columns as usize);
+                    self.fit();
+                }
+                _ => {}
+            }
+
+            // a full interval passes between resuming and the next generation
+            if was_paused && !self.is_paused() {
+                self.pacer.restart(Instant::now());
+            }
+        }
+    }
+
+    /// Whether generations are on hold, either explicitly or while editing.
+    fn is_paused(&self) -> bool {
+        self.paused || self.cursor.is_some()
+    }
+
+    /// Draws the current frame, waiting for the terminal if it is busy.
+    fn draw(&mut self) -> io::Result<()> {
+        let frame = self.frame();
+        self.renderer.draw(frame)
+    }
+
+    /// Draws the current frame unless the terminal is still busy with an
+    /// earlier one.
+    fn offer(&mut self) -> io::Result<()> {
+        match self.renderer.ready() {
+            true => self.draw(),
+            false => Ok(()),
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = self.game.frame();
+
+        if let Some((row, col)) = self.cursor {
+            self.game.highlight(&mut frame, row, col, CURSOR_COLOR);
+        }
+
+        let status = match (&self.message, self.cursor) {
+            (Some(message), _) => message.as_str(),
+            (None, Some(_)) => "editing: arrows move, space toggles, e resumes",
+            (None, None) if self.paused => {
+                "paused: b/f step back/forward, p resumes, s/l save/load, e edits, q quits"
+            }
+            (None, None) => {
+                "arrows pan, z/x zoom, b/f step, 1-9 speed, p pauses, s/l save/load, e edits, q quits"
+            }
+        };
+        match self.pacer.achieved_fps().filter(|_| !self.is_paused()) {
+            Some(fps) => match self.renderer.dropped() {
+                0 => frame.push_text(&format!("{fps:.1} fps  {status}")),
+                dropped => frame.push_text(&format!("{fps:.1} fps, {dropped} dropped  {status}")),
+            },
+            None => frame.push_text(status),
+        }
+        frame
+    }
+
+    /// Applies a key press, returns false when the user asked to quit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.message = None;
+        if let Some(cursor) = self.cursor {
+            return self.handle_edit_key(key, cursor);
+        }
+
+        let time_lapse = self.game.render_every() > 1;
+        let viewport = self.game.viewport_mut();
+        let step_rows = (viewport.rows as isize / 4).max(1);
+        let step_cols = (viewport.cols as isize / 4).max(1);
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('e') => self.start_editing(),
+            KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('b') => {
+                self.paused = true;
+                self.game.rewind();
+            }
+            KeyCode::Char('f') => {
+                self.paused = true;
+                self.game.forward();
+            }
+            KeyCode::Char('s') => self.save_checkpoint(),
+            KeyCode::Char('l') => self.load_checkpoint(),
+            KeyCode::Char(digit @ '1'..='9') if !time_lapse => {
+                let step_size = digit.to_digit(10).unwrap() as usize;
+                self.game.set_step_size(step_size);
+                self.message = Some(format!("{step_size} generations per frame"));
+            }
+            KeyCode::Up => viewport.pan(-step_rows, 0),
+            KeyCode::Down => viewport.pan(step_rows, 0),
+            KeyCode::Left => viewport.pan(0, -step_cols),
+            KeyCode::Right => viewport.pan(0, step_cols),
+            KeyCode::Char('z') => {
+                let zoom = viewport.zoom / 2;
+                viewport.set_zoom(zoom);
+                self.fit();
+            }
+            KeyCode::Char('x') => {
+                let zoom = viewport.zoom * 2;
+                viewport.set_zoom(zoom);
+                self.fit();
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Applies a key press while editing, returns false when the user asked to quit.
+    fn handle_edit_key(&mut self, key: KeyEvent, (row, col): (isize, isize)) -> bool {
+        let zoom = self.game.viewport().zoom as isize;
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('q') => return false,
+            KeyCode::Char('e') | KeyCode::Esc => self.cursor = None,
+            KeyCode::Char(' ') | KeyCode::Enter => self.game.toggle_cell(row, col),
+            KeyCode::Up => self.move_cursor(row - zoom, col),
+            KeyCode::Down => self.move_cursor(row + zoom, col),
+            KeyCode::Left => self.move_cursor(row, col - zoom),
+            KeyCode::Right => self.move_cursor(row, col + zoom),
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Toggles the clicked cell and paints the cells dragged over while editing.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.cursor.is_none() {
+            return;
+        }
+
+        let Some((row, col)) = self
+            .game
+            .cell_on_screen(mouse.row as usize, mouse.column as usize)
+        else {
+            return;
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.game.toggle_cell(row, col);
+                self.move_cursor(row, col);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                self.game.set_cell(row, col, true);
+                self.move_cursor(row, col);
+            }
+            _ => {}
+        }
+    }
+
+    fn save_checkpoint(&mut self) {
+        let path = self.checkpoint.display();
+        self.message = Some(match self.game.save_checkpoint(self.checkpoint) {
+            Ok(()) => format!("saved checkpoint to {path}"),
+            Err(err) => format!("failed to save checkpoint to {path}: {err}"),
+        });
+    }
+
+    fn load_checkpoint(&mut self) {
+        let path = self.checkpoint.display();
+        self.message = Some(match self.game.restore_checkpoint(self.checkpoint) {
+            Ok(()) => format!("loaded checkpoint from {path}"),
+            Err(err) => format!("failed to load checkpoint from {path}: {err}"),
+        });
+        // the checkpointed board may have another size
+        self.fit();
+    }
+
+    /// Pauses the simulation and puts the edit cursor in the middle of the view.
+    fn start_editing(&mut self) {
+        let viewport = self.game.viewport();
+        let row = viewport.row + (viewport.rows * viewport.zoom / 2) as isize;
+        let col = viewport.col + (viewport.cols * viewport.zoom / 2) as isize;
+        self.move_cursor(row, col);
+
+        // the middle of the view may lie outside of a board smaller than it
+        if self.cursor.is_none() && self.game.cell_at(0, 0).is_some() {
+            self.move_cursor(0, 0);
+        }
+    }
+
+    /// Moves the edit cursor to a cell of the universe and keeps it in view.
+    fn move_cursor(&mut self, row: isize, col: isize) {
+        if self.game.cell_at(row, col).is_some() {
+            self.cursor = Some((row, col));
+            self.game.viewport_mut().scroll_to(row, col);
+        }
+    }
+
+    /// Sizes the viewport to the terminal and redraws everything.
+    fn fit(&mut self) {
+        self.game.fit_viewport(self.size.0, self.size.1);
+        self.renderer.invalidate();
+    }
+}
+
+/// Universes ticked in lockstep and drawn side by side.
+struct Comparison<'a> {
+    simulator: &'a mut Simulator,
+    renderer: RenderThread<Stdout>,
+    pacer: FramePacer,
+    /// Terminal size in lines and columns.
+    size: (usize, usize),
+    paused: bool,
+    /// Shown in place of the key help until the next key press.
+    message: Option<String>,
+}
+
+impl<'a> Comparison<'a> {
+    fn new(
+        simulator: &'a mut Simulator,
+        renderer: RenderThread<Stdout>,
+        pacer: FramePacer,
+    ) -> io::Result<Self> {
+        let (columns, lines) = terminal::size()?;
+        let mut comparison = Comparison {
+            simulator,
+            renderer,
+            pacer,
+            size: (lines as usize, columns as usize),
+            paused: false,
+            message: None,
+        };
+        comparison.fit();
+        Ok(comparison)
+    }
+
+    fn event_loop(&mut self) -> io::Result<()> {
+        self.pacer.restart(Instant::now());
+        let mut ticked = false;
+
+        loop {
+            let finished = !self.paused && self.is_finished();
+            match ticked && !finished {
+                true => self.offer()?,
+                false => self.draw()?,
+            }
+            if finished {
+                return Ok(());
+            }
+
+            let event = loop {
+                if shutdown::requested() {
+                    return Ok(());
+                }
+                let timeout = match self.paused {
+                    true => SIGNAL_INTERVAL,
+                    false => self.pacer.remaining(Instant::now()).min(SIGNAL_INTERVAL),
+                };
+                if event::poll(timeout)? {
+                    break Some(event::read()?);
+                }
+                if !self.paused && self.pacer.is_due(Instant::now()) {
+                    break None;
+                }
+            };
+            let Some(event) = event else {
+                self.pacer.frame_shown(Instant::now());
+                self.step();
+                ticked = true;
+                continue;
+            };
+            ticked = false;
+
+            let was_paused = self.paused;
+            match event {
+                Event::Key(key) if key.kind == KeyEventKind::Press && !self.handle_key(key) => {
+                    return Ok(());
+                }
+                Event::Resize(columns, lines) => {
+                    self.size = (lines as usize, columns as usize);
+                    self.fit();
+                }
+                _ => {}
+            }
+
+            if was_paused && !self.paused {
+                self.pacer.restart(Instant::now());
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.simulator.is_finished()
+    }
+
+    fn is_time_lapse(&self) -> bool {
+        self.simulator
+            .universes()
+            .any(|(_, game)| game.render_every() > 1)
+    }
+
+    /// Runs the generations of a frame in the universes still running.
+    fn step(&mut self) {
+        self.simulator.step_all();
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let frame = self.frame();
+        self.renderer.draw(frame)
+    }
+
+    fn offer(&mut self) -> io::Result<()> {
+        match self.renderer.ready() {
+            true => self.draw(),
+            false => Ok(()),
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = self
+            .simulator
+            .universes()
+            .map(|(_, game)| {
+                let mut frame = Frame::default();
+                frame.push_text(&label(game));
+                frame.lines.extend(game.frame().lines);
+                frame
+            })
+            .reduce(|left, right| left.beside(right, COMPARE_GAP))
+            .unwrap_or_default();
+
+        let status = match &self.message {
+            Some(message) => message.as_str(),
+            None if self.paused => "paused: f steps forward, p resumes, q quits",
+            None => "arrows pan, z/x zoom, f steps, 1-9 speed, p pauses, q quits",
+        };
+        match self.pacer.achieved_fps().filter(|_| !self.paused) {
+            Some(fps) => frame.push_text(&format!("{fps:.1} fps  {status}")),
+            None => frame.push_text(status),
+        }
+        frame
+    }
+
+    /// Applies a key press to all universes, returns false when the user asked to quit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.message = None;
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('f') => {
+                self.paused = true;
+                for (_, game) in self.simulator.universes_mut() {
+                    if !game.is_finished() {
+                        game.forward();
+                    }
+                }
+            }
+            KeyCode::Char(digit @ '1'..='9') if !self.is_time_lapse() => {
+                let step_size = digit.to_digit(10).unwrap() as usize;
+                for (_, game) in self.simulator.universes_mut() {
+                    game.set_step_size(step_size);
+                }
+                self.message = Some(format!("{step_size} generations per frame"));
+            }
+            KeyCode::Char('z') => self.zoom(|zoom| zoom / 2),
+            KeyCode::Char('x') => self.zoom(|zoom| zoom * 2),
+            KeyCode::Up => self.pan(-1, 0),
+            KeyCode::Down => self.pan(1, 0),
+            KeyCode::Left => self.pan(0, -1),
+            KeyCode::Right => self.pan(0, 1),
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Pans all views a quarter of their size up or down as `rows` is
+    /// negative or positive, and left or right as `cols` is.
+    fn pan(&mut self, rows: isize, cols: isize) {
+        for (_, game) in self.simulator.universes_mut() {
+            let viewport = game.viewport_mut();
+            let step_rows = (viewport.rows as isize / 4).max(1);
+            let step_cols = (viewport.cols as isize / 4).max(1);
+            viewport.pan(rows * step_rows, cols * step_cols);
+        }
+    }
+
+    fn zoom(&mut self, by: impl Fn(usize) -> usize) {
+        for (_, game) in self.simulator.universes_mut() {
+            let viewport = game.viewport_mut();
+            viewport.set_zoom(by(viewport.zoom));
+        }
+        self.fit();
+    }
+
+    /// Shares the width of the terminal out between the viewports and
+    /// redraws everything.
+    fn fit(&mut self) {
+        let (lines, columns) = self.size;
+        let count = self.simulator.len().max(1);
+        let columns = columns.saturating_sub(COMPARE_GAP * (count - 1)) / count;
+        for (_, game) in self.simulator.universes_mut() {
+            // a line above every game shows its rule
+            game.fit_viewport(lines.saturating_sub(1), columns);
+        }
+        self.renderer.invalidate();
+    }
+}
+
+/// Frames at `fps` per second, or as fast as the generations drawn by a
+/// time-lapse of `game` come.
+fn pacer(game: &GameOfLife, fps: f64) -> FramePacer {
+    match game.render_every() {
+        1 => FramePacer::new(fps, Instant::now()),
+        _ => FramePacer::unlimited(Instant::now()),
+    }
+}
+
+/// The rule and edge mode of a compared game, shown above it.
+fn label(game: &GameOfLife) -> String {
+    let rule = game.rule();
+    let edge = rule
+        .edge
+        .to_possible_value()
+        .expect("edge modes have names");
+    format!("{rule}, {} edges", edge.get_name())
+}
+
+/// Parses a positive number of frames per second on the command line.
+fn parse_fps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(fps) if fps > 0.0 && fps <= 1000.0 => Ok(fps),
+        _ => Err(format!(
+            "invalid frame rate {s:?}, expected a number above 0 and up to 1000"
+        )),
+    }
+}
@@ -0,0 +1,364 @@
+//! Turmites, such as Langton's ant: ants walking over a board of colored
+//! cells. Every tick each ant looks at the color of its cell and, by a
+//! state table, paints the cell, turns, changes its state and steps forward.
+//!
+//! Tables are written like Langton's ant generalizations, one turn per
+//! color, e.g. `RL` for Langton's ant or `LLRR`, where each color is painted
+//! over with the next one, or in Golly's turmite notation, a list of states
+//! each listing, for every color, the color painted, the turn (1 none,
+//! 2 right, 4 U-turn, 8 left) and the next state:
+//!
+//! ```text
+//! {{{1, 8, 1}, {1, 8, 1}}, {{1, 2, 1}, {0, 1, 0}}}
+//! ```
+//!
+//! The board holds the ants along with the colors, so history, checkpoints
+//! and board files keep them: a cell is its color plus the number of colors
+//! times 1 + 4 × the state + the direction of the ant on it, if any.
+
+use core::str::FromStr;
+
+use rand::Rng;
+
+use crate::edge::Edge;
+use crate::engine::TickEngine;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    None,
+    Right,
+    UTurn,
+    Left,
+}
+
+impl Turn {
+    /// The turn of a Golly turmite code.
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Turn::None),
+            2 => Some(Turn::Right),
+            4 => Some(Turn::UTurn),
+            8 => Some(Turn::Left),
+            _ => None,
+        }
+    }
+
+    /// The turn of a letter of Langton's ant notation.
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter.to_ascii_uppercase() {
+            'N' => Some(Turn::None),
+            'R' => Some(Turn::Right),
+            'U' => Some(Turn::UTurn),
+            'L' => Some(Turn::Left),
+            _ => None,
+        }
+    }
+
+    /// Number of quarter turns clockwise.
+    fn quarters(self) -> u8 {
+        match self {
+            Turn::None => 0,
+            Turn::Right => 1,
+            Turn::UTurn => 2,
+            Turn::Left => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn(self, turn: Turn) -> Self {
+        Direction::ALL[(self as usize + usize::from(turn.quarters())) % 4]
+    }
+
+    /// Row and column offsets of a step forward.
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ant {
+    pub state: u8,
+    pub direction: Direction,
+}
+
+/// What an ant does on a cell of some color in some state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// The color the cell is painted.
+    pub color: u8,
+    pub turn: Turn,
+    /// The state of the ant afterwards.
+    pub state: u8,
+}
+
+/// The state table of a turmite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turmite {
+    colors: u8,
+    states: u8,
+    /// The transitions of every state, one per color.
+    transitions: Vec<Transition>,
+}
+
+impl Turmite {
+    /// Langton's ant, `RL`: turn right on white, left on black, and flip the color.
+    pub fn langton() -> Self {
+        "RL".parse().expect("valid turmite")
+    }
+
+    fn new(colors: u8, states: u8, transitions: Vec<Transition>) -> Result<Self, String> {
+        // the largest cell is the last color under an ant in the last state facing west
+        if usize::from(colors) * (1 + 4 * usize::from(states)) > 256 {
+            return Err(format!(
+                "turmite with {colors} colors and {states} states does not fit in a cell"
+            ));
+        }
+        Ok(Turmite {
+            colors,
+            states,
+            transitions,
+        })
+    }
+
+    pub fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    pub fn transition(&self, state: u8, color: u8) -> Transition {
+        self.transitions[usize::from(state) * usize::from(self.colors) + usize::from(color)]
+    }
+
+    /// The cell of `color` with `ant` on it.
+    pub fn encode(&self, color: u8, ant: Option<Ant>) -> u8 {
+        let ant = ant.map_or(0, |ant| 1 + ant.state * 4 + ant.direction as u8);
+        color + self.colors * ant
+    }
+
+    /// The color of `cell` and the ant on it. Colors beyond the table wrap
+    /// around and ants in states beyond it are ignored.
+    pub fn decode(&self, cell: u8) -> (u8, Option<Ant>) {
+        let (color, ant) = (cell % self.colors, cell / self.colors);
+        let ant = (ant != 0 && ant <= 4 * self.states).then(|| Ant {
+            state: (ant - 1) / 4,
+            direction: Direction::ALL[usize::from((ant - 1) % 4)],
+        });
+        (color, ant)
+    }
+
+    /// Puts `count` ants in their first state on `matrix`, the first in the
+    /// middle facing north, the others on random cells facing random ways.
+    /// Boards that already hold ants, e.g. saved from an earlier run, are
+    /// left as they are.
+    pub fn place_ants<R: Rng>(&self, matrix: &mut Matrix, count: usize, rng: &mut R) {
+        let (rows, cols) = (matrix.row_count(), matrix.col_count());
+        if rows == 0
+            || cols == 0
+            || matrix
+                .as_slice()
+                .iter()
+                .any(|&cell| self.decode(cell).1.is_some())
+        {
+            return;
+        }
+
+        for idx in 0..count {
+            let (row, col, direction) = match idx {
+                0 => (rows / 2, cols / 2, Direction::North),
+                _ => (
+                    rng.gen_range(0..rows),
+                    rng.gen_range(0..cols),
+                    Direction::ALL[rng.gen_range(0..4)],
+                ),
+            };
+            let (color, _) = self.decode(matrix.get(row, col));
+            let ant = Ant {
+                state: 0,
+                direction,
+            };
+            matrix.set(row, col, self.encode(color, Some(ant)));
+        }
+    }
+}
+
+/// Parses a turmite in Langton's ant notation, e.g. `RL`, or Golly's
+/// turmite notation.
+impl FromStr for Turmite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('{') {
+            return parse_table(s);
+        }
+
+        let invalid =
+            || format!("invalid turmite {s:?}, expected turns such as RL or a Golly turmite table");
+        let turns = s
+            .chars()
+            .map(|letter| Turn::from_letter(letter).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let colors = u8::try_from(turns.len()).map_err(|_| invalid())?;
+        if colors == 0 {
+            return Err(invalid());
+        }
+
+        let transitions = turns
+            .iter()
+            .enumerate()
+            .map(|(color, &turn)| Transition {
+                color: (color as u8 + 1) % colors,
+                turn,
+                state: 0,
+            })
+            .collect();
+        Turmite::new(colors, 1, transitions)
+    }
+}
+
+/// Parses a table in Golly's turmite notation.
+fn parse_table(s: &str) -> Result<Turmite, String> {
+    let invalid = |reason: &str| format!("invalid turmite table {s:?}, {reason}");
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let body = compact
+        .strip_prefix("{{{")
+        .and_then(|body| body.strip_suffix("}}}"))
+        .ok_or_else(|| invalid("expected {{{color, turn, state}, ...}, ...}"))?;
+
+    let states: Vec<Vec<[u8; 3]>> = body
+        .split("}},{{")
+        .map(|state| {
+            state
+                .split("},{")
+                .map(|transition| {
+                    let numbers = transition
+                        .split(',')
+                        .map(|number| number.parse::<u8>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| invalid("expected numbers"))?;
+                    <[u8; 3]>::try_from(numbers)
+                        .map_err(|_| invalid("expected three numbers per transition"))
+                })
+                .collect()
+        })
+        .collect::<Result<_, String>>()?;
+
+    let colors = states[0].len();
+    if states.iter().any(|state| state.len() != colors) {
+        return Err(invalid("every state needs a transition for every color"));
+    }
+    let (colors, count) = match (u8::try_from(colors), u8::try_from(states.len())) {
+        (Ok(colors), Ok(count)) => (colors, count),
+        _ => return Err(invalid("too many colors or states")),
+    };
+
+    let transitions = states
+        .iter()
+        .flatten()
+        .map(|&[color, turn, state]| match Turn::from_code(turn) {
+            Some(turn) if color < colors && state < count => Ok(Transition { color, turn, state }),
+            Some(_) => Err(invalid("colors and states are numbered from 0")),
+            None => Err(invalid("turns are 1, 2, 4 or 8")),
+        })
+        .collect::<Result<_, _>>()?;
+    Turmite::new(colors, count, transitions)
+}
+
+/// Moves the ants of a turmite over the board.
+pub struct TurmiteEngine {
+    turmite: Turmite,
+    /// The ants of the current tick, reused between ticks.
+    moves: Vec<(usize, Ant)>,
+}
+
+impl TurmiteEngine {
+    pub fn new(turmite: Turmite) -> Self {
+        TurmiteEngine {
+            turmite,
+            moves: Vec::new(),
+        }
+    }
+
+    pub fn turmite(&self) -> &Turmite {
+        &self.turmite
+    }
+}
+
+impl TickEngine for TurmiteEngine {
+    /// The births and survivals of the rule are ignored. Ants walking over a
+    /// wrapping edge come back on the other side, other edges turn them
+    /// about. An ant walking onto the cell of another ant replaces it.
+    fn step(&mut self, src: &Matrix, dst: &mut Matrix, rule: &Rule) {
+        let (rows, cols) = (src.row_count() as isize, src.col_count() as isize);
+        let turmite = &self.turmite;
+
+        self.moves.clear();
+        for (idx, (value, &cell)) in dst
+            .as_mut_slice()
+            .iter_mut()
+            .zip(src.as_slice())
+            .enumerate()
+        {
+            let (color, ant) = turmite.decode(cell);
+            *value = color;
+            let Some(ant) = ant else {
+                continue;
+            };
+
+            let transition = turmite.transition(ant.state, color);
+            *value = transition.color;
+            let mut direction = ant.direction.turn(transition.turn);
+            let (row, col) = src.inverse_idx(idx);
+            let (d_row, d_col) = direction.offset();
+            let (mut row, mut col) = (row as isize + d_row, col as isize + d_col);
+            if !(0..rows).contains(&row) || !(0..cols).contains(&col) {
+                match rule.edge {
+                    Edge::Wrap => (row, col) = (row.rem_euclid(rows), col.rem_euclid(cols)),
+                    _ => {
+                        (row, col) = (row - d_row, col - d_col);
+                        direction = direction.turn(Turn::UTurn);
+                    }
+                }
+            }
+
+            let ant = Ant {
+                state: transition.state,
+                direction,
+            };
+            self.moves
+                .push((row as usize * cols as usize + col as usize, ant));
+        }
+
+        let cells = dst.as_mut_slice();
+        for &(idx, ant) in &self.moves {
+            let (color, _) = turmite.decode(cells[idx]);
+            cells[idx] = turmite.encode(color, Some(ant));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use clap::Args;
+use std::process::ExitCode;
+
+use crate::engine::EngineKind;
+use crate::game_of_life::{GameOfLife, GameOfLifeArgs};
+use crate::matrix::Matrix;
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    game: GameOfLifeArgs,
+    /// Engine the one selected with --engine is compared with
+    #[arg(long, value_enum, default_value_t = EngineKind::Serial)]
+    reference: EngineKind,
+    /// Number of generations to compare
+    #[arg(long, default_value_t = 1000)]
+    ticks: usize,
+}
+
+/// Ticks the initial board with both engines and reports the first
+/// generation and cell where they diverge, failing if they do.
+pub fn run(args: &VerifyArgs) -> ExitCode {
+    let mut game = GameOfLife::from_args(&args.game);
+    game.set_history(0);
+
+    // the reference resumes from the state of the game, so that both start
+    // from the same board, seed and rule even if no seed was given
+    let mut reference = GameOfLife::new(Matrix::new(0, 0));
+    reference.resume(game.checkpoint());
+    reference.set_engine(args.game.build_engine(args.reference));
+
+    let engine = args.game.engine();
+    for _ in 0..args.ticks {
+        game.tick();
+        reference.tick();
+        // equal boards hash equal, so only boards whose hashes differ are
+        // looked at cell by cell
+        if game.state_hash() == reference.state_hash() {
+            continue;
+        }
+
+        if let Some(divergence) = divergence(game.matrix(), reference.matrix()) {
+            println!(
+                "{engine:?} and {:?} diverge at tick {}: {divergence}",
+                args.reference,
+                game.ticks()
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    println!(
+        "{engine:?} and {:?} agree for {} ticks, seed {}, final state hash {:016x}",
+        args.reference,
+        args.ticks,
+        game.seed(),
+        game.state_hash()
+    );
+    ExitCode::SUCCESS
+}
+
+/// Where `actual` first differs from `expected`, if anywhere.
+fn divergence(actual: &Matrix, expected: &Matrix) -> Option<String> {
+    if (actual.row_count(), actual.col_count()) != (expected.row_count(), expected.col_count()) {
+        return Some(format!(
+            "board of {} x {} cells instead of {} x {}",
+            actual.row_count(),
+            actual.col_count(),
+            expected.row_count(),
+            expected.col_count()
+        ));
+    }
+
+    let idx = actual
+        .as_slice()
+        .iter()
+        .zip(expected.as_slice())
+        .position(|(actual, expected)| actual != expected)?;
+    let (row, col) = actual.inverse_idx(idx);
+    Some(format!(
+        "cell ({row}, {col}) is {} instead of {}",
+        actual.as_slice()[idx],
+        expected.as_slice()[idx]
+    ))
+}
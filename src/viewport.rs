@@ -0,0 +1,130 @@
+use crate::matrix::Matrix;
+
+/// A window onto the universe. Coordinates are relative to the top-left cell
+/// of the initial matrix, so they stay stable while an unbounded universe grows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub row: isize,
+    pub col: isize,
+    /// Number of rows and columns of displayed cells.
+    pub rows: usize,
+    pub cols: usize,
+    /// Width and height of the block of universe cells aggregated into one
+    /// displayed cell, which is alive if any cell of its block is.
+    pub zoom: usize,
+}
+
+impl Viewport {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Viewport {
+            row: 0,
+            col: 0,
+            rows,
+            cols,
+            zoom: 1,
+        }
+    }
+
+    /// Whether the viewport shows exactly the matrix, cell by cell.
+    pub fn covers<T: Copy + Default>(&self, matrix: &Matrix<T>, origin: (usize, usize)) -> bool {
+        self.zoom == 1
+            && self.row == -(origin.0 as isize)
+            && self.col == -(origin.1 as isize)
+            && self.rows == matrix.row_count()
+            && self.cols == matrix.col_count()
+    }
+
+    /// Moves the viewport by a number of displayed cells.
+    pub fn pan(&mut self, rows: isize, cols: isize) {
+        let zoom = self.zoom as isize;
+        self.row += rows * zoom;
+        self.col += cols * zoom;
+    }
+
+    /// The displayed cell showing the universe cell at `row`, `col`, if visible.
+    pub fn cell_position(&self, row: isize, col: isize) -> Option<(usize, usize)> {
+        let zoom = self.zoom as isize;
+        let (row, col) = (
+            (row - self.row).div_euclid(zoom),
+            (col - self.col).div_euclid(zoom),
+        );
+
+        let visible =
+            (0..self.rows as isize).contains(&row) && (0..self.cols as isize).contains(&col);
+        visible.then_some((row as usize, col as usize))
+    }
+
+    /// The top-left universe cell shown by the displayed cell at `row`, `col`,
+    /// if the viewport has such a cell.
+    pub fn universe_position(&self, row: usize, col: usize) -> Option<(isize, isize)> {
+        let zoom = self.zoom as isize;
+        (row < self.rows && col < self.cols).then(|| {
+            (
+                self.row + row as isize * zoom,
+                self.col + col as isize * zoom,
+            )
+        })
+    }
+
+    /// Pans the least amount that brings the universe cell at `row`, `col` into view.
+    pub fn scroll_to(&mut self, row: isize, col: isize) {
+        let height = (self.rows * self.zoom) as isize;
+        let width = (self.cols * self.zoom) as isize;
+
+        self.row = self.row.clamp(row - height + 1, row);
+        self.col = self.col.clamp(col - width + 1, col);
+    }
+
+    /// Sets the zoom level, keeping the center of the viewport in place.
+    pub fn set_zoom(&mut self, zoom: usize) {
+        let zoom = zoom.max(1);
+        let (center_row, center_col) = (
+            self.row + (self.rows * self.zoom / 2) as isize,
+            self.col + (self.cols * self.zoom / 2) as isize,
+        );
+
+        self.zoom = zoom;
+        self.row = center_row - (self.rows * zoom / 2) as isize;
+        self.col = center_col - (self.cols * zoom / 2) as isize;
+    }
+
+    /// Copies the visible region out of `matrix`, whose initial top-left cell
+    /// now lies at `origin`. Cells outside of `matrix` are dead, and zoomed
+    /// blocks take the largest value of their cells.
+    pub fn extract<T: Copy + Default + Ord>(
+        &self,
+        matrix: &Matrix<T>,
+        origin: (usize, usize),
+    ) -> Matrix<T> {
+        let mut view = Matrix::new(self.rows, self.cols);
+        let zoom = self.zoom as isize;
+        let top = origin.0 as isize + self.row;
+        let left = origin.1 as isize + self.col;
+
+        for row in 0..self.rows {
+            let src_rows = clamp_range(top + row as isize * zoom, zoom, matrix.row_count());
+
+            for col in 0..self.cols {
+                let src_cols = clamp_range(left + col as isize * zoom, zoom, matrix.col_count());
+
+                let value = src_rows
+                    .clone()
+                    .flat_map(|src_row| src_cols.clone().map(move |src_col| (src_row, src_col)))
+                    .map(|(src_row, src_col)| matrix.get(src_row, src_col))
+                    .max()
+                    .unwrap_or_default();
+                view.set(row, col, value);
+            }
+        }
+
+        view
+    }
+}
+
+/// The part of `start..start + len` that lies within `0..bound`.
+fn clamp_range(start: isize, len: isize, bound: usize) -> std::ops::Range<usize> {
+    let bound = bound as isize;
+    let begin = start.clamp(0, bound) as usize;
+    let end = (start + len).clamp(0, bound) as usize;
+    begin..end.max(begin)
+}
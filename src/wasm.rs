@@ -0,0 +1,69 @@
+//! Bindings for running the simulation in a browser. The board is read
+//! straight out of the WebAssembly memory, one byte per cell, row after row:
+//!
+//! ```js
+//! const game = new GameOfLife(64, 64, 42);
+//! game.tick();
+//! const cells = new Uint8Array(memory.buffer, game.cells_ptr(), game.width() * game.height());
+//! ```
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use wasm_bindgen::prelude::*;
+
+use crate::edge::Edge;
+use crate::game_of_life::GameOfLife;
+use crate::matrix::Matrix;
+use crate::rule::Rule;
+
+#[wasm_bindgen(js_name = GameOfLife)]
+pub struct WasmGameOfLife {
+    game: GameOfLife,
+}
+
+#[wasm_bindgen(js_class = GameOfLife)]
+impl WasmGameOfLife {
+    /// A random board of `width` by `height` cells, half of them alive.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: usize, height: usize, seed: u32) -> WasmGameOfLife {
+        let mut rng = StdRng::seed_from_u64(u64::from(seed));
+        let matrix = Matrix::random(height, width, 0.5, &mut rng);
+        WasmGameOfLife {
+            game: GameOfLife::new(matrix),
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.game.tick();
+    }
+
+    /// Pointer to the first cell, valid until the next call.
+    pub fn cells_ptr(&self) -> *const u8 {
+        self.game.matrix().as_slice().as_ptr()
+    }
+
+    pub fn width(&self) -> usize {
+        self.game.matrix().col_count()
+    }
+
+    pub fn height(&self) -> usize {
+        self.game.matrix().row_count()
+    }
+
+    pub fn ticks(&self) -> usize {
+        self.game.ticks()
+    }
+
+    pub fn toggle_cell(&mut self, row: usize, col: usize) {
+        self.game.toggle_cell(row as isize, col as isize);
+    }
+
+    /// Switches to a rule in B/S notation such as `B36/S23`, with wrapping
+    /// edges if `wrap` is set.
+    pub fn set_rule(&mut self, rule: &str, wrap: bool) -> Result<(), JsError> {
+        let edge = if wrap { Edge::Wrap } else { Edge::Dead };
+        let rule: Rule = rule.parse().map_err(|err: String| JsError::new(&err))?;
+        self.game.set_rule(rule.with_edge(edge));
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Objects on a board told apart, classified and counted.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::analyze::{self, Census, Haul, Kind, Velocity};
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::patterns;
+use conway_game_of_life::rule::Rule;
+
+fn board(objects: &[(&str, usize, usize)]) -> Matrix {
+    let mut matrix = Matrix::new(40, 40);
+    for &(name, row, col) in objects {
+        let pattern = patterns::find(name).unwrap().matrix();
+        matrix.blit(&pattern, row, col, Overflow::Clip);
+    }
+    matrix
+}
+
+#[test]
+fn names_known_objects_in_any_phase_and_orientation() {
+    let mut board = board(&[
+        ("block", 1, 1),
+        ("block", 1, 30),
+        ("beehive", 8, 1),
+        ("blinker", 8, 20),
+        ("pulsar", 20, 1),
+        ("lwss", 20, 25),
+    ]);
+    let glider = patterns::find("glider").unwrap().matrix().rotate90();
+    board.blit(&glider, 33, 30, Overflow::Clip);
+
+    let objects = analyze::objects(&board, &Rule::CONWAY);
+    let census = Census::new(&objects);
+    assert_eq!(objects.len(), 7);
+    assert_eq!(census.count("block"), 2);
+    assert_eq!(census.count("beehive"), 1);
+    assert_eq!(census.count("blinker"), 1);
+    assert_eq!(census.count("pulsar"), 1);
+    assert_eq!(census.count("lwss"), 1);
+    assert_eq!(census.count("glider"), 1);
+    assert_eq!(census.entries()[0], ("block", 2));
+
+    let kinds: Vec<(usize, usize, Kind)> = objects
+        .iter()
+        .map(|object| (object.row, object.col, object.kind))
+        .collect();
+    assert!(kinds.contains(&(1, 1, Kind::StillLife)));
+    assert!(kinds.contains(&(20, 1, Kind::Oscillator { period: 3 })));
+    assert!(kinds.contains(&(
+        20,
+        25,
+        Kind::Spaceship {
+            period: 4,
+            shift: (0, -2)
+        }
+    )));
+}
+
+#[test]
+fn classifies_unknown_objects_by_how_they_behave() {
+    // two blocks next to each other are a single still life without a name
+    let objects = analyze::objects(&board(&[("block", 0, 0), ("block", 0, 3)]), &Rule::CONWAY);
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].label(), "still life");
+
+    let objects = analyze::objects(&board(&[("r-pentomino", 10, 10)]), &Rule::CONWAY);
+    assert_eq!(objects[0].kind, Kind::Active);
+
+    // under another rule objects are only classified
+    let highlife: Rule = "B36/S23".parse().unwrap();
+    let objects = analyze::objects(&board(&[("blinker", 5, 5)]), &highlife);
+    assert_eq!(objects[0].label(), "p2 oscillator");
+}
+
+#[test]
+fn reports_the_speed_and_direction_of_moving_objects() {
+    let mut game = GameOfLife::new(board(&[
+        ("glider", 2, 2),
+        ("block", 2, 30),
+        ("lwss", 30, 30),
+    ]));
+    game.set_unbounded(true);
+
+    let mut tracks = analyze::moving_objects(&mut game, 120);
+    tracks.sort_by(|a, b| a.label.cmp(&b.label));
+    let moves: Vec<(&str, String, &str)> = tracks
+        .iter()
+        .map(|track| {
+            (
+                track.label.as_str(),
+                track.velocity.to_string(),
+                track.velocity.direction(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        moves,
+        [
+            ("glider", "c/4 diagonal".to_string(), "south-east"),
+            ("lwss", "c/2 orthogonal".to_string(), "west"),
+        ]
+    );
+
+    assert_eq!(Velocity::new((-2, 1), 6).to_string(), "c/3 oblique");
+    assert_eq!(Velocity::new((0, 2), 5).direction(), "east");
+}
+
+#[test]
+fn gives_objects_their_catagolue_apgcodes() {
+    let codes = [
+        ("block", "xs4_33"),
+        ("beehive", "xs6_696"),
+        ("loaf", "xs7_2596"),
+        ("boat", "xs5_253"),
+        ("blinker", "xp2_7"),
+        ("beacon", "xp2_318c"),
+        ("pentadecathlon", "xp15_4r4z4r4"),
+        ("pulsar", "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401"),
+        ("glider", "xq4_153"),
+        ("lwss", "xq4_6frc"),
+    ];
+    for (name, code) in codes {
+        // in any phase and orientation
+        let mut board = Matrix::new(40, 40);
+        let pattern = patterns::find(name).unwrap().matrix().flip_h().rotate90();
+        board.blit(&pattern, 10, 10, Overflow::Clip);
+        let mut game = GameOfLife::new(board);
+        game.tick();
+
+        let objects = analyze::objects(game.matrix(), &Rule::CONWAY);
+        assert_eq!(objects.len(), 1, "{name}");
+        assert_eq!(objects[0].apgcode, code, "{name}");
+    }
+
+    let objects = analyze::objects(&board(&[("r-pentomino", 10, 10)]), &Rule::CONWAY);
+    assert_eq!(objects[0].apgcode, "PATHOLOGICAL");
+}
+
+#[test]
+fn prints_the_census_as_a_catagolue_haul() {
+    let objects = analyze::objects(
+        &board(&[("block", 1, 1), ("block", 1, 30), ("glider", 20, 20)]),
+        &Rule::CONWAY,
+    );
+    let mut census = Census::apgcodes(&objects);
+    census.add(analyze::GLIDER_APGCODE, 2);
+    assert_eq!(census.total(), 5);
+
+    let haul = Haul {
+        rule: &Rule::CONWAY,
+        symmetry: Some("C1"),
+        soups: Some(10),
+        census: &census,
+    };
+    assert_eq!(
+        haul.to_string(),
+        "@RULE b3s23\n@SYMMETRY C1\n@NUM_SOUPS 10\n@NUM_OBJECTS 5\n\n\
+         @CENSUS TABLE\nxq4_153 3\nxs4_33 2\n"
+    );
+}
@@ -0,0 +1,27 @@
+//! The comparison table of the bench subcommand.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::bench::{report, Measurement};
+
+#[test]
+fn report_compares_engines_with_serial() {
+    let measurement = |engine, size, seconds| Measurement {
+        engine,
+        size,
+        ticks: 10,
+        seconds,
+    };
+    let report = report(&[
+        measurement("serial", 64, 1.0),
+        measurement("rayon", 64, 0.25),
+        measurement("serial", 256, 2.0),
+    ]);
+    let lines: Vec<&str> = report.lines().collect();
+
+    assert!(lines[0].contains("64x64 gen/s") && lines[0].contains("256x256 gen/s"));
+    assert!(lines[1].starts_with("serial") && lines[1].contains("10.0 (1.00x)"));
+    assert!(lines[2].starts_with("rayon") && lines[2].contains("40.0 (4.00x)"));
+    // rayon was not measured on the larger board
+    assert!(lines[2].trim_end().ends_with('-'));
+}
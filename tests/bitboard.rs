@@ -0,0 +1,65 @@
+//! Boards packed one bit per cell must hold and tick the same cells as the
+//! usual boards of a byte per cell.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::bitboard::BitMatrix;
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::EngineKind;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const EDGES: [Edge; 4] = [Edge::Dead, Edge::Alive, Edge::Wrap, Edge::Mirror];
+
+#[test]
+fn cells_are_reached_like_in_a_matrix() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (rows, cols) in [(1, 1), (3, 64), (5, 65), (40, 200)] {
+        let matrix = Matrix::random(rows, cols, 0.5, &mut rng);
+        let mut bits = BitMatrix::from_matrix(&matrix);
+        for row in 0..rows {
+            for col in 0..cols {
+                assert_eq!(bits.get(row, col), matrix.get(row, col));
+            }
+        }
+        assert!(bits.to_matrix() == matrix);
+
+        let (row, col) = (rng.gen_range(0..rows), rng.gen_range(0..cols));
+        let value = 1 - matrix.get(row, col);
+        bits.set(row, col, value);
+        let mut expected = matrix.clone();
+        expected.set(row, col, value);
+        assert!(bits.to_matrix() == expected);
+    }
+}
+
+#[test]
+fn packed_boards_tick_like_serial_across_words() {
+    let mut rng = StdRng::seed_from_u64(1);
+    // whole words, a word and a column, and several words with a partial one
+    for (rows, cols) in [(64, 64), (30, 65), (100, 200)] {
+        let matrix = Matrix::random(rows, cols, 0.4, &mut rng);
+        for edge in EDGES {
+            for rule in ["B3/S23", "B36/S23", "B0/S8"] {
+                let rule = rule.parse::<Rule>().unwrap().with_edge(edge);
+                let mut serial = EngineKind::Serial.build(None, None);
+                let (mut expected, mut bits) = (matrix.clone(), BitMatrix::from_matrix(&matrix));
+                let mut next_bits = BitMatrix::new(rows, cols);
+                for generation in 0..10 {
+                    let mut next = Matrix::new(rows, cols);
+                    serial.step(&expected, &mut next, &rule);
+                    expected = next;
+                    bits.step(&rule, &mut next_bits);
+                    std::mem::swap(&mut bits, &mut next_bits);
+                    assert!(
+                        bits.to_matrix() == expected,
+                        "{rows}x{cols} board differs from serial at generation {generation} \
+                         with {edge:?} edges under {rule}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Frames recorded in the asciinema v2 format.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::render::cast::Cast;
+use serde_json::Value;
+
+#[test]
+fn casts_start_with_a_header_and_time_every_output() {
+    let mut cast = Cast::new(Vec::new(), 80, 24).unwrap();
+    cast.record(b"\x1b[2Jhello").unwrap();
+    cast.record(b"world\n").unwrap();
+    let text = String::from_utf8(cast.into_inner().unwrap()).unwrap();
+
+    let lines: Vec<Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["version"], 2);
+    assert_eq!(
+        (lines[0]["width"].as_u64(), lines[0]["height"].as_u64()),
+        (Some(80), Some(24))
+    );
+
+    assert_eq!(lines[1][1], "o");
+    assert_eq!(lines[1][2], "\u{1b}[2Jhello");
+    assert_eq!(lines[2][2], "world\n");
+    assert!(lines[1][0].as_f64().unwrap() <= lines[2][0].as_f64().unwrap());
+}
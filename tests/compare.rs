@@ -0,0 +1,80 @@
+//! Games forked under another rule and drawn side by side.
+
+#![cfg(feature = "std")]
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::{EngineKind, TickEngine};
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::patterns;
+use conway_game_of_life::render::Frame;
+use conway_game_of_life::rule::Rule;
+
+fn text(text: &str) -> Frame {
+    let mut frame = Frame::default();
+    frame.push_text(text);
+    frame
+}
+
+fn lines(frame: &Frame) -> Vec<String> {
+    frame
+        .lines
+        .iter()
+        .map(|line| line.iter().map(|glyph| glyph.ch).collect())
+        .collect()
+}
+
+fn serial() -> Box<dyn TickEngine> {
+    EngineKind::Serial.build(None, None)
+}
+
+#[test]
+fn frames_side_by_side_line_up() {
+    let frame = text("ab\nc").beside(text("xy\nz\nw"), 2);
+    assert_eq!(lines(&frame), ["ab  xy", "c   z", "    w"]);
+
+    let frame = text("abc\nd\ne").beside(text("x"), 1);
+    assert_eq!(lines(&frame), ["abc x", "d", "e"]);
+}
+
+#[test]
+fn forks_under_the_same_rule_stay_in_lockstep() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut game = GameOfLife::new(Matrix::random(24, 24, 0.4, &mut rng));
+    game.tick();
+    let mut fork = game.fork(*game.rule(), serial());
+    assert_eq!(fork.ticks(), game.ticks());
+
+    for _ in 0..30 {
+        game.tick();
+        fork.tick();
+        assert_eq!(fork.state_hash(), game.state_hash());
+    }
+}
+
+#[test]
+fn forks_follow_their_own_rule_and_edges() {
+    let mut game = GameOfLife::new(Matrix::new(12, 12));
+    let glider = patterns::parse("glider").unwrap().matrix();
+    game.place_pattern(&glider, 0, 0, Overflow::Clip);
+    let mut wrapped = game.fork(Rule::CONWAY.with_edge(Edge::Wrap), serial());
+    let highlife = "B36/S23".parse::<Rule>().unwrap();
+    let mut high = game.fork(highlife, serial());
+    assert_eq!(wrapped.rule().edge, Edge::Wrap);
+    assert_eq!(*high.rule(), highlife);
+    assert_eq!(game.rule().edge, Edge::Dead);
+
+    // gliders fly alike under HighLife until they reach the edges, where
+    // they crash into dead cells but come round on a torus
+    for _ in 0..60 {
+        game.tick();
+        wrapped.tick();
+        high.tick();
+    }
+    assert_eq!(high.state_hash(), game.state_hash());
+    assert_eq!(wrapped.matrix().as_slice().iter().sum::<u8>(), 5);
+    assert_ne!(wrapped.state_hash(), game.state_hash());
+}
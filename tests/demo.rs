@@ -0,0 +1,32 @@
+//! The tour of famous patterns.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::demo::{caption, stage, tour};
+use conway_game_of_life::patterns;
+
+#[test]
+fn every_pattern_of_the_tour_has_a_caption() {
+    let tour = tour();
+    assert_eq!(tour[0].name, "glider");
+    for pattern in tour {
+        assert_ne!(caption(pattern), pattern.name);
+    }
+    assert_eq!(caption(patterns::find("block").unwrap()), "block");
+}
+
+#[test]
+fn patterns_are_staged_in_the_middle() {
+    let glider = patterns::find("glider").unwrap();
+    let matrix = stage(glider, 7, 9);
+    assert_eq!(matrix.count_live(), 5);
+    assert_eq!(
+        matrix.crop(2, 3, 3, 3).as_slice(),
+        glider.matrix().as_slice()
+    );
+
+    // too large for the board, it is clipped
+    let gun = patterns::find("gosper-glider-gun").unwrap();
+    let matrix = stage(gun, 4, 4);
+    assert_eq!((matrix.row_count(), matrix.col_count()), (4, 4));
+}
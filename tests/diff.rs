@@ -0,0 +1,32 @@
+#![cfg(feature = "native")]
+
+use conway_game_of_life::diff::{Change, Diff};
+use conway_game_of_life::plaintext;
+
+#[test]
+fn changes_are_counted_and_drawn() {
+    let before = plaintext::parse(".O.\n.O.\n.O.").unwrap();
+    let after = plaintext::parse("...\nOOO\n...").unwrap();
+    let diff = Diff::new(&before, &after);
+
+    assert_eq!((diff.born, diff.died, diff.alive), (2, 2, 1));
+    assert_eq!(diff.changes.get(0, 1), Change::Died);
+    assert_eq!(diff.changes.get(1, 0), Change::Born);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.render(false), ". - . \n+ x + \n. - . \n");
+    assert_eq!(
+        diff.to_string(),
+        "4 cells differ: 2 born, 2 died, 1 alive in both"
+    );
+}
+
+#[test]
+fn boards_of_different_sizes_are_compared_from_the_top_left() {
+    let before = plaintext::parse("OO\nOO").unwrap();
+    let after = plaintext::parse("OO.\nOO.\n..O").unwrap();
+    let diff = Diff::new(&before, &after);
+
+    assert_eq!((diff.changes.row_count(), diff.changes.col_count()), (3, 3));
+    assert_eq!((diff.born, diff.died, diff.alive), (1, 0, 4));
+    assert!(Diff::new(&before, &before).is_empty());
+}
@@ -0,0 +1,84 @@
+//! Strips of the board ticked by separate ranks swapping halo rows.
+
+#![cfg(feature = "native")]
+
+use std::net::TcpListener;
+use std::thread;
+
+use conway_game_of_life::distributed::{run_rank, strip_rows};
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::Serial;
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// The board after `ticks` generations ticked by `ranks` ranks on threads.
+fn distributed(board: &Matrix, rule: Rule, ranks: usize, ticks: usize) -> Matrix {
+    let listeners: Vec<TcpListener> = (0..ranks)
+        .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+        .collect();
+    let peers: Vec<_> = listeners
+        .iter()
+        .map(|listener| listener.local_addr().unwrap())
+        .collect();
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = listeners
+            .iter()
+            .enumerate()
+            .map(|(rank, listener)| {
+                let peers = &peers;
+                scope.spawn(move || {
+                    let board = (rank == 0).then_some(board);
+                    run_rank(
+                        listener,
+                        peers,
+                        rank,
+                        board,
+                        &rule,
+                        &mut Serial::new(),
+                        ticks,
+                    )
+                    .unwrap()
+                })
+            })
+            .collect();
+        let mut boards = handles.into_iter().map(|handle| handle.join().unwrap());
+        let board = boards.next().unwrap().expect("rank 0 gathers the board");
+        assert!(boards.all(|board| board.is_none()));
+        board
+    })
+}
+
+fn serial(board: &Matrix, rule: Rule, ticks: usize) -> Matrix {
+    let mut game = GameOfLife::new(board.clone());
+    game.set_rule(rule);
+    for _ in 0..ticks {
+        game.tick();
+    }
+    game.matrix().clone()
+}
+
+#[test]
+fn strips_cover_the_board() {
+    let strips: Vec<_> = (0..3).map(|rank| strip_rows(10, 3, rank)).collect();
+    assert_eq!(strips, [0..3, 3..6, 6..10]);
+}
+
+#[test]
+fn ranks_agree_with_a_single_process() {
+    let board = Matrix::random(23, 17, 0.4, &mut StdRng::seed_from_u64(3));
+    for edge in [Edge::Dead, Edge::Alive, Edge::Wrap, Edge::Mirror] {
+        let rule = Rule::CONWAY.with_edge(edge);
+        let expected = serial(&board, rule, 30);
+        for ranks in [1, 2, 4] {
+            assert_eq!(
+                distributed(&board, rule, ranks, 30).as_slice(),
+                expected.as_slice(),
+                "{ranks} ranks with {edge:?} edges"
+            );
+        }
+    }
+}
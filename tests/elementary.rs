@@ -0,0 +1,49 @@
+//! Elementary cellular automata scrolling up the board.
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::elementary::Elementary;
+use conway_game_of_life::engine::TickEngine;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+
+fn row(cells: &str) -> Vec<u8> {
+    cells.bytes().map(|cell| u8::from(cell == b'#')).collect()
+}
+
+#[test]
+fn rule_30_grows_a_triangle() {
+    let mut matrix = Matrix::new(4, 7);
+    matrix.set(3, 3, 1);
+    let mut next = Matrix::new(4, 7);
+    let mut engine = Elementary::new(30);
+    for _ in 0..3 {
+        engine.step(&matrix, &mut next, &Rule::CONWAY);
+        core::mem::swap(&mut matrix, &mut next);
+    }
+
+    let expected = [
+        row("...#..."),
+        row("..###.."),
+        row(".##..#."),
+        row("##.####"),
+    ];
+    for (idx, expected) in expected.iter().enumerate() {
+        assert_eq!(
+            &matrix.as_slice()[idx * 7..(idx + 1) * 7],
+            expected.as_slice()
+        );
+    }
+}
+
+#[test]
+fn edges_wrap_or_stay_dead() {
+    let rule_110 = Elementary::new(110);
+    let mut next = vec![0; 5];
+
+    rule_110.next_row(&row("#...."), &mut next, Edge::Wrap);
+    assert_eq!(next, row("#...#"));
+    rule_110.next_row(&row("....#"), &mut next, Edge::Dead);
+    assert_eq!(next, row("...##"));
+    rule_110.next_row(&row("#...."), &mut next, Edge::Dead);
+    assert_eq!(next, row("#...."));
+}
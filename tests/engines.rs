@@ -0,0 +1,233 @@
+//! Every engine must tick random boards exactly like a plain neighbour
+//! count does, whatever the board size, edge mode and rule.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::{self, EngineKind, TickEngine};
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::streaming;
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const CASES: u64 = 200;
+const GENERATIONS: usize = 4;
+const EDGES: [Edge; 4] = [Edge::Dead, Edge::Alive, Edge::Wrap, Edge::Mirror];
+const RULES: [&str; 3] = ["B3/S23", "B36/S23", "B2/S"];
+
+/// Every engine, with sizes that split boards into uneven parts.
+fn engines(chunk_rows: usize, workers: usize) -> Vec<(EngineKind, Box<dyn TickEngine>)> {
+    [
+        EngineKind::Serial,
+        EngineKind::Rayon,
+        EngineKind::RayonChunked,
+        EngineKind::Threads,
+        EngineKind::Simd,
+        EngineKind::Incremental,
+        EngineKind::RayonTiles,
+        EngineKind::Tiled,
+        EngineKind::Lut,
+        EngineKind::BitParallel,
+    ]
+    .into_iter()
+    .map(|kind| (kind, kind.build(Some(chunk_rows), Some(workers))))
+    .collect()
+}
+
+/// The generations following `matrix` according to `engine`.
+fn generations(engine: &mut dyn TickEngine, matrix: &Matrix, rule: &Rule) -> Vec<Matrix> {
+    let mut generations = vec![matrix.clone()];
+    for _ in 0..GENERATIONS {
+        let src = generations.last().unwrap();
+        let mut dst = Matrix::new(src.row_count(), src.col_count());
+        engine.step(src, &mut dst, rule);
+        generations.push(dst);
+    }
+    generations.split_off(1)
+}
+
+/// The generations following `matrix` under the `B.../S...` rule `notation`,
+/// counting the eight neighbours of every cell one by one. It shares nothing
+/// with the engines but `Edge::cell`, so it catches bugs they all have.
+fn reference(matrix: &Matrix, notation: &str, edge: Edge) -> Vec<Matrix> {
+    let (birth, survival) = notation.split_once('/').unwrap();
+    let counts =
+        |digits: &str| -> Vec<u8> { digits[1..].bytes().map(|digit| digit - b'0').collect() };
+    let (birth, survival) = (counts(birth), counts(survival));
+
+    let mut generations = vec![matrix.clone()];
+    for _ in 0..GENERATIONS {
+        let src = generations.last().unwrap();
+        let mut dst = Matrix::new(src.row_count(), src.col_count());
+        for row in 0..src.row_count() {
+            for col in 0..src.col_count() {
+                let (r, c) = (row as isize, col as isize);
+                let mut live = 0;
+                for (dr, dc) in (-1..=1).flat_map(|dr| (-1..=1).map(move |dc| (dr, dc))) {
+                    if (dr, dc) != (0, 0) {
+                        live += edge.cell(src, r + dr, c + dc);
+                    }
+                }
+                let alive = if src.get(row, col) == 1 {
+                    survival.contains(&live)
+                } else {
+                    birth.contains(&live)
+                };
+                dst.set(row, col, alive as u8);
+            }
+        }
+        generations.push(dst);
+    }
+    generations.split_off(1)
+}
+
+/// The rows, columns and cells of a board of 1 to 39 rows and columns,
+/// filled anywhere from empty to full.
+fn boards() -> impl Strategy<Value = (usize, usize, Vec<u8>)> {
+    (1..40usize, 1..40usize, 0.0..=1.0f64).prop_flat_map(|(rows, cols, fill_prob)| {
+        let cells = prop::collection::vec(prop::bool::weighted(fill_prob), rows * cols);
+        cells.prop_map(move |cells| (rows, cols, cells.into_iter().map(u8::from).collect()))
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES as u32))]
+
+    #[test]
+    fn engines_match_the_reference_on_random_boards(
+        (rows, cols, cells) in boards(),
+        edge in prop::sample::select(EDGES.to_vec()),
+        notation in prop::sample::select(RULES.to_vec()),
+        chunk_rows in 1..8usize,
+        workers in 1..8usize,
+    ) {
+        let matrix = Matrix::from_vec(rows, cols, cells).unwrap();
+        let rule = notation.parse::<Rule>().unwrap().with_edge(edge);
+        let expected = reference(&matrix, notation, edge);
+        for (kind, mut engine) in engines(chunk_rows, workers) {
+            let actual = generations(&mut *engine, &matrix, &rule);
+            prop_assert!(
+                actual == expected,
+                "{kind:?} differs from the reference on a {rows}x{cols} board \
+                 with {edge:?} edges under {rule}, starting from\n{matrix}"
+            );
+        }
+    }
+}
+
+#[test]
+fn engines_match_the_reference_on_single_cells() {
+    for (rows, cols) in [(1, 1), (1, 5), (5, 1), (2, 2)] {
+        for edge in EDGES {
+            let mut matrix = Matrix::new(rows, cols);
+            matrix.set(0, 0, 1);
+            let rule = Rule::CONWAY.with_edge(edge);
+
+            let expected = reference(&matrix, "B3/S23", edge);
+            for (kind, mut engine) in engines(3, 2) {
+                let actual = generations(&mut *engine, &matrix, &rule);
+                assert!(
+                    actual == expected,
+                    "{kind:?} differs from the reference on a {rows}x{cols} board with {edge:?} edges"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn engines_sized_for_the_machine_match_serial() {
+    assert!(engine::available_workers() >= 1);
+    assert_eq!(engine::band_rows(0), engine::CACHE_BAND_BYTES);
+    assert_eq!(engine::band_rows(usize::MAX), 1);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    // wide enough for several blocks of rows, more rows than most machines have threads
+    let matrix = Matrix::random(300, 3000, 0.4, &mut rng);
+    for edge in EDGES {
+        let rule = Rule::CONWAY.with_edge(edge);
+        let expected = generations(&mut *EngineKind::Serial.build(None, None), &matrix, &rule);
+        for kind in [EngineKind::RayonChunked, EngineKind::Threads] {
+            let actual = generations(&mut *kind.build(None, None), &matrix, &rule);
+            assert!(
+                actual == expected,
+                "{kind:?} differs from serial with {edge:?} edges"
+            );
+        }
+    }
+}
+
+#[test]
+fn incremental_engines_match_serial_across_tiles() {
+    let mut rng = StdRng::seed_from_u64(0);
+    // larger than the tiles of rayon-tiles, and sparse so most of them settle
+    let matrix = Matrix::random(150, 200, 0.05, &mut rng);
+
+    for edge in EDGES {
+        let rule = Rule::CONWAY.with_edge(edge);
+        let mut serial = EngineKind::Serial.build(None, None);
+        for kind in [EngineKind::Incremental, EngineKind::RayonTiles] {
+            let mut engine = kind.build(None, None);
+            let (mut expected, mut actual) = (matrix.clone(), matrix.clone());
+            for generation in 0..100 {
+                let mut next = Matrix::new(150, 200);
+                serial.step(&expected, &mut next, &rule);
+                expected = next;
+                let mut next = Matrix::new(150, 200);
+                engine.step(&actual, &mut next, &rule);
+                actual = next;
+                assert!(
+                    actual == expected,
+                    "{kind:?} differs from serial at generation {generation} with {edge:?} edges"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn ticking_in_place_matches_serial() {
+    for case in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(case);
+        let (rows, cols) = (rng.gen_range(1..40), rng.gen_range(1..40));
+        let fill_prob = rng.gen_range(0.0..=1.0);
+        let matrix = Matrix::random(rows, cols, fill_prob, &mut rng);
+        let edge = EDGES[rng.gen_range(0..EDGES.len())];
+        let rule = RULES[rng.gen_range(0..RULES.len())]
+            .parse::<Rule>()
+            .unwrap()
+            .with_edge(edge);
+
+        let expected = generations(&mut *EngineKind::Serial.build(None, None), &matrix, &rule);
+        let mut actual = matrix.clone();
+        for (generation, expected) in expected.iter().enumerate() {
+            let previous = actual.clone();
+            let transition = streaming::tick_in_place(&mut actual, &rule);
+            assert!(
+                &actual == expected,
+                "case {case}: generation {generation} in place differs from serial on a \
+                 {rows}x{cols} board with {edge:?} edges under {rule}, starting from\n{matrix}"
+            );
+
+            let count = |f: fn(u8, u8) -> bool| {
+                previous
+                    .as_slice()
+                    .iter()
+                    .zip(actual.as_slice())
+                    .filter(|(&before, &after)| f(before, after))
+                    .count()
+            };
+            assert_eq!(
+                transition.births,
+                count(|before, after| before == 0 && after == 1)
+            );
+            assert_eq!(
+                transition.deaths,
+                count(|before, after| before == 1 && after == 0)
+            );
+            assert_eq!(transition.population, count(|_, after| after == 1));
+        }
+    }
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "std")]
+
+use conway_game_of_life::events::EventWriter;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::plaintext;
+use conway_game_of_life::rle;
+use conway_game_of_life::stats::Stats;
+
+#[test]
+fn events_follow_the_changes() {
+    let before = plaintext::parse("...\nOOO\n...").unwrap();
+    let after = plaintext::parse(".O.\n.O.\n.O.").unwrap();
+    let mut stats = Stats::new(&before);
+
+    let mut out = Vec::new();
+    let mut events = EventWriter::new(&mut out, true, &before);
+    events.write(stats.latest(), &before).unwrap();
+    events
+        .write(stats.record(1, &before, &after), &after)
+        .unwrap();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["tick"], 1);
+    assert_eq!(lines[1]["population"], 3);
+    assert_eq!(
+        (&lines[1]["births"], &lines[1]["deaths"]),
+        (&2.into(), &2.into())
+    );
+    assert_eq!(lines[1]["bounding_box"]["rows"], 3);
+    assert_eq!(
+        lines[1]["hash"],
+        format!("{:016x}", after.state_hash()).as_str()
+    );
+
+    // the first generation changed from an empty board
+    let first = rle::parse(lines[0]["changes"].as_str().unwrap()).unwrap();
+    assert!(first == before);
+    let changes = rle::parse(lines[1]["changes"].as_str().unwrap()).unwrap();
+    assert!(changes == plaintext::parse(".O.\nO.O\n.O.").unwrap());
+}
+
+#[test]
+fn changes_are_left_out_unless_followed() {
+    let matrix = Matrix::new(4, 4);
+    let stats = Stats::new(&matrix);
+
+    let mut out = Vec::new();
+    EventWriter::new(&mut out, false, &matrix)
+        .write(stats.latest(), &matrix)
+        .unwrap();
+    let event: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert!(event.get("changes").is_none());
+    assert!(event["bounding_box"].is_null());
+}
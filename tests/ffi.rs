@@ -0,0 +1,34 @@
+#![cfg(feature = "cdylib")]
+
+use conway_game_of_life::ffi::{gol_cells, gol_free, gol_new, gol_set_cell, gol_tick};
+use std::ptr;
+
+#[test]
+fn games_tick_through_the_c_abi() {
+    unsafe {
+        let game = gol_new(5, 5, 42, 0.0);
+        for col in 1..4 {
+            gol_set_cell(game, 2, col, true);
+        }
+        gol_tick(game, 1);
+
+        let (mut rows, mut cols) = (0, 0);
+        let cells = gol_cells(game, &mut rows, &mut cols);
+        assert_eq!((rows, cols), (5, 5));
+        let cells = std::slice::from_raw_parts(cells, rows * cols);
+        let alive: Vec<usize> = (0..cells.len()).filter(|&i| cells[i] != 0).collect();
+        // the blinker stands upright
+        assert_eq!(alive, [7, 12, 17]);
+
+        gol_free(game);
+    }
+}
+
+#[test]
+fn null_games_are_ignored() {
+    unsafe {
+        gol_tick(ptr::null_mut(), 1);
+        assert!(gol_cells(ptr::null(), ptr::null_mut(), ptr::null_mut()).is_null());
+        gol_free(ptr::null_mut());
+    }
+}
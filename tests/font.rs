@@ -0,0 +1,30 @@
+use conway_game_of_life::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+
+#[test]
+fn text_is_drawn_glyph_after_glyph() {
+    let matrix = font::render("Hi\nL").unwrap();
+    assert_eq!(
+        (matrix.row_count(), matrix.col_count()),
+        (2 * GLYPH_HEIGHT + 1, 2 * GLYPH_WIDTH + 1)
+    );
+
+    // the left stroke of H, its crossbar and the stem of I
+    assert!((0..GLYPH_HEIGHT).all(|row| matrix.get(row, 0) == 1));
+    assert!((0..GLYPH_WIDTH).all(|col| matrix.get(3, col) == 1));
+    assert!((0..GLYPH_HEIGHT).all(|row| matrix.get(row, GLYPH_WIDTH + 3) == 1));
+    // the column between the glyphs and the row between the lines are dead
+    assert!((0..matrix.row_count()).all(|row| matrix.get(row, GLYPH_WIDTH) == 0));
+    assert!((0..matrix.col_count()).all(|col| matrix.get(GLYPH_HEIGHT, col) == 0));
+    // the foot of L
+    assert!((0..GLYPH_WIDTH).all(|col| matrix.get(2 * GLYPH_HEIGHT, col) == 1));
+}
+
+#[test]
+fn every_printable_character_has_a_glyph() {
+    assert!((' '..='~')
+        .filter(|ch| !"[\\]^`{|}~".contains(*ch))
+        .all(|ch| font::glyph(ch).is_some()));
+    assert_eq!(font::glyph('a'), font::glyph('A'));
+    assert!(font::render("naïve").is_err());
+    assert_eq!(font::render("").unwrap().count_live(), 0);
+}
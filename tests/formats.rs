@@ -0,0 +1,196 @@
+//! Reading and writing boards in every supported format.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::convert::{Rotation, Transform};
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::format::Format;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::{plaintext, rle};
+
+const FORMATS: [Format; 6] = [
+    Format::Json,
+    Format::Rle,
+    Format::Plaintext,
+    Format::Macrocell,
+    Format::Mcell,
+    Format::Life106,
+];
+
+const GLIDER: &str = ".O.\n..O\nOOO";
+
+fn glider() -> Matrix {
+    plaintext::parse(GLIDER).unwrap()
+}
+
+#[test]
+fn formats_round_trip() {
+    // live cells touch every side, since some formats drop empty margins
+    let mut matrix = Matrix::new(23, 19);
+    for (row, col) in [(0, 0), (0, 18), (22, 0), (22, 18)] {
+        matrix.blit(&glider(), row, col, Overflow::Clip);
+    }
+    matrix.set(22, 17, 1);
+    matrix.set(11, 9, 1);
+
+    for format in FORMATS {
+        let decoded = format.decode(&format.encode(&matrix));
+        assert!(decoded.as_ref() == Ok(&matrix), "{format:?}");
+    }
+}
+
+#[test]
+fn macrocell_shares_identical_nodes() {
+    let mut matrix = Matrix::new(64, 64);
+    for row in (0..64).step_by(8) {
+        for col in (0..64).step_by(8) {
+            matrix.blit(&glider(), row, col, Overflow::Clip);
+        }
+    }
+
+    let text = Format::Macrocell.encode(&matrix);
+    // a leaf and one node for each level from 16x16 to 64x64
+    assert_eq!(
+        text.lines()
+            .filter(|line| !line.starts_with(['[', '#']))
+            .count(),
+        4
+    );
+    // without the dead cells beyond the last glider
+    assert!(Format::Macrocell.decode(&text) == Ok(matrix.crop_to_bounding_box()));
+}
+
+#[test]
+fn reads_golly_macrocell() {
+    // a glider in the se quadrant of the se quadrant, far from the origin
+    let text = "[M2] (golly 4.2)\n#R B3/S23\n$$.*$..*$***$\n4 0 0 0 1\n5 0 0 0 2\n";
+    let matrix = Format::Macrocell.decode(text).unwrap();
+    assert!(matrix == glider());
+
+    assert!(Format::Macrocell.decode("4 0 0 0 0\n").is_err());
+    assert!(Format::Macrocell.decode("[M2]\n4 0 0 0 1\n").is_err());
+}
+
+#[test]
+fn reads_mcell_board() {
+    let text = "#MCell 4.20\n#GAME Life\n#BOARD 6x5\n#L $3.A$4.A\n#L $2.3A\n";
+    let matrix = Format::Mcell.decode(text).unwrap();
+    let mut expected = Matrix::new(5, 6);
+    expected.blit(&glider(), 1, 2, Overflow::Clip);
+    assert!(matrix == expected, "{matrix}");
+
+    assert!(Format::Mcell
+        .decode("#MCell 4.20\n#BOARD 2x2\n#L 3A\n")
+        .is_err());
+}
+
+#[test]
+fn reads_life_106_cells() {
+    // a glider around the origin, with a comment
+    let text = "#Life 1.06\n#D glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+    let matrix = Format::Life106.decode(text).unwrap();
+    assert!(matrix == glider());
+    assert_eq!(Format::from_path("glider.lif".as_ref()), Format::Life106);
+
+    assert!(Format::Life106.decode("#Life 1.05\n.O\n").is_err());
+    assert!(Format::Life106.decode("#Life 1.06\n0 1 2\n").is_err());
+}
+
+#[test]
+fn transforms_apply_in_order() {
+    let mut matrix = Matrix::new(6, 7);
+    matrix.blit(&glider(), 2, 3, Overflow::Clip);
+    let transform = Transform {
+        crop: true,
+        rotate: Some(Rotation::Quarter),
+        flip_h: true,
+        pad: 1,
+        ..Transform::default()
+    };
+
+    let expected = glider().rotate90().flip_h().expand(1, 1, 1, 1);
+    assert!(transform.apply(matrix.clone()) == expected);
+    assert!(Transform::default().apply(matrix.clone()) == matrix);
+}
+
+#[test]
+fn formats_are_detected_from_the_text() {
+    let mut matrix = Matrix::new(5, 6);
+    matrix.blit(&glider(), 1, 2, Overflow::Clip);
+
+    for format in FORMATS {
+        let text = format.encode(&matrix);
+        assert_eq!(Format::detect(&text), format);
+    }
+    assert_eq!(
+        Format::detect("#N Glider\nx = 3, y = 3\nbo$2bo$3o!"),
+        Format::Rle
+    );
+    assert_eq!(
+        Format::detect("!Name: Glider\n.O.\n..O\nOOO"),
+        Format::Plaintext
+    );
+}
+
+#[test]
+fn rle_headers_carry_the_rule() {
+    let highlife = "B36/S23".parse::<Rule>().unwrap();
+    let text = Format::Rle.encode_with_rule(&glider(), &highlife);
+    assert!(text.starts_with("x = 3, y = 3, rule = B36/S23\n"));
+    assert!(Format::Rle.decode_with_rule(&text) == Ok((glider(), Some(highlife))));
+
+    // S/B notation, Golly's bounded grids and headers without a rule
+    let (_, rule) = rle::parse_with_rule("x = 3, y = 3, rule = 23/36\nbo$2bo$3o!").unwrap();
+    assert_eq!(rule, Some(highlife));
+    let (_, rule) = rle::parse_with_rule("x = 3, y = 3, rule = B3/S23:T8,8\nbo$2bo$3o!").unwrap();
+    assert_eq!(rule, Some(Rule::CONWAY));
+    let (_, rule) = rle::parse_with_rule("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    assert_eq!(rule, None);
+
+    assert!(rle::parse("x = 3, y = 3, rule = WireWorld\nbo$2bo$3o!").is_err());
+}
+
+#[test]
+fn boards_of_another_rule_are_rejected() {
+    let highlife = "B36/S23".parse::<Rule>().unwrap();
+    let path = std::env::temp_dir().join(format!("highlife-{}.rle", std::process::id()));
+    glider().save_with_rule(&path, &highlife).unwrap();
+
+    assert!(Matrix::load_with_rule(&path).unwrap() == (glider(), Some(highlife)));
+    assert!(Matrix::load_for_rule(&path, &highlife.with_edge(Edge::Wrap)).is_ok());
+    let Err(err) = Matrix::load_for_rule(&path, &Rule::CONWAY) else {
+        panic!("a HighLife pattern loaded under Conway's rule");
+    };
+    assert!(err
+        .to_string()
+        .ends_with("is a pattern of rule B36/S23, not B3/S23"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn headers_of_empty_or_huge_boards_are_rejected() {
+    for text in [
+        "x = 4000000000, y = 4000000000\n!",
+        "x = 0, y = 3\n!",
+        "x = 3, y = 3\n18446744073709551615b2o!",
+        "x = 3, y = 3\n18446744073709551615$2o!",
+    ] {
+        assert!(Format::Rle.decode(text).is_err(), "{text}");
+    }
+    assert!(Format::Mcell
+        .decode("#MCell 4.20\n#BOARD 4000000000x4000000000\n")
+        .is_err());
+    assert!(Format::Plaintext.decode("!Name: nothing\n").is_err());
+}
+
+#[test]
+fn life_106_cells_too_far_apart_are_rejected() {
+    for text in [
+        "#Life 1.06\n0 -4611686018427387904\n0 4611686018427387904\n",
+        "#Life 1.06\n0 0\n4000000000 4000000000\n",
+        "#Life 1.06\n",
+    ] {
+        assert!(Format::Life106.decode(text).is_err(), "{text}");
+    }
+}
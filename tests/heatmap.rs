@@ -0,0 +1,41 @@
+//! Activity counted for the heatmap renderer.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::plaintext;
+use conway_game_of_life::render::text::Renderer;
+use conway_game_of_life::render::theme::Theme;
+
+#[test]
+fn counts_generations_every_cell_has_been_alive() {
+    let mut matrix = Matrix::new(5, 5);
+    matrix.blit(&plaintext::parse("OOO").unwrap(), 2, 1, Overflow::Clip);
+    let mut game = GameOfLife::new(matrix);
+    assert!(game.activity().is_none());
+
+    game.set_renderer(Renderer::Heatmap);
+    for _ in 0..4 {
+        game.tick();
+    }
+
+    // the blinker has been horizontal three times and vertical twice
+    let activity = game.activity().unwrap();
+    let mut expected = Matrix::<u32>::new(5, 5);
+    for (row, col, count) in [(2, 1, 3), (2, 3, 3), (1, 2, 2), (3, 2, 2), (2, 2, 5)] {
+        expected.set(row, col, count);
+    }
+    assert!(activity == &expected);
+
+    // the busiest cell is drawn hottest, cells alive less often cooler
+    let glyphs = Renderer::Heatmap.render_glyphs(game.matrix(), None, Some(activity), Theme::Mono);
+    let cell = |row: usize, col: usize| glyphs[row][col * 2];
+    assert_eq!((cell(2, 2).ch, cell(2, 2).bg), ('x', Some(231)));
+    assert_eq!((cell(2, 1).ch, cell(2, 1).bg), ('x', Some(202)));
+    assert_eq!((cell(1, 2).ch, cell(1, 2).bg), (' ', Some(124)));
+    assert_eq!((cell(0, 0).ch, cell(0, 0).bg), ('.', None));
+
+    game.set_renderer(Renderer::Ascii);
+    assert!(game.activity().is_none());
+}
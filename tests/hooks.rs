@@ -0,0 +1,70 @@
+//! Tick hooks observing and stopping a simulation.
+
+#![cfg(feature = "std")]
+
+use std::ops::ControlFlow;
+use std::sync::{Arc, Mutex};
+
+use conway_game_of_life::game_of_life::{GameOfLife, StopReason};
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::plaintext;
+
+#[test]
+fn hooks_see_every_generation_and_can_stop_the_run() {
+    let mut matrix = Matrix::new(20, 20);
+    matrix.blit(
+        &plaintext::parse(".O.\n..O\nOOO").unwrap(),
+        0,
+        0,
+        Overflow::Clip,
+    );
+    let mut game = GameOfLife::new(matrix);
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&seen);
+    game.on_tick(move |generation, matrix| {
+        log.lock()
+            .unwrap()
+            .push((generation.tick, generation.population, matrix.count_live()));
+        ControlFlow::Continue(())
+    });
+    game.on_tick(|generation, _| {
+        if generation.tick == 8 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+
+    game.run_until(GameOfLife::is_finished);
+
+    assert_eq!(game.ticks(), 8);
+    assert_eq!(game.stop_reason(), Some(StopReason::Hook));
+    let expected: Vec<_> = (1..=8).map(|tick| (tick, 5, 5)).collect();
+    assert_eq!(*seen.lock().unwrap(), expected);
+}
+
+#[test]
+fn steps_run_several_generations_until_the_game_finishes() {
+    let mut game = GameOfLife::new(plaintext::parse(".....\n..O..\n..O..\n..O..\n.....").unwrap());
+    let ticks = Arc::new(Mutex::new(0));
+    let seen = Arc::clone(&ticks);
+    game.on_tick(move |generation, _| {
+        *seen.lock().unwrap() = generation.tick;
+        ControlFlow::Continue(())
+    });
+
+    game.set_step_size(0);
+    assert_eq!(game.step_size(), 1);
+    game.step();
+    assert_eq!(game.ticks(), 1);
+
+    game.set_step_size(5);
+    game.step();
+    assert_eq!((game.ticks(), *ticks.lock().unwrap()), (6, 6));
+
+    game.set_max_ticks(Some(8));
+    game.step();
+    assert_eq!(game.ticks(), 8);
+    assert!(game.is_finished());
+}
@@ -0,0 +1,102 @@
+//! Wall-clock limits on runs, benchmarks and soup searches.
+
+#![cfg(feature = "std")]
+
+use std::time::{Duration, Instant};
+
+use conway_game_of_life::game_of_life::{parse_seconds, GameOfLife, StopReason};
+use conway_game_of_life::matrix::Matrix;
+
+#[test]
+fn games_stop_once_their_time_is_up() {
+    let mut game = GameOfLife::new(Matrix::new(64, 64));
+    game.tick();
+    game.set_max_seconds(Some(Duration::from_millis(50)));
+    assert!(!game.is_finished());
+
+    let started = Instant::now();
+    game.run_until(GameOfLife::is_finished);
+    assert!(started.elapsed() >= Duration::from_millis(50));
+
+    let generations = game.ticks() - 1;
+    assert!(generations > 0);
+    assert_eq!(
+        game.stop_reason(),
+        Some(StopReason::MaxSeconds {
+            limit: Duration::from_millis(50),
+            generations,
+        })
+    );
+    assert_eq!(
+        game.stop_reason().unwrap().to_string(),
+        format!("reached max seconds (0.05) after {generations} generations")
+    );
+}
+
+#[test]
+fn tick_limits_reached_first_win() {
+    let mut game = GameOfLife::new(Matrix::new(8, 8));
+    game.set_max_ticks(Some(5));
+    game.set_max_seconds(Some(Duration::from_secs(3600)));
+    game.run_until(GameOfLife::is_finished);
+    assert_eq!(game.ticks(), 5);
+    assert_eq!(game.stop_reason(), Some(StopReason::MaxTicks(5)));
+
+    // more seconds than the clock counts
+    let mut game = GameOfLife::new(Matrix::new(8, 8));
+    game.set_max_seconds(Some(parse_seconds("1e19").unwrap()));
+    game.tick();
+    assert!(!game.is_finished());
+}
+
+#[test]
+fn seconds_are_positive_numbers() {
+    assert_eq!(parse_seconds("1.5"), Ok(Duration::from_millis(1500)));
+    assert_eq!(parse_seconds("600"), Ok(Duration::from_secs(600)));
+    for invalid in ["0", "-1", "soon", "inf", "NaN"] {
+        assert!(parse_seconds(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn benchmarks_measure_the_generations_run_in_time() {
+    use conway_game_of_life::bench;
+
+    let limited = bench::measure(
+        "serial",
+        &["--engine", "serial"],
+        256,
+        usize::MAX,
+        Some(Duration::from_millis(100)),
+        0,
+    );
+    assert!(limited.ticks > 0 && limited.ticks < usize::MAX);
+    assert!(limited.seconds >= 0.1);
+
+    let full = bench::measure("serial", &["--engine", "serial"], 16, 7, None, 0);
+    assert_eq!(full.ticks, 7);
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn soup_searches_past_their_deadline_stop() {
+    use conway_game_of_life::search;
+
+    let soup = |_| Matrix::new(16, 16);
+    let mut finished = 0;
+    let generations = search::run_soups(
+        0..1000,
+        soup,
+        2000,
+        Some(Instant::now()),
+        |_, _, _| (),
+        |()| finished += 1,
+    );
+    assert_eq!((finished, generations), (0, 0));
+
+    let generations = search::run_soups(0..10, soup, 2000, None, |_, _, _| (), |()| finished += 1);
+    assert_eq!(finished, 10);
+    // empty soups repeat themselves right away
+    assert_eq!(generations, 10);
+}
@@ -0,0 +1,44 @@
+//! The lookup table must hold the next state of every neighborhood.
+
+use conway_game_of_life::lut::{table, TABLE_SIZE};
+use conway_game_of_life::rule::Rule;
+
+/// Neighborhood index of a cell, its neighbors as rows of three bits.
+fn index(rows: [u8; 3]) -> usize {
+    // column by column, left to right, each column top to bottom
+    let mut index = 0;
+    for col in (0..3).rev() {
+        for row in rows {
+            index = index << 1 | usize::from(row >> col & 1);
+        }
+    }
+    index
+}
+
+#[test]
+fn conway_table_follows_the_rule() {
+    let table = table(&Rule::CONWAY);
+    assert_eq!(table.len(), TABLE_SIZE);
+    // a blinker's center survives, its ends die and the cells beside it are born
+    assert_eq!(table[index([0b000, 0b111, 0b000])], 1);
+    assert_eq!(table[index([0b000, 0b011, 0b000])], 0);
+    assert_eq!(table[index([0b010, 0b000, 0b010])], 0);
+    assert_eq!(table[index([0b100, 0b000, 0b011])], 1);
+    // overcrowded
+    assert_eq!(table[index([0b111, 0b111, 0b000])], 0);
+}
+
+#[test]
+fn table_matches_counting_for_any_rule() {
+    let rule: Rule = "B36/S125".parse().unwrap();
+    let table = table(&rule);
+    for (index, &next) in table.iter().enumerate() {
+        let alive = (index >> 4 & 1) as u8;
+        let live_count = (index & !(1 << 4)).count_ones() as u8;
+        assert_eq!(
+            next,
+            rule.next_state(alive, live_count),
+            "index {index:09b}"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Constructing, reshaping and transforming matrices.
+
+use conway_game_of_life::matrix::{Anchor, BoundingBox, Matrix};
+
+/// A matrix whose cells count up from 1 row after row.
+fn numbered(rows: usize, cols: usize) -> Matrix {
+    let cells = (1..=rows * cols).map(|cell| cell as u8).collect();
+    Matrix::from_vec(rows, cols, cells).unwrap()
+}
+
+#[test]
+fn try_new_rejects_empty_and_oversized_matrices() {
+    assert!(Matrix::<u8>::try_new(0, 3).is_err());
+    assert!(Matrix::<u8>::try_new(3, 0).is_err());
+    assert!(Matrix::<u8>::try_new(usize::MAX, 2).is_err());
+    assert!(Matrix::<u64>::try_new(usize::MAX / 8, 2).is_err());
+    assert!(Matrix::<u8>::try_new(2, 3) == Ok(Matrix::new(2, 3)));
+}
+
+#[test]
+fn resize_keeps_cells_at_the_anchor() {
+    let matrix = numbered(2, 3);
+    let resized = |rows, cols, anchor| {
+        let mut matrix = matrix.clone();
+        matrix.resize(rows, cols, anchor);
+        matrix.into_vec()
+    };
+
+    assert_eq!(
+        resized(3, 4, Anchor::TopLeft),
+        [1, 2, 3, 0, 4, 5, 6, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        resized(3, 4, Anchor::BottomRight),
+        [0, 0, 0, 0, 0, 1, 2, 3, 0, 4, 5, 6]
+    );
+    assert_eq!(resized(1, 2, Anchor::TopLeft), [1, 2]);
+    assert_eq!(resized(1, 2, Anchor::BottomRight), [5, 6]);
+    assert_eq!(resized(1, 1, Anchor::Center), [2]);
+    assert_eq!(
+        resized(4, 5, Anchor::Center),
+        [0, 0, 0, 0, 0, 0, 1, 2, 3, 0, 0, 4, 5, 6, 0, 0, 0, 0, 0, 0]
+    );
+    assert!(resized(0, 0, Anchor::Center).is_empty());
+}
+
+#[test]
+fn resize_back_restores_the_matrix() {
+    let matrix = numbered(3, 3);
+    for anchor in [Anchor::TopLeft, Anchor::Center, Anchor::BottomRight] {
+        let mut resized = matrix.clone();
+        resized.resize(7, 6, anchor);
+        assert_eq!(resized.count_live(), matrix.count_live());
+        resized.resize(3, 3, anchor);
+        assert!(resized == matrix, "{anchor:?}");
+    }
+}
+
+#[test]
+fn rotations_and_flips_move_cells() {
+    // 1 2 3
+    // 4 5 6
+    let matrix = numbered(2, 3);
+
+    assert_eq!(matrix.rotate90().into_vec(), [4, 1, 5, 2, 6, 3]);
+    assert_eq!(matrix.rotate180().into_vec(), [6, 5, 4, 3, 2, 1]);
+    assert_eq!(matrix.rotate270().into_vec(), [3, 6, 2, 5, 1, 4]);
+    assert_eq!(matrix.flip_h().into_vec(), [3, 2, 1, 6, 5, 4]);
+    assert_eq!(matrix.flip_v().into_vec(), [4, 5, 6, 1, 2, 3]);
+    assert_eq!(
+        (matrix.rotate90().row_count(), matrix.rotate90().col_count()),
+        (3, 2)
+    );
+}
+
+#[test]
+fn transforms_compose() {
+    let matrix = numbered(3, 4);
+
+    assert!(matrix.rotate90().rotate270() == matrix);
+    assert!(matrix.rotate90().rotate90() == matrix.rotate180());
+    assert!(matrix.flip_h().flip_v() == matrix.rotate180());
+    assert!(matrix.flip_h().flip_h() == matrix);
+}
+
+#[test]
+fn crops_to_the_live_cells() {
+    let mut matrix = Matrix::new(5, 6);
+    assert_eq!(matrix.bounding_box(), None);
+    assert_eq!(matrix.crop_to_bounding_box().size(), 0);
+
+    matrix.set(1, 4, 1);
+    matrix.set(3, 2, 1);
+    let bounds = BoundingBox {
+        row: 1,
+        col: 2,
+        rows: 3,
+        cols: 3,
+    };
+    assert_eq!(matrix.bounding_box(), Some(bounds));
+    assert_eq!(
+        matrix.crop_to_bounding_box().into_vec(),
+        [0, 0, 1, 0, 0, 0, 1, 0, 0]
+    );
+}
+
+#[test]
+fn state_hashes_tell_boards_apart() {
+    let board = numbered(3, 4);
+    assert_eq!(board.state_hash(), board.clone().state_hash());
+
+    // the same cells in another shape
+    let reshaped = Matrix::from_vec(4, 3, board.as_slice().to_vec()).unwrap();
+    assert_ne!(board.state_hash(), reshaped.state_hash());
+
+    let mut changed = board.clone();
+    changed.set(2, 3, 0);
+    assert_ne!(board.state_hash(), changed.state_hash());
+    // states of multi-state rules count, not only whether cells are alive
+    changed.set(2, 3, 2);
+    assert_ne!(board.state_hash(), changed.state_hash());
+}
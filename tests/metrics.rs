@@ -0,0 +1,40 @@
+//! Timing of ticks.
+
+#![cfg(feature = "std")]
+
+use std::time::Duration;
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::metrics::Metrics;
+
+#[test]
+fn pace_is_averaged_over_recent_ticks() {
+    let mut metrics = Metrics::default();
+    assert_eq!(metrics.average_tick(), None);
+    assert_eq!(metrics.to_string(), "");
+
+    // the slow first ticks fall out of the window
+    for _ in 0..10 {
+        metrics.record(Duration::from_secs(1), 1000);
+    }
+    for _ in 0..32 {
+        metrics.record(Duration::from_millis(2), 1000);
+    }
+    assert_eq!(metrics.ticks(), 42);
+    assert_eq!(metrics.total(), Duration::from_millis(10_064));
+    assert_eq!(metrics.average_tick(), Some(Duration::from_millis(2)));
+    assert_eq!(metrics.ticks_per_sec(), Some(500.0));
+    assert_eq!(metrics.cells_per_sec(), Some(500_000.0));
+    assert_eq!(metrics.to_string(), "2.000 ms/tick 0.5 Mcells/s");
+}
+
+#[test]
+fn every_tick_is_timed() {
+    let mut game = GameOfLife::new(Matrix::new(16, 16));
+    for _ in 0..3 {
+        game.tick();
+    }
+    assert_eq!(game.metrics().ticks(), 3);
+    assert!(game.metrics().last_tick().is_some());
+}
@@ -0,0 +1,65 @@
+//! Boards kept in memory-mapped files.
+
+#![cfg(all(feature = "native", unix))]
+
+use std::path::PathBuf;
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::plaintext;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mmap-{name}-{}.bin", std::process::id()))
+}
+
+#[test]
+fn mapped_boards_are_kept_in_their_file() {
+    let path = temp_path("kept");
+    let _ = std::fs::remove_file(&path);
+
+    let (mut matrix, created) = Matrix::map_file(&path, 3, 4).unwrap();
+    assert!(created && matrix.is_mapped());
+    assert_eq!(matrix.count_live(), 0);
+    matrix.set(1, 2, 1);
+    matrix.flush().unwrap();
+    assert_eq!(std::fs::read(&path).unwrap()[6], 1);
+    drop(matrix);
+
+    let (matrix, created) = Matrix::map_file(&path, 3, 4).unwrap();
+    assert!(!created);
+    assert_eq!(matrix.get(1, 2), 1);
+    // copies are kept in memory
+    assert!(!matrix.clone().is_mapped());
+    assert!(matrix.clone() == matrix);
+    drop(matrix);
+
+    assert!(Matrix::map_file(&path, 4, 4).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn mapped_boards_tick_in_place_like_boards_in_memory() {
+    let path = temp_path("tick");
+    let _ = std::fs::remove_file(&path);
+    let glider = plaintext::parse(".O.\n..O\nOOO").unwrap();
+
+    let mut board = Matrix::new(12, 12);
+    board.blit(&glider, 0, 0, Overflow::Clip);
+    let (mut mapped, _) = Matrix::map_file(&path, 12, 12).unwrap();
+    mapped.blit(&glider, 0, 0, Overflow::Clip);
+
+    let mut game = GameOfLife::new(board);
+    let mut mapped_game = GameOfLife::new(mapped);
+    mapped_game.set_low_memory(true);
+    for _ in 0..20 {
+        game.tick();
+        mapped_game.tick();
+    }
+    assert!(mapped_game.matrix().is_mapped());
+    assert!(mapped_game.matrix() == game.matrix());
+    drop(mapped_game);
+
+    let (reopened, _) = Matrix::map_file(&path, 12, 12).unwrap();
+    assert!(reopened == *game.matrix());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "ndarray")]
+
+use conway_game_of_life::engine::{Serial, TickEngine};
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use ndarray::{array, s, Array2};
+
+#[test]
+fn boards_come_from_arrays() {
+    let blinker = array![[0, 0, 0], [1, 1, 1], [0, 0, 0]];
+    let matrix = Matrix::from(blinker.clone());
+    assert_eq!((matrix.row_count(), matrix.col_count()), (3, 3));
+    assert_eq!(matrix.view(), blinker);
+
+    // transposed arrays are read in their logical order
+    let matrix = Matrix::from(blinker.t().to_owned());
+    assert_eq!(matrix.get(1, 1), 1);
+    assert_eq!(matrix.get(0, 1), 1);
+    assert_eq!(matrix.get(1, 0), 0);
+}
+
+#[test]
+fn results_are_viewed_as_arrays() {
+    let mut matrix = Matrix::from(Array2::<u8>::zeros((5, 5)));
+    matrix.view_mut().slice_mut(s![2, 1..4]).fill(1);
+
+    let mut next = Matrix::new(5, 5);
+    Serial::new().step(&matrix, &mut next, &Rule::CONWAY);
+    assert_eq!(next.view().column(2).sum(), 3);
+    assert_eq!(next.view().sum(), 3);
+}
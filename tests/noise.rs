@@ -0,0 +1,45 @@
+//! Random births and deaths on top of the rule.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::noise::Noise;
+
+fn noisy_game(low_memory: bool) -> GameOfLife {
+    let mut game = GameOfLife::new(Matrix::new(30, 30));
+    game.set_low_memory(low_memory);
+    game.set_noise(Noise::new(0.05, 0.01));
+    game
+}
+
+#[test]
+fn noise_is_reproducible_and_counted_in_the_statistics() {
+    let mut game = noisy_game(false);
+    let mut low_memory = noisy_game(true);
+    for _ in 0..10 {
+        game.tick();
+        low_memory.tick();
+        assert!(game.matrix() == low_memory.matrix());
+
+        let generation = game.stats().latest();
+        assert_eq!(generation.population, game.matrix().count_live());
+        assert_eq!(
+            low_memory.stats().latest().population,
+            generation.population
+        );
+    }
+    assert!(game.matrix().count_live() > 0);
+}
+
+#[test]
+fn certain_noise_flips_every_cell() {
+    let mut game = GameOfLife::new(Matrix::new(4, 4));
+    game.set_noise(Noise::new(1.0, 0.0));
+    game.tick();
+    assert_eq!(game.matrix().count_live(), 16);
+
+    game.set_noise(Noise::new(0.0, 1.0));
+    game.tick();
+    assert_eq!(game.matrix().count_live(), 0);
+}
@@ -0,0 +1,47 @@
+//! Batch runs telling how they ended by their exit status.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::batch::Outcome;
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::plaintext;
+
+fn run(board: &str, stop_on_cycle: bool) -> Outcome {
+    let mut game = GameOfLife::new(plaintext::parse(board).unwrap());
+    game.set_stop_on_cycle(stop_on_cycle);
+    game.set_max_ticks(Some(20));
+    game.run_until(GameOfLife::is_finished);
+    Outcome::of(&game)
+}
+
+#[test]
+fn outcomes_follow_where_the_run_ended_up() {
+    let block = "....\n.OO.\n.OO.\n....";
+    let blinker = ".....\n..O..\n..O..\n..O..\n.....";
+    let lone = "...\n.O.\n...";
+
+    assert_eq!(run(block, true), Outcome::Stabilized);
+    assert_eq!(run(blinker, true), Outcome::Cycle);
+    assert_eq!(run(lone, true), Outcome::DiedOut);
+
+    // runs going on after the board settled end the same way
+    assert_eq!(run(block, false), Outcome::Stabilized);
+    assert_eq!(run(blinker, false), Outcome::Cycle);
+    assert_eq!(run(lone, false), Outcome::DiedOut);
+
+    let glider = format!(".O\n..O\nOOO{}", "\n............".repeat(9));
+    assert_eq!(run(&glider, false), Outcome::MaxTicks);
+}
+
+#[test]
+fn outcomes_have_distinct_exit_codes() {
+    let codes = [
+        Outcome::MaxTicks,
+        Outcome::Stabilized,
+        Outcome::DiedOut,
+        Outcome::Cycle,
+        Outcome::Interrupted,
+    ]
+    .map(Outcome::exit_code);
+    assert_eq!(codes, [0, 2, 3, 4, 130]);
+}
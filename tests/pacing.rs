@@ -0,0 +1,46 @@
+//! Frames paced at a steady rate.
+
+#![cfg(feature = "std")]
+
+use std::time::{Duration, Instant};
+
+use conway_game_of_life::pacing::FramePacer;
+
+const MS: Duration = Duration::from_millis(1);
+
+#[test]
+fn time_spent_on_a_frame_is_taken_out_of_the_wait() {
+    let start = Instant::now();
+    let mut pacer = FramePacer::new(10.0, start);
+    assert!(!pacer.is_due(start));
+    assert_eq!(pacer.remaining(start), 100 * MS);
+
+    // shown 30 ms late, the next frame still comes 200 ms after the start
+    pacer.frame_shown(start + 130 * MS);
+    assert_eq!(pacer.remaining(start + 130 * MS), 70 * MS);
+    assert!(pacer.is_due(start + 200 * MS));
+}
+
+#[test]
+fn frames_missed_entirely_are_not_rushed_out() {
+    let start = Instant::now();
+    let mut pacer = FramePacer::new(10.0, start);
+    pacer.frame_shown(start + 450 * MS);
+    assert_eq!(pacer.remaining(start + 450 * MS), 100 * MS);
+}
+
+#[test]
+fn the_achieved_rate_is_measured_over_the_latest_frames() {
+    let start = Instant::now();
+    let mut pacer = FramePacer::new(50.0, start);
+    assert_eq!(pacer.achieved_fps(), None);
+
+    for frame in 1..=40 {
+        pacer.frame_shown(start + frame * 40 * MS);
+    }
+    let fps = pacer.achieved_fps().unwrap();
+    assert!((fps - 25.0).abs() < 1e-9, "{fps}");
+
+    pacer.restart(start + 2 * Duration::from_secs(1));
+    assert_eq!(pacer.achieved_fps(), None);
+}
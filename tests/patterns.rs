@@ -0,0 +1,118 @@
+//! Known still lifes, oscillators and spaceships, ticked with dead and
+//! wrapping edges.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::plaintext;
+use conway_game_of_life::rule::Rule;
+
+const EDGES: [Edge; 2] = [Edge::Dead, Edge::Wrap];
+
+const BLOCK: &str = "OO\nOO";
+const BLINKER: [&str; 2] = ["OOO", "O\nO\nO"];
+const TOAD: [&str; 2] = [".OOO\nOOO.", "..O.\nO..O\nO..O\n.O.."];
+const GLIDER: &str = ".O.\n..O\nOOO";
+
+/// A `rows` by `cols` board with `pattern` at `(row, col)`.
+fn board(rows: usize, cols: usize, pattern: &str, row: usize, col: usize) -> Matrix {
+    let mut matrix = Matrix::new(rows, cols);
+    let pattern = plaintext::parse(pattern).expect("test patterns are valid plaintext");
+    matrix.blit(&pattern, row, col, Overflow::Wrap);
+    matrix
+}
+
+fn game(matrix: Matrix, edge: Edge) -> GameOfLife {
+    let mut game = GameOfLife::new(matrix);
+    game.set_rule(Rule::CONWAY.with_edge(edge));
+    game
+}
+
+fn assert_board(game: &GameOfLife, expected: &Matrix, edge: Edge) {
+    assert!(
+        game.matrix() == expected,
+        "{edge:?} edges, tick {}, expected\n{expected}got\n{}",
+        game.ticks(),
+        game.matrix()
+    );
+}
+
+#[test]
+fn block_is_still() {
+    for edge in EDGES {
+        let start = board(4, 4, BLOCK, 1, 1);
+        let mut game = game(start.clone(), edge);
+
+        for _ in 0..3 {
+            game.tick();
+            assert_board(&game, &start, edge);
+        }
+    }
+}
+
+#[test]
+fn blinker_has_period_2() {
+    for edge in EDGES {
+        let phases = [board(5, 5, BLINKER[0], 2, 1), board(5, 5, BLINKER[1], 1, 2)];
+        let mut game = game(phases[0].clone(), edge);
+
+        for tick in 1..=6 {
+            game.tick();
+            assert_board(&game, &phases[tick % 2], edge);
+        }
+    }
+}
+
+#[test]
+fn toad_has_period_2() {
+    for edge in EDGES {
+        let phases = [board(6, 6, TOAD[0], 2, 1), board(6, 6, TOAD[1], 1, 1)];
+        let mut game = game(phases[0].clone(), edge);
+
+        for tick in 1..=6 {
+            game.tick();
+            assert_board(&game, &phases[tick % 2], edge);
+        }
+    }
+}
+
+#[test]
+fn glider_moves_one_cell_diagonally_every_4_ticks() {
+    for edge in EDGES {
+        let mut game = game(board(12, 12, GLIDER, 0, 0), edge);
+
+        for step in 1..=8 {
+            for _ in 0..4 {
+                game.tick();
+            }
+            assert_board(&game, &board(12, 12, GLIDER, step, step), edge);
+        }
+    }
+}
+
+#[test]
+fn glider_wraps_around_torus() {
+    let start = board(8, 8, GLIDER, 5, 5);
+    let mut game = game(start.clone(), Edge::Wrap);
+
+    for step in 1..=8 {
+        for _ in 0..4 {
+            game.tick();
+        }
+        let (row, col) = ((5 + step) % 8, (5 + step) % 8);
+        assert_board(&game, &board(8, 8, GLIDER, row, col), Edge::Wrap);
+    }
+    assert_board(&game, &start, Edge::Wrap);
+}
+
+#[test]
+fn glider_stops_at_dead_edges() {
+    let mut game = game(board(6, 6, GLIDER, 0, 0), Edge::Dead);
+
+    for _ in 0..40 {
+        game.tick();
+    }
+    assert_board(&game, &board(6, 6, BLOCK, 4, 4), Edge::Dead);
+}
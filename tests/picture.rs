@@ -0,0 +1,50 @@
+#![cfg(feature = "std")]
+
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::picture;
+use std::fs::{self, File};
+use std::path::PathBuf;
+
+/// Writes a grayscale PNG image of `width` by `height` pixels.
+fn write_png(name: &str, width: u32, height: u32, pixels: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("picture-{name}-{}.png", std::process::id()));
+    let mut encoder = png::Encoder::new(File::create(&path).unwrap(), width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(pixels).unwrap();
+    path
+}
+
+#[test]
+fn dark_pixels_become_live_cells() {
+    // a black square in the top-left quarter of a white 4x4 image
+    #[rustfmt::skip]
+    let pixels = [
+        0, 0, 255, 255,
+        0, 0, 255, 255,
+        255, 255, 255, 255,
+        255, 255, 255, 255,
+    ];
+    let path = write_png("square", 4, 4, &pixels);
+    let luminance = picture::load_luminance(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!((luminance.row_count(), luminance.col_count()), (4, 4));
+    assert_eq!(luminance.get(0, 0), 0.0);
+    assert_eq!(luminance.get(3, 3), 1.0);
+
+    let board = picture::threshold(&picture::scale(&luminance, 2, 2), 0.5, false);
+    assert!(board == Matrix::from_vec(2, 2, vec![1, 0, 0, 0]).unwrap());
+    let board = picture::threshold(&picture::scale(&luminance, 8, 8), 0.5, false);
+    assert_eq!(board.count_live(), 16);
+}
+
+#[test]
+fn dithering_matches_the_gray_level() {
+    let gray = Matrix::from_vec(16, 16, vec![0.25; 256]).unwrap();
+
+    assert_eq!(picture::threshold(&gray, 0.5, false).count_live(), 256);
+    let dithered = picture::threshold(&gray, 0.5, true).count_live();
+    assert!((180..=204).contains(&dithered), "{dithered}");
+}
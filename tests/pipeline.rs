@@ -0,0 +1,57 @@
+//! Frames drawn on a thread of their own.
+
+#![cfg(feature = "native")]
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use conway_game_of_life::render::pipeline::RenderThread;
+use conway_game_of_life::render::terminal::TerminalRenderer;
+use conway_game_of_life::render::Frame;
+
+fn text(text: &str) -> Frame {
+    let mut frame = Frame::default();
+    frame.push_text(text);
+    frame
+}
+
+/// A terminal taking its time over every frame.
+struct Slow(Vec<u8>);
+
+impl Write for Slow {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        thread::sleep(Duration::from_millis(200));
+        Ok(())
+    }
+}
+
+#[test]
+fn frames_handed_over_are_all_drawn_by_the_time_it_finishes() {
+    let mut renderer = RenderThread::spawn(TerminalRenderer::new(Vec::new()));
+    for frame in ["abc", "def", "ghi"] {
+        renderer.draw(text(frame)).unwrap();
+    }
+
+    let out = String::from_utf8(renderer.finish().unwrap().into_inner()).unwrap();
+    let drawn: String = out.chars().filter(|ch| ('a'..='i').contains(ch)).collect();
+    assert_eq!(drawn, "abcdefghi");
+}
+
+#[test]
+fn frames_are_dropped_while_the_terminal_is_busy() {
+    let mut renderer = RenderThread::spawn(TerminalRenderer::new(Slow(Vec::new())));
+    assert!(renderer.ready());
+    renderer.draw(text("busy")).unwrap();
+    assert!(!renderer.ready());
+    assert!(!renderer.ready());
+    assert_eq!(renderer.dropped(), 2);
+
+    // the frames handed over are still drawn
+    let Slow(out) = renderer.finish().unwrap().into_inner();
+    assert!(String::from_utf8(out).unwrap().contains('y'));
+}
@@ -0,0 +1,47 @@
+//! The Prometheus metrics endpoint.
+
+#![cfg(feature = "metrics")]
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::prometheus::Exporter;
+
+fn get(exporter: &Exporter, path: &str) -> String {
+    let mut stream = TcpStream::connect(exporter.local_addr()).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn serves_the_progress_of_a_game() {
+    let exporter = Exporter::serve("127.0.0.1:0".parse().unwrap()).unwrap();
+    exporter.info("gameoflife_engine_info", "Engine", &[("engine", "serial")]);
+
+    let mut game =
+        GameOfLife::new(Matrix::from_vec(3, 3, vec![0, 0, 0, 1, 1, 1, 0, 0, 0]).unwrap());
+    game.set_exporter(exporter.clone());
+    game.tick();
+    game.tick();
+
+    let response = get(&exporter, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("# TYPE gameoflife_generation gauge\ngameoflife_generation 2\n"));
+    assert!(response.contains("gameoflife_population 3\n"));
+    assert!(response.contains("# TYPE gameoflife_tick_seconds_total counter\n"));
+    assert!(response.contains("gameoflife_engine_info{engine=\"serial\"} 1\n"));
+
+    assert!(get(&exporter, "/other").starts_with("HTTP/1.1 404 Not Found"));
+}
+
+#[test]
+fn counters_add_up() {
+    let exporter = Exporter::serve("127.0.0.1:0".parse().unwrap()).unwrap();
+    exporter.increment("gameoflife_soups_total", "Soups", 1.0);
+    exporter.increment("gameoflife_soups_total", "Soups", 2.0);
+    assert!(exporter.render().contains("gameoflife_soups_total 3\n"));
+}
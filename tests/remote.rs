@@ -0,0 +1,47 @@
+//! Generations streamed over WebSocket.
+
+#![cfg(feature = "remote")]
+
+use std::thread;
+use std::time::Duration;
+
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::remote::{self, Broadcaster};
+
+fn glider() -> Matrix {
+    Matrix::from_vec(3, 3, vec![0, 1, 0, 0, 0, 1, 1, 1, 1]).unwrap()
+}
+
+#[test]
+fn generations_are_packed_eight_cells_to_a_byte() {
+    let message = remote::encode(&glider(), 7);
+    assert_eq!(message.len(), 16 + 2);
+    assert_eq!(&message[16..], &[0b1110_0010, 0b1]);
+    let (matrix, tick) = remote::decode(&message).unwrap();
+    assert_eq!((matrix.as_slice(), tick), (glider().as_slice(), 7));
+
+    assert!(remote::decode(&message[..17]).is_err());
+    assert_eq!(
+        remote::parse_serve_addr("ws://0.0.0.0:9000"),
+        Ok("0.0.0.0:9000".parse().unwrap())
+    );
+}
+
+#[test]
+fn viewers_get_the_latest_generation_and_then_every_one() {
+    let broadcaster = Broadcaster::serve("127.0.0.1:0".parse().unwrap()).unwrap();
+    broadcaster.send(&glider(), 0);
+
+    let url = format!("ws://{}", broadcaster.local_addr());
+    let generations = remote::connect(&url).unwrap();
+    let (matrix, tick) = generations.recv().unwrap();
+    assert_eq!((matrix.as_slice(), tick), (glider().as_slice(), 0));
+
+    while broadcaster.viewers() == 0 {
+        thread::sleep(Duration::from_millis(10));
+    }
+    broadcaster.send(&Matrix::new(2, 5), 1);
+    let (matrix, tick) = generations.recv().unwrap();
+    assert_eq!((matrix.row_count(), matrix.col_count(), tick), (2, 5, 1));
+    assert_eq!(matrix.count_live(), 0);
+}
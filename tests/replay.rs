@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::replay::Replay;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::scenario::Scenario;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::path::PathBuf;
+
+fn replay_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("replay-{name}-{}.log", std::process::id()))
+}
+
+/// Ends the recording of `game` and plays it back for as many generations
+/// as the game ran.
+fn play_back(path: &PathBuf, game: GameOfLife) -> GameOfLife {
+    let ticks = game.ticks();
+    drop(game);
+
+    let mut replayed = GameOfLife::new(Matrix::new(1, 1));
+    replayed.play_replay(Replay::load(path).unwrap());
+    while replayed.ticks() < ticks {
+        replayed.tick();
+    }
+    std::fs::remove_file(path).unwrap();
+    replayed
+}
+
+#[test]
+fn replays_reproduce_edits_and_scenarios() {
+    let path = replay_path("edits");
+    let matrix = Matrix::random(30, 40, 0.3, &mut StdRng::seed_from_u64(0));
+    let mut game = GameOfLife::new(matrix);
+    game.set_scenario(Scenario::parse("5 place glider 2,2\n12 rule B36/S23").unwrap());
+    game.record_replay(&path).unwrap();
+
+    for tick in 0..30 {
+        if tick % 7 == 3 {
+            game.toggle_cell(tick / 2, tick);
+            game.set_cell(10, tick, true);
+        }
+        game.tick();
+    }
+    let expected = game.matrix().clone();
+    assert!(*play_back(&path, game).matrix() == expected);
+}
+
+#[test]
+fn edits_of_rewound_generations_are_dropped() {
+    let path = replay_path("rewind");
+    let matrix = Matrix::random(20, 20, 0.4, &mut StdRng::seed_from_u64(1));
+    let mut game = GameOfLife::new(matrix);
+    game.set_history(50);
+    game.record_replay(&path).unwrap();
+
+    for _ in 0..10 {
+        game.tick();
+    }
+    game.toggle_cell(3, 3);
+    game.set_rule(Rule::new(&[3, 6], &[2, 3], game.rule().edge));
+    for _ in 0..5 {
+        game.tick();
+    }
+    // back before the edits, which are undone, though the rule stays
+    for _ in 0..8 {
+        game.rewind();
+    }
+    game.toggle_cell(5, 5);
+    for _ in 0..10 {
+        game.tick();
+    }
+    let expected = game.matrix().clone();
+    assert!(*play_back(&path, game).matrix() == expected);
+}
+
+#[test]
+fn replays_without_a_starting_state_are_rejected() {
+    assert!(Replay::parse("# nothing recorded\n").is_err());
+    assert!(Replay::parse("12 toggle 1,1\n").is_err());
+}
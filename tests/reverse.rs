@@ -0,0 +1,63 @@
+//! Predecessors of patterns and Gardens of Eden.
+
+#![cfg(feature = "native")]
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::patterns;
+use conway_game_of_life::reverse::{self, Outcome};
+use conway_game_of_life::rule::Rule;
+
+fn assert_predecessor(target: &Matrix, rule: &Rule) {
+    let search = reverse::predecessor(target, rule, 10_000_000);
+    let Outcome::Found(predecessor) = &search.outcome else {
+        panic!("{search}");
+    };
+    assert_eq!(
+        (predecessor.row_count(), predecessor.col_count()),
+        (target.row_count() + 2, target.col_count() + 2)
+    );
+    assert!(reverse::successor(predecessor, rule) == *target);
+}
+
+#[test]
+fn finds_predecessors_of_known_patterns() {
+    for name in ["block", "blinker", "glider", "lwss", "pulsar"] {
+        assert_predecessor(&patterns::find(name).unwrap().matrix(), &Rule::CONWAY);
+    }
+    let highlife: Rule = "B36/S23".parse().unwrap();
+    assert_predecessor(&patterns::find("glider").unwrap().matrix(), &highlife);
+}
+
+#[test]
+fn finds_predecessors_of_evolved_boards() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut game = GameOfLife::new(Matrix::random(10, 10, 0.4, &mut rng));
+    for _ in 0..5 {
+        game.tick();
+    }
+    // the board of the previous generation is one, so there must be one
+    assert_predecessor(game.matrix(), &Rule::CONWAY);
+}
+
+#[test]
+fn proves_gardens_of_eden() {
+    // every cell dies, so only dead cells have a predecessor
+    let death: Rule = "B/S".parse().unwrap();
+    let mut target = Matrix::new(3, 3);
+    assert_predecessor(&target, &death);
+    target.set(1, 1, 1);
+    let search = reverse::predecessor(&target, &death, 1000);
+    assert!(search.outcome == Outcome::GardenOfEden, "{search}");
+}
+
+#[test]
+fn gives_up_when_out_of_steps() {
+    let target = patterns::find("pulsar").unwrap().matrix();
+    let search = reverse::predecessor(&target, &Rule::CONWAY, 10);
+    assert!(search.outcome == Outcome::GaveUp, "{search}");
+    assert_eq!(search.steps, 10);
+}
@@ -0,0 +1,145 @@
+//! Golly rule tables and trees.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::engine::{Serial, TickEngine};
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::ruletable::{RuleTable, RuleTableEngine};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const LIFE: &str = "\
+@RULE Life
+@TABLE
+n_states:2
+neighborhood:Moore
+symmetries:permute
+var a={0,1}
+var b={a}
+var c={a}
+var d={a}
+var e={a}
+var f={a}
+var g={a}
+var h={a}
+# births, written without commas
+0111000001
+# survivals
+1,1,1,0,0,0,0,0,0,1
+1,1,1,1,0,0,0,0,0,1
+# everything else dies
+1,a,b,c,d,e,f,g,h,0
+@COLORS
+1 255 255 255
+";
+
+const WIREWORLD: &str = "\
+@RULE WireWorld
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={a}
+var c={a}
+var d={a}
+var e={a}
+var f={a}
+var g={a}
+var h={a}
+var i={0,2,3}
+var j={i}
+var k={i}
+var l={i}
+var m={i}
+var n={i}
+var o={i}
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+";
+
+/// Every cell takes the state of its northern neighbor, so everything moves south.
+const SOUTHWARD: &str = "\
+@RULE Southward
+@TREE
+num_states=2
+num_neighbors=4
+num_nodes=9
+1 0 0
+1 1 1
+2 0 0
+2 1 1
+3 2 2
+3 3 3
+4 4 4
+4 5 5
+5 6 7
+";
+
+fn step(table: &str, matrix: &Matrix) -> Matrix {
+    let mut engine = RuleTableEngine::new(RuleTable::parse(table).unwrap());
+    let mut next = Matrix::new(matrix.row_count(), matrix.col_count());
+    engine.step(matrix, &mut next, &Rule::CONWAY);
+    next
+}
+
+fn row(states: &[u8]) -> Matrix {
+    let mut matrix = Matrix::new(1, states.len());
+    for (col, &state) in states.iter().enumerate() {
+        matrix.set(0, col, state);
+    }
+    matrix
+}
+
+#[test]
+fn life_as_a_table_matches_the_built_in_rule() {
+    let table = RuleTable::parse(LIFE).unwrap();
+    assert_eq!((table.name(), table.states()), ("Life", 2));
+
+    let mut matrix = Matrix::random(24, 24, 0.4, &mut StdRng::seed_from_u64(3));
+    let mut expected = matrix.clone();
+    let mut serial = Serial::new();
+    for _ in 0..20 {
+        let mut next = Matrix::new(24, 24);
+        serial.step(&expected, &mut next, &Rule::CONWAY);
+        expected = next;
+        matrix = step(LIFE, &matrix);
+        assert!(matrix == expected);
+    }
+}
+
+#[test]
+fn wireworld_electrons_run_along_wires() {
+    let wire = row(&[2, 1, 3, 3, 3, 3]);
+    let next = step(WIREWORLD, &wire);
+    assert!(next == row(&[3, 2, 1, 3, 3, 3]), "{next}");
+    assert!(step(WIREWORLD, &next) == row(&[3, 3, 2, 1, 3, 3]));
+}
+
+#[test]
+fn trees_decide_one_neighbor_at_a_time() {
+    let mut matrix = Matrix::new(3, 3);
+    matrix.set(0, 1, 1);
+    let next = step(SOUTHWARD, &matrix);
+    let mut expected = Matrix::new(3, 3);
+    expected.set(1, 1, 1);
+    assert!(next == expected, "{next}");
+}
+
+#[test]
+fn rejects_invalid_rules() {
+    for rule in [
+        "@RULE Empty",
+        "@TABLE\nn_states:2\nneighborhood:hexagonal",
+        "@TABLE\nn_states:2\n0,1,2,0,0,0,0,0,0,1",
+        "@TABLE\nn_states:2\n0,1,1,0",
+        "@TABLE\nn_states:2\nvar a={0,1}\n0,0,0,0,0,0,0,0,0,a",
+        "@TABLE\nn_states:2\nneighborhood:vonNeumann\nsymmetries:rotate8\n0,0,0,0,0,1",
+        "@TREE\nnum_states=2\nnum_neighbors=4\n1 0 2",
+    ] {
+        assert!(RuleTable::parse(rule).is_err(), "{rule}");
+    }
+}
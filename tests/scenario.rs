@@ -0,0 +1,70 @@
+//! Scenario events applied at their generations.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::{Matrix, Overflow};
+use conway_game_of_life::plaintext;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::scenario::Scenario;
+
+fn board(rows: usize, cols: usize, pattern: &str, row: usize, col: usize) -> Matrix {
+    let mut matrix = Matrix::new(rows, cols);
+    let pattern = plaintext::parse(pattern).expect("test patterns are valid plaintext");
+    matrix.blit(&pattern, row, col, Overflow::Clip);
+    matrix
+}
+
+fn game_with(scenario: &str) -> GameOfLife {
+    let mut game = GameOfLife::new(Matrix::new(10, 10));
+    game.set_scenario(Scenario::parse(scenario).unwrap());
+    game
+}
+
+#[test]
+fn events_happen_at_their_generation() {
+    let mut game = game_with("# a block, then a blinker\n0 place block 1,1\n2 place blinker 6,2\n");
+    let block = board(10, 10, "OO\nOO", 1, 1);
+    assert!(game.matrix() == &block);
+
+    game.tick();
+    assert!(game.matrix() == &block);
+    game.tick();
+    let mut expected = block.clone();
+    expected.blit(&plaintext::parse("OOO").unwrap(), 6, 2, Overflow::Clip);
+    assert!(game.matrix() == &expected, "{}", game.matrix());
+
+    game.tick();
+    let mut expected = block;
+    expected.blit(&plaintext::parse("O\nO\nO").unwrap(), 5, 3, Overflow::Clip);
+    assert!(game.matrix() == &expected, "{}", game.matrix());
+}
+
+#[test]
+fn clears_toggles_and_switches_rules() {
+    let mut game = game_with(
+        "0 place block 0,0\n0 place block 4,4 rotate90\n1 clear 0,0 3x3\n1 toggle 9,9\n1 rule B36/S23",
+    );
+    game.tick();
+
+    let mut expected = board(10, 10, "OO\nOO", 4, 4);
+    expected.set(9, 9, 1);
+    assert!(game.matrix() == &expected, "{}", game.matrix());
+    assert_eq!(game.rule(), &"B36/S23".parse::<Rule>().unwrap());
+}
+
+#[test]
+fn rejects_invalid_events() {
+    for scenario in [
+        "place block 0,0",
+        "1 place nothing 0,0",
+        "1 place block 0,0 rotate45",
+        "1 clear 0,0 3",
+        "1 toggle 0",
+        "1 rule B9",
+        "1 explode",
+        "1 toggle 0,0 twice",
+    ] {
+        assert!(Scenario::parse(scenario).is_err(), "{scenario}");
+    }
+}
@@ -0,0 +1,32 @@
+//! Signals ending a run cleanly with a summary.
+
+#![cfg(feature = "native")]
+
+use std::time::Duration;
+
+use conway_game_of_life::batch::{self, Outcome};
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::shutdown;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use signal_hook::consts::SIGTERM;
+use signal_hook::low_level;
+
+#[test]
+fn a_caught_signal_stops_the_run_and_shows_in_the_summary() {
+    let mut game = GameOfLife::new(Matrix::random(32, 32, 0.3, &mut StdRng::seed_from_u64(7)));
+    assert!(!shutdown::requested());
+
+    shutdown::catch_signals().unwrap();
+    low_level::raise(SIGTERM).unwrap();
+    assert!(shutdown::requested());
+
+    game.run_until(|game| game.is_finished() || game.ticks() >= 100 || shutdown::requested());
+    assert_eq!(game.ticks(), 0);
+
+    let summary = batch::summary(&game, game.ticks(), Duration::from_secs(2));
+    assert!(summary.contains("elapsed: 2.00 s, 0.0 ticks/s on average"));
+    assert!(summary.ends_with("stopped: interrupted\n"));
+    assert_eq!(Outcome::of(&game).exit_code(), 130);
+}
@@ -0,0 +1,101 @@
+//! Independent universes ticked together.
+
+#![cfg(feature = "native")]
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::simulator::Simulator;
+
+fn soup(seed: u64) -> GameOfLife {
+    let mut rng = StdRng::seed_from_u64(seed);
+    GameOfLife::new(Matrix::random(20, 20, 0.4, &mut rng))
+}
+
+#[test]
+fn universes_tick_like_on_their_own() {
+    let mut simulator = Simulator::new();
+    let ids: Vec<_> = (0..8)
+        .map(|seed| simulator.add_universe(soup(seed)))
+        .collect();
+    assert_eq!(simulator.len(), 8);
+
+    for _ in 0..25 {
+        simulator.tick_all();
+    }
+    for (seed, id) in ids.into_iter().enumerate() {
+        let mut alone = soup(seed as u64);
+        for _ in 0..25 {
+            alone.tick();
+        }
+        let universe = simulator.universe(id).unwrap();
+        assert_eq!(universe.ticks(), 25);
+        assert_eq!(universe.state_hash(), alone.state_hash());
+    }
+}
+
+#[test]
+fn finished_universes_are_left_alone_and_taken_out() {
+    let mut simulator = Simulator::new();
+    let mut short = soup(1);
+    short.set_max_ticks(Some(3));
+    let short = simulator.add_universe(short);
+    let mut long = soup(2);
+    long.set_max_ticks(Some(5));
+    let long = simulator.add_universe(long);
+
+    let mut ticked = Vec::new();
+    while !simulator.is_finished() {
+        ticked.push(simulator.tick_all_then(|game| game.ticks()));
+    }
+    assert_eq!(ticked.len(), 5);
+    assert_eq!(ticked[2], [(short, 3), (long, 3)]);
+    assert_eq!(ticked[3], [(long, 4)]);
+
+    let finished = simulator.take_finished();
+    let ids: Vec<_> = finished.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, [short, long]);
+    assert!(simulator.is_empty());
+}
+
+#[test]
+fn ids_are_never_reused() {
+    let mut simulator = Simulator::new();
+    let first = simulator.add_universe(soup(1));
+    let second = simulator.add_universe(soup(2));
+    assert!(simulator.remove_universe(first).is_some());
+    assert!(simulator.remove_universe(first).is_none());
+
+    let third = simulator.add_universe(soup(3));
+    assert_ne!(third, first);
+    assert!(simulator.universe(first).is_none());
+    let ids: Vec<_> = simulator.universes().map(|(id, _)| id).collect();
+    assert_eq!(ids, [second, third]);
+}
+
+#[test]
+fn steps_run_the_step_size_of_every_universe() {
+    let mut simulator = Simulator::new();
+    let mut fast = soup(1);
+    fast.set_step_size(4);
+    let fast = simulator.add_universe(fast);
+    let slow = simulator.add_universe(soup(2));
+
+    simulator.step_all();
+    simulator.step_all();
+    assert_eq!(simulator.universe(fast).unwrap().ticks(), 8);
+    assert_eq!(simulator.universe(slow).unwrap().ticks(), 2);
+
+    for (_, game) in simulator.universes_mut() {
+        game.set_max_ticks(Some(10));
+    }
+    simulator.run_all();
+    let ticks: Vec<_> = simulator
+        .into_universes()
+        .into_iter()
+        .map(|(_, game)| game.ticks())
+        .collect();
+    assert_eq!(ticks, [10, 10]);
+}
@@ -0,0 +1,109 @@
+//! Checkpoints saved as compressed binary snapshots.
+
+#![cfg(feature = "native")]
+
+use std::path::PathBuf;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use conway_game_of_life::checkpoint::{Checkpoint, CHECKPOINT_VERSION};
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::snapshot;
+
+fn temp_path(name: &str, extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "snapshot-{name}-{}.{extension}",
+        std::process::id()
+    ))
+}
+
+fn checkpoint(rows: usize, cols: usize) -> Checkpoint {
+    let mut rng = StdRng::seed_from_u64(7);
+    Checkpoint {
+        version: CHECKPOINT_VERSION,
+        ticks: 1234,
+        seed: 7,
+        edge: Edge::Wrap,
+        rule: "B36/S23".parse::<Rule>().unwrap().with_edge(Edge::Wrap),
+        unbounded: true,
+        origin: (5, 9),
+        matrix: Matrix::random(rows, cols, 0.3, &mut rng),
+    }
+}
+
+fn assert_same(loaded: &Checkpoint, saved: &Checkpoint) {
+    assert_eq!(loaded.ticks, saved.ticks);
+    assert_eq!(loaded.seed, saved.seed);
+    assert_eq!(loaded.edge, saved.edge);
+    assert_eq!(loaded.rule, saved.rule);
+    assert_eq!(loaded.unbounded, saved.unbounded);
+    assert_eq!(loaded.origin, saved.origin);
+    assert!(loaded.matrix == saved.matrix);
+}
+
+#[test]
+fn snapshots_round_trip_every_field() {
+    let saved = checkpoint(37, 53);
+    let mut bytes = Vec::new();
+    snapshot::write(&saved, &mut bytes).unwrap();
+    assert_eq!(&bytes[..4], b"GOLZ");
+
+    let loaded = snapshot::read(bytes.as_slice()).unwrap();
+    assert_same(&loaded, &saved);
+}
+
+#[test]
+fn checkpoints_ending_in_snap_are_saved_as_snapshots() {
+    let saved = checkpoint(256, 256);
+    let snap = temp_path("saved", "snap");
+    let json = temp_path("saved", "json");
+    saved.save(&snap).unwrap();
+    saved.save(&json).unwrap();
+
+    let snap_size = std::fs::metadata(&snap).unwrap().len();
+    let json_size = std::fs::metadata(&json).unwrap().len();
+    assert!(
+        snap_size * 4 < json_size,
+        "{snap_size} vs {json_size} bytes"
+    );
+    assert_same(&Checkpoint::load(&snap).unwrap(), &saved);
+
+    // snapshots are told apart by their contents, not their name
+    let renamed = temp_path("renamed", "json");
+    std::fs::rename(&snap, &renamed).unwrap();
+    assert_same(&Checkpoint::load(&renamed).unwrap(), &saved);
+
+    std::fs::remove_file(&renamed).unwrap();
+    std::fs::remove_file(&json).unwrap();
+}
+
+#[test]
+fn empty_boards_compress_to_almost_nothing() {
+    let mut saved = checkpoint(1, 1);
+    saved.matrix = Matrix::new(1000, 1000);
+    let mut bytes = Vec::new();
+    snapshot::write(&saved, &mut bytes).unwrap();
+    assert!(bytes.len() < 1000, "{} bytes", bytes.len());
+    assert!(snapshot::read(bytes.as_slice()).unwrap().matrix == saved.matrix);
+}
+
+#[test]
+fn broken_snapshots_are_rejected() {
+    let mut bytes = Vec::new();
+    snapshot::write(&checkpoint(8, 8), &mut bytes).unwrap();
+
+    let mut newer = bytes.clone();
+    newer[4..8].copy_from_slice(&(snapshot::SNAPSHOT_VERSION + 1).to_le_bytes());
+    let err = snapshot::read(newer.as_slice()).err().unwrap();
+    assert!(err.to_string().contains("newer"), "{err}");
+
+    let mut resized = bytes.clone();
+    resized[8..16].copy_from_slice(&9u64.to_le_bytes());
+    assert!(snapshot::read(resized.as_slice()).is_err());
+
+    assert!(snapshot::read(&bytes[..bytes.len() / 2]).is_err());
+    assert!(snapshot::read(&b"{\"version\":2}"[..]).is_err());
+}
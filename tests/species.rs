@@ -0,0 +1,59 @@
+//! Several species sharing a rule.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::game_of_life::GameOfLife;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::species::{self, SpeciesEngine};
+
+#[test]
+fn newborn_cells_take_the_majority_species() {
+    // cell, then neighbors clockwise from the north
+    let rule = Rule::CONWAY;
+    assert_eq!(
+        species::next_state(&[0, 1, 2, 1, 0, 0, 0, 0, 0], 2, &rule),
+        1
+    );
+    assert_eq!(
+        species::next_state(&[0, 2, 0, 2, 0, 1, 0, 0, 0], 2, &rule),
+        2
+    );
+    // survivors keep their species
+    assert_eq!(
+        species::next_state(&[2, 1, 1, 0, 0, 0, 0, 0, 0], 2, &rule),
+        2
+    );
+    assert_eq!(
+        species::next_state(&[2, 1, 0, 0, 0, 0, 0, 0, 0], 2, &rule),
+        0
+    );
+    // three parents of different species have a child of the fourth
+    assert_eq!(
+        species::next_state(&[0, 1, 0, 2, 0, 4, 0, 0, 0], 4, &rule),
+        3
+    );
+}
+
+#[test]
+fn species_tick_like_life_when_told_apart_by_liveness() {
+    let mut matrix = Matrix::new(8, 8);
+    for (row, col, species) in [(1, 2, 1), (2, 3, 2), (3, 1, 2), (3, 2, 1), (3, 3, 2)] {
+        matrix.set(row, col, species);
+    }
+    let mut game = GameOfLife::new(matrix);
+    game.set_engine(Box::new(SpeciesEngine::new(2)));
+
+    for _ in 0..4 {
+        game.tick();
+    }
+    // the glider moved one cell down and right, its cells keep two species
+    let live: Vec<(usize, usize)> = (0..64)
+        .map(|idx| (idx / 8, idx % 8))
+        .filter(|&(row, col)| game.matrix().get(row, col) != 0)
+        .collect();
+    assert_eq!(live, [(2, 3), (3, 4), (4, 2), (4, 3), (4, 4)]);
+    assert!(live
+        .iter()
+        .all(|&(row, col)| (1..=2).contains(&game.matrix().get(row, col))));
+}
@@ -0,0 +1,30 @@
+//! Population statistics of a simulation.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::stats::{Generation, Stats};
+
+fn stats(populations: &[usize]) -> Stats {
+    let mut stats = Stats::new(&Matrix::new(0, 0));
+    for (tick, &population) in populations.iter().enumerate() {
+        stats.push(Generation {
+            tick: tick + 1,
+            population,
+            births: 0,
+            deaths: 0,
+        });
+    }
+    stats
+}
+
+#[test]
+fn sparkline_scales_the_recent_populations() {
+    let stats = stats(&[100, 10, 17, 24, 80]);
+
+    assert_eq!(stats.sparkline(4), "▁▁▂█");
+    // the initial generation of an empty board has no live cells
+    assert_eq!(stats.sparkline(6), "▁█▁▂▂▆");
+    assert_eq!(stats.sparkline(0), "");
+    assert_eq!(Stats::new(&Matrix::new(2, 2)).sparkline(3), "▁");
+}
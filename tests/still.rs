@@ -0,0 +1,59 @@
+//! Still lifes and oscillators found by brute force.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::analyze::Kind;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::still;
+
+#[test]
+fn enumerates_every_object_of_small_boxes_once() {
+    let discoveries = still::enumerate(4, &Rule::CONWAY, 2);
+    let apgcodes: Vec<&str> = discoveries
+        .iter()
+        .map(|discovery| discovery.apgcode.as_str())
+        .collect();
+    // the block, tub, boat, beehive, loaf and pond, the blinker, toad and
+    // beacon, and the rest of the still lifes and oscillators of up to 4x4
+    for apgcode in [
+        "xs4_33", "xs4_252", "xs5_253", "xs6_696", "xs7_2596", "xs8_6996", "xp2_7", "xp2_7e",
+        "xp2_318c",
+    ] {
+        assert!(apgcodes.contains(&apgcode), "{apgcode} in {apgcodes:?}");
+    }
+    assert_eq!(apgcodes.len(), 17);
+    // smaller boxes first
+    assert_eq!(apgcodes[0], "xs4_33");
+
+    let still_lifes = still::enumerate(4, &Rule::CONWAY, 1);
+    assert_eq!(still_lifes.len(), 13);
+    assert!(still_lifes
+        .iter()
+        .all(|discovery| discovery.kind == Kind::StillLife));
+}
+
+#[test]
+fn searches_under_other_rules() {
+    // nothing is born and nothing dies, so every board is still, down to
+    // a single cell
+    let rule: Rule = "B/S012345678".parse().unwrap();
+    let discoveries = still::enumerate(2, &rule, 2);
+    assert!(discoveries
+        .iter()
+        .any(|discovery| discovery.apgcode == "xs1_1"));
+}
+
+#[test]
+fn keeps_the_objects_random_soups_settle_into() {
+    let discoveries = still::search_soups(12, &Rule::CONWAY, 2, (0, 200), 0.5, 1000);
+    let apgcodes: Vec<&str> = discoveries
+        .iter()
+        .map(|discovery| discovery.apgcode.as_str())
+        .collect();
+    assert!(apgcodes.contains(&"xs4_33"));
+    assert!(apgcodes.contains(&"xp2_7"));
+    assert!(discoveries.iter().all(|discovery| matches!(
+        discovery.kind,
+        Kind::StillLife | Kind::Oscillator { period: 2 }
+    )));
+}
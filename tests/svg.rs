@@ -0,0 +1,53 @@
+//! Boards drawn as SVG.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::render::image::ImageStyle;
+use conway_game_of_life::render::svg::write_svg;
+use conway_game_of_life::render::Color;
+
+fn svg(matrix: &Matrix, style: &ImageStyle) -> String {
+    let mut out = Vec::new();
+    write_svg(&mut out, matrix, style).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn runs_of_live_cells_are_single_rectangles() {
+    let matrix = Matrix::from_vec(2, 4, vec![0, 1, 1, 1, 1, 0, 0, 1]).unwrap();
+    let style = ImageStyle {
+        cell_size: 10,
+        ..ImageStyle::default()
+    };
+    let svg = svg(&matrix, &style);
+
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(r#"viewBox="0 0 40 20""#));
+    assert!(svg.contains(r##"<rect width="40" height="20" fill="#000000"/>"##));
+    assert!(svg.contains(r##"<g fill="#ffffff">"##));
+    let cells: Vec<&str> = svg.lines().filter(|line| line.contains(" x=")).collect();
+    assert_eq!(
+        cells,
+        [
+            r#"<rect x="10" y="0" width="30" height="10"/>"#,
+            r#"<rect x="0" y="10" width="10" height="10"/>"#,
+            r#"<rect x="30" y="10" width="10" height="10"/>"#,
+        ]
+    );
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn stroked_cells_are_outlined_one_by_one() {
+    let matrix = Matrix::from_vec(1, 3, vec![1, 1, 0]).unwrap();
+    let style = ImageStyle {
+        stroke: Some(Color::new(128, 128, 128)),
+        stroke_width: 0.5,
+        ..ImageStyle::default()
+    };
+    let svg = svg(&matrix, &style);
+
+    assert!(svg.contains(r##"<g fill="#ffffff" stroke="#808080" stroke-width="0.5">"##));
+    assert_eq!(svg.matches(r#"width="8" height="8"/>"#).count(), 2);
+}
@@ -0,0 +1,73 @@
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::symmetry::Symmetry;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn soup(rows: usize, cols: usize, symmetry: Symmetry) -> Matrix {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut matrix = Matrix::random(rows, cols, 0.5, &mut rng);
+    symmetry.apply(&mut matrix);
+    matrix
+}
+
+#[test]
+fn soups_are_their_own_images() {
+    for size in [15, 16] {
+        let c2 = soup(size, size + 3, Symmetry::C2);
+        assert!(c2 == c2.rotate180());
+        assert!(c2 != c2.flip_h());
+
+        let c4 = soup(size, size, Symmetry::C4);
+        assert!(c4 == c4.rotate90());
+        assert!(c4 != c4.flip_v());
+
+        let d4 = soup(size + 3, size, Symmetry::D4);
+        assert!(d4 == d4.flip_h() && d4 == d4.flip_v());
+
+        let d8 = soup(size, size, Symmetry::D8);
+        assert!(d8 == d8.flip_h() && d8 == d8.rotate90());
+    }
+}
+
+#[test]
+fn the_first_cell_of_every_orbit_is_kept() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let random = Matrix::random(16, 16, 0.5, &mut rng);
+    let d8 = soup(16, 16, Symmetry::D8);
+
+    for row in 0..8 {
+        for col in row..8 {
+            assert_eq!(d8.get(row, col), random.get(row, col));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn fill_regions_are_centered() {
+    use clap::Parser;
+    use conway_game_of_life::game_of_life::{GameOfLife, GameOfLifeArgs};
+
+    let args = GameOfLifeArgs::parse_from([
+        "gol",
+        "--rows",
+        "20",
+        "--cols",
+        "30",
+        "--seed",
+        "1",
+        "--fill-prob",
+        "1",
+        "--fill-region",
+        "6x10",
+        "--symmetry",
+        "d4",
+    ]);
+    let game = GameOfLife::from_args(&args);
+    let bounds = game.matrix().bounding_box().unwrap();
+    assert_eq!(
+        (bounds.row, bounds.col, bounds.rows, bounds.cols),
+        (7, 10, 6, 10)
+    );
+    assert_eq!(game.matrix().count_live(), 60);
+}
@@ -0,0 +1,45 @@
+//! Drawing frames in the terminal, with escape codes or the console API.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::render::terminal::{console_color, TerminalRenderer};
+use conway_game_of_life::render::Frame;
+use crossterm::style::Color;
+
+#[test]
+fn only_the_glyphs_that_changed_are_redrawn() {
+    let mut out = Vec::new();
+    let mut renderer = TerminalRenderer::new(&mut out);
+    let mut frame = Frame::default();
+    frame.push_text("ab\ncd");
+    renderer.draw(frame).unwrap();
+
+    let mut frame = Frame::default();
+    frame.push_text("ab\nce");
+    renderer.draw(frame).unwrap();
+    drop(renderer);
+
+    // the first frame clears the screen, the second only rewrites its `e`
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("\x1b[2J"));
+    let second = &out[out.find('d').unwrap() + 1..];
+    assert!(second.contains('e'));
+    assert!(!second.contains(['a', 'b', 'c', 'd']));
+}
+
+#[test]
+fn palette_colors_map_to_the_closest_console_color() {
+    assert_eq!(console_color(1), Color::DarkRed);
+    assert_eq!(console_color(15), Color::White);
+    // pure red, green and blue of the color cube
+    assert_eq!(console_color(196), Color::Red);
+    assert_eq!(console_color(46), Color::Green);
+    assert_eq!(console_color(21), Color::Blue);
+    assert_eq!(console_color(88), Color::DarkRed);
+    assert_eq!(console_color(16), Color::Black);
+    assert_eq!(console_color(231), Color::White);
+    // the grayscale ramp
+    assert_eq!(console_color(232), Color::Black);
+    assert_eq!(console_color(244), Color::Grey);
+    assert_eq!(console_color(255), Color::White);
+}
@@ -0,0 +1,63 @@
+//! Boards stored tile after tile must hold and tick the same cells as the
+//! usual row-major boards.
+
+#![cfg(feature = "native")]
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::EngineKind;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::tiled::TiledMatrix;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn cells_are_reached_like_in_a_matrix() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (rows, cols) in [(1, 1), (64, 64), (65, 63), (130, 200)] {
+        let matrix = Matrix::random(rows, cols, 0.5, &mut rng);
+        let mut tiled = TiledMatrix::from_matrix(&matrix);
+        assert_eq!((tiled.row_count(), tiled.col_count()), (rows, cols));
+        for row in 0..rows {
+            for col in 0..cols {
+                assert_eq!(tiled.get(row, col), matrix.get(row, col));
+            }
+        }
+        assert!(tiled.to_matrix() == matrix);
+
+        let (row, col) = (rng.gen_range(0..rows), rng.gen_range(0..cols));
+        let value = 1 - matrix.get(row, col);
+        tiled.set(row, col, value);
+        let mut expected = matrix.clone();
+        expected.set(row, col, value);
+        assert!(tiled.to_matrix() == expected);
+    }
+}
+
+#[test]
+fn tiled_engine_matches_serial_across_tiles() {
+    let mut rng = StdRng::seed_from_u64(1);
+    // tiles are cut on both sides, and the border between them is crossed
+    let matrix = Matrix::random(150, 200, 0.3, &mut rng);
+
+    for edge in [Edge::Dead, Edge::Alive, Edge::Wrap, Edge::Mirror] {
+        let rule = Rule::CONWAY.with_edge(edge);
+        let (mut serial, mut tiled) = (
+            EngineKind::Serial.build(None, None),
+            EngineKind::Tiled.build(None, None),
+        );
+        let (mut expected, mut actual) = (matrix.clone(), matrix.clone());
+        for generation in 0..20 {
+            let mut next = Matrix::new(150, 200);
+            serial.step(&expected, &mut next, &rule);
+            expected = next;
+            let mut next = Matrix::new(150, 200);
+            tiled.step(&actual, &mut next, &rule);
+            actual = next;
+            assert!(
+                actual == expected,
+                "tiled differs from serial at generation {generation} with {edge:?} edges"
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Time-lapses drawing and recording every Nth generation only.
+
+#![cfg(feature = "std")]
+
+use std::fs::File;
+
+use clap::Parser;
+
+use conway_game_of_life::engine::EngineKind;
+use conway_game_of_life::game_of_life::{GameOfLife, GameOfLifeArgs};
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::plaintext;
+
+fn blinker() -> GameOfLife {
+    GameOfLife::new(plaintext::parse(".....\n..O..\n..O..\n..O..\n.....").unwrap())
+}
+
+#[test]
+fn steps_run_up_to_the_next_generation_drawn() {
+    let mut game = blinker();
+    game.set_step_size(3);
+    game.set_render_every(7);
+    assert!(game.is_rendered());
+
+    game.step();
+    assert_eq!(game.ticks(), 7);
+    game.step();
+    assert_eq!(game.ticks(), 14);
+
+    // a generation run in between doesn't shift the generations drawn
+    game.tick();
+    assert!(!game.is_rendered());
+    game.step();
+    assert_eq!(game.ticks(), 21);
+    assert!(game.is_rendered());
+
+    game.set_max_ticks(Some(25));
+    game.step();
+    assert_eq!(game.ticks(), 25);
+
+    game.set_render_every(0);
+    assert_eq!(game.render_every(), 1);
+}
+
+#[test]
+fn gifs_get_a_frame_per_generation_drawn() {
+    let path = std::env::temp_dir().join(format!("timelapse-{}.gif", std::process::id()));
+    let args = GameOfLifeArgs::parse_from([
+        "gol",
+        "--rows",
+        "8",
+        "--cols",
+        "8",
+        "--seed",
+        "3",
+        "--record",
+        path.to_str().unwrap(),
+        "--record-frames",
+        "100",
+        "--render-every",
+        "10",
+    ]);
+    let mut game = GameOfLife::from_args(&args);
+    assert_eq!(game.render_every(), 10);
+    while game.ticks() < 35 {
+        game.tick();
+    }
+    assert_eq!(game.ticks(), 35);
+    drop(game);
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+    let mut frames = 0;
+    while decoder.read_next_frame().unwrap().is_some() {
+        frames += 1;
+    }
+    // the initial generation and generations 10, 20 and 30
+    assert_eq!(frames, 4);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn forks_keep_the_pace_of_the_time_lapse() {
+    let mut game = GameOfLife::new(Matrix::new(4, 4));
+    game.set_render_every(4);
+    let fork = game.fork(*game.rule(), EngineKind::Serial.build(None, None));
+    assert_eq!(fork.render_every(), 4);
+}
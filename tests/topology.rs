@@ -0,0 +1,106 @@
+//! Boards on grids whose edges are joined as Golly's topologies.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::{Serial, TickEngine};
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::topology::{Join, Topology, TopologyEngine};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn topology(s: &str) -> Topology {
+    s.parse().unwrap()
+}
+
+#[test]
+fn descriptors_round_trip() {
+    for descriptor in [
+        "P10,5", "T10,5", "T10+3,5", "T10,5-2", "K10*,5", "K10,5*", "C10,5", "S7",
+    ] {
+        assert_eq!(topology(descriptor).to_string(), descriptor);
+    }
+    let shifted = topology("t100+20,50");
+    assert_eq!((shifted.cols, shifted.rows), (100, 50));
+    assert_eq!(shifted.horizontal, Join::Shifted(20));
+
+    for invalid in [
+        "",
+        "X10,5",
+        "T10",
+        "T0,5",
+        "T10+1,5+1",
+        "K10,5",
+        "K10*,5*",
+        "P10+1,5",
+        "S5,5",
+    ] {
+        assert!(invalid.parse::<Topology>().is_err(), "{invalid:?}");
+    }
+}
+
+#[test]
+fn cells_beyond_the_edges_map_onto_the_board() {
+    let shifted = topology("T10+3,5");
+    assert_eq!(shifted.map(5, 0), Some((0, 3)));
+    assert_eq!(shifted.map(-1, 0), Some((4, 7)));
+    assert_eq!(shifted.map(2, 10), Some((2, 0)));
+    assert_eq!(shifted.map(5, 9), Some((0, 2)));
+
+    let klein = topology("K10*,5");
+    assert_eq!(klein.map(-1, 2), Some((4, 7)));
+    assert_eq!(klein.map(3, -1), Some((3, 9)));
+
+    let cross = topology("C10,5");
+    assert_eq!(cross.map(1, 10), Some((3, 0)));
+
+    let sphere = topology("S5");
+    assert_eq!(sphere.map(-1, 3), Some((3, 0)));
+    assert_eq!(sphere.map(3, -1), Some((0, 3)));
+    assert_eq!(sphere.map(5, 1), Some((1, 4)));
+    assert_eq!(sphere.map(1, 5), Some((4, 1)));
+    assert_eq!(sphere.map(-1, -1), None);
+
+    assert_eq!(topology("P10,5").map(-1, 0), None);
+}
+
+#[test]
+fn plain_tori_and_planes_tick_like_the_edge_modes() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for (descriptor, edge) in [("T13,9", Edge::Wrap), ("P13,9", Edge::Dead)] {
+        let mut engine = TopologyEngine::new(topology(descriptor));
+        let mut serial = Serial::new();
+        let rule = Rule::CONWAY.with_edge(edge);
+        let mut expected = Matrix::random(9, 13, 0.4, &mut rng);
+        let mut actual = expected.clone();
+
+        for _ in 0..8 {
+            let mut next = Matrix::new(9, 13);
+            serial.step(&expected, &mut next, &rule);
+            expected = next;
+            let mut next = Matrix::new(9, 13);
+            engine.step(&actual, &mut next, &Rule::CONWAY);
+            actual = next;
+            assert_eq!(actual.as_slice(), expected.as_slice(), "{descriptor}");
+        }
+    }
+}
+
+#[test]
+fn gliders_come_back_shifted_on_a_shifted_torus() {
+    let glider = Matrix::from_vec(3, 3, vec![0, 1, 0, 0, 0, 1, 1, 1, 1]).unwrap();
+    let mut board = Matrix::new(8, 8);
+    board.blit(&glider, 0, 0, Default::default());
+    let mut engine = TopologyEngine::new(topology("T8+2,8"));
+
+    // a glider crosses the board in 32 generations, one cell a quarter
+    for _ in 0..32 {
+        let mut next = Matrix::new(8, 8);
+        engine.step(&board, &mut next, &Rule::CONWAY);
+        board = next;
+    }
+    let mut expected = Matrix::new(8, 8);
+    expected.blit(&glider, 0, 2, Default::default());
+    assert_eq!(board.as_slice(), expected.as_slice());
+}
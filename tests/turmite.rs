@@ -0,0 +1,95 @@
+//! Turmites walking over the board.
+
+#![cfg(feature = "std")]
+
+use conway_game_of_life::edge::Edge;
+use conway_game_of_life::engine::TickEngine;
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::rule::Rule;
+use conway_game_of_life::turmite::{Ant, Direction, Turmite, TurmiteEngine, Turn};
+
+fn ant(state: u8, direction: Direction) -> Option<Ant> {
+    Some(Ant { state, direction })
+}
+
+fn tick(engine: &mut TurmiteEngine, matrix: &mut Matrix, edge: Edge) {
+    let mut next = Matrix::new(matrix.row_count(), matrix.col_count());
+    engine.step(matrix, &mut next, &Rule::CONWAY.with_edge(edge));
+    *matrix = next;
+}
+
+#[test]
+fn langtons_ant_turns_right_on_white_and_left_on_black() {
+    let turmite = Turmite::langton();
+    let mut matrix = Matrix::new(5, 5);
+    matrix.set(2, 2, turmite.encode(0, ant(0, Direction::North)));
+    let mut engine = TurmiteEngine::new(turmite.clone());
+
+    tick(&mut engine, &mut matrix, Edge::Dead);
+    assert_eq!(turmite.decode(matrix.get(2, 2)), (1, None));
+    assert_eq!(
+        turmite.decode(matrix.get(2, 3)),
+        (0, ant(0, Direction::East))
+    );
+
+    // around a square back to the black start cell, where it turns left
+    for _ in 0..3 {
+        tick(&mut engine, &mut matrix, Edge::Dead);
+    }
+    assert_eq!(
+        turmite.decode(matrix.get(2, 2)),
+        (1, ant(0, Direction::North))
+    );
+    tick(&mut engine, &mut matrix, Edge::Dead);
+    assert_eq!(turmite.decode(matrix.get(2, 2)), (0, None));
+    assert_eq!(
+        turmite.decode(matrix.get(2, 1)),
+        (0, ant(0, Direction::West))
+    );
+}
+
+#[test]
+fn golly_tables_match_turn_strings() {
+    let table: Turmite = "{{{1, 2, 0}, {0, 8, 0}}}".parse().unwrap();
+    assert_eq!(table, Turmite::langton());
+
+    let fibonacci: Turmite = "{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}".parse().unwrap();
+    assert_eq!((fibonacci.colors(), fibonacci.states()), (2, 2));
+    assert_eq!(fibonacci.transition(1, 1).turn, Turn::None);
+    assert_eq!(fibonacci.transition(1, 1).state, 0);
+
+    assert!("RLX".parse::<Turmite>().is_err());
+    assert!("{{{1,3,0},{0,8,0}}}".parse::<Turmite>().is_err());
+    assert!("{{{2,2,0},{0,8,0}}}".parse::<Turmite>().is_err());
+    assert!("{{{1,2,0}},{{0,8,0},{1,2,0}}}".parse::<Turmite>().is_err());
+}
+
+#[test]
+fn ants_wrap_around_or_turn_about_at_the_edges() {
+    let turmite: Turmite = "N".parse().unwrap();
+    let mut engine = TurmiteEngine::new(turmite.clone());
+
+    let mut matrix = Matrix::new(3, 3);
+    matrix.set(0, 1, turmite.encode(0, ant(0, Direction::North)));
+    tick(&mut engine, &mut matrix, Edge::Wrap);
+    assert_eq!(turmite.decode(matrix.get(2, 1)).1, ant(0, Direction::North));
+
+    let mut matrix = Matrix::new(3, 3);
+    matrix.set(0, 1, turmite.encode(0, ant(0, Direction::North)));
+    tick(&mut engine, &mut matrix, Edge::Dead);
+    assert_eq!(turmite.decode(matrix.get(0, 1)).1, ant(0, Direction::South));
+}
+
+#[test]
+fn ants_are_placed_only_on_boards_without_any() {
+    let turmite = Turmite::langton();
+    let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+
+    let mut matrix = Matrix::new(4, 4);
+    turmite.place_ants(&mut matrix, 1, &mut rng);
+    assert_eq!(turmite.decode(matrix.get(2, 2)).1, ant(0, Direction::North));
+
+    let placed = matrix.clone();
+    turmite.place_ants(&mut matrix, 3, &mut rng);
+    assert_eq!(matrix.as_slice(), placed.as_slice());
+}
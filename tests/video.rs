@@ -0,0 +1,42 @@
+//! Generations piped as raw RGB frames into a video encoder.
+
+#![cfg(all(feature = "std", unix))]
+
+use std::process::Command;
+
+use conway_game_of_life::matrix::Matrix;
+use conway_game_of_life::render::image::ImageStyle;
+use conway_game_of_life::render::video::VideoRecorder;
+
+#[test]
+fn frames_are_piped_as_raw_rgb() {
+    let path = std::env::temp_dir().join(format!("video-{}.rgb", std::process::id()));
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(format!("cat > '{}'", path.display()));
+
+    let blinker = Matrix::from_vec(1, 3, vec![1, 1, 1]).unwrap();
+    let style = ImageStyle {
+        cell_size: 2,
+        ..ImageStyle::default()
+    };
+    let mut video = VideoRecorder::spawn(command, &blinker, style).unwrap();
+    video.record(&blinker).unwrap();
+    video.record(&Matrix::new(1, 3)).unwrap();
+    assert!(video.record(&Matrix::new(3, 1)).is_err());
+    video.finish().unwrap();
+
+    let frames = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let frame_len = 6 * 2 * 3;
+    assert_eq!(frames.len(), 2 * frame_len);
+    assert!(frames[..frame_len].iter().all(|&byte| byte == 255));
+    assert!(frames[frame_len..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn failing_encoders_are_reported() {
+    let blinker = Matrix::from_vec(1, 3, vec![1, 1, 1]).unwrap();
+    let video =
+        VideoRecorder::spawn(Command::new("false"), &blinker, ImageStyle::default()).unwrap();
+    assert!(video.finish().is_err());
+}